@@ -1,6 +1,29 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20241215_000002_create_inventory;
+mod m20241215_000003_product_status_enum;
+mod m20241215_000004_soft_delete_products;
+mod m20241215_000005_create_product_images;
+mod m20241215_000006_add_product_sku;
+mod m20241215_000007_create_price_history;
+mod m20241215_000008_create_reviews;
+mod m20241215_000009_create_product_attributes;
+mod m20241215_000010_create_translations;
+mod m20241215_000011_create_product_relations;
+mod m20241215_000012_create_wishlists;
+mod m20241215_000013_create_product_alerts;
+mod m20241215_000014_add_row_versions;
+mod m20241215_000015_create_digital_products;
+mod m20241215_000016_create_bundles;
+mod m20241215_000017_create_vendors;
+mod m20241215_000018_create_guest_carts;
+mod m20241215_000019_add_guest_checkout;
+mod m20241215_000020_add_cart_quantity_rules;
+mod m20241215_000021_add_cart_price_snapshots;
+mod m20241215_000022_create_carts;
+mod m20241215_000023_create_cart_reminders;
+mod m20241215_000024_create_coupons;
 
 pub struct Migrator;
 
@@ -9,6 +32,29 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20241215_000002_create_inventory::Migration),
+            Box::new(m20241215_000003_product_status_enum::Migration),
+            Box::new(m20241215_000004_soft_delete_products::Migration),
+            Box::new(m20241215_000005_create_product_images::Migration),
+            Box::new(m20241215_000006_add_product_sku::Migration),
+            Box::new(m20241215_000007_create_price_history::Migration),
+            Box::new(m20241215_000008_create_reviews::Migration),
+            Box::new(m20241215_000009_create_product_attributes::Migration),
+            Box::new(m20241215_000010_create_translations::Migration),
+            Box::new(m20241215_000011_create_product_relations::Migration),
+            Box::new(m20241215_000012_create_wishlists::Migration),
+            Box::new(m20241215_000013_create_product_alerts::Migration),
+            Box::new(m20241215_000014_add_row_versions::Migration),
+            Box::new(m20241215_000015_create_digital_products::Migration),
+            Box::new(m20241215_000016_create_bundles::Migration),
+            Box::new(m20241215_000017_create_vendors::Migration),
+            Box::new(m20241215_000018_create_guest_carts::Migration),
+            Box::new(m20241215_000019_add_guest_checkout::Migration),
+            Box::new(m20241215_000020_add_cart_quantity_rules::Migration),
+            Box::new(m20241215_000021_add_cart_price_snapshots::Migration),
+            Box::new(m20241215_000022_create_carts::Migration),
+            Box::new(m20241215_000023_create_cart_reminders::Migration),
+            Box::new(m20241215_000024_create_coupons::Migration),
         ]
     }
 }
@@ -0,0 +1,230 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(LocationKind::Enum)
+                    .values([LocationKind::Warehouse, LocationKind::Shop])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(MovementReason::Enum)
+                    .values([
+                        MovementReason::Adjustment,
+                        MovementReason::Transfer,
+                        MovementReason::Fulfilment,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create Locations Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Locations::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Locations::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Locations::Code).string().not_null().unique_key())
+                    .col(ColumnDef::new(Locations::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Locations::Kind)
+                            .custom(LocationKind::Enum)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Locations::Priority).integer().not_null().default(0))
+                    .col(ColumnDef::new(Locations::IsActive).boolean().not_null().default(true))
+                    .col(ColumnDef::new(Locations::CreatedAt).timestamp_with_time_zone().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create StockLevels Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockLevels::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(StockLevels::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(StockLevels::LocationId).uuid().not_null())
+                    .col(ColumnDef::new(StockLevels::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(StockLevels::Quantity).integer().not_null().default(0))
+                    .col(ColumnDef::new(StockLevels::UpdatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(StockLevels::Table, StockLevels::LocationId)
+                            .to(Locations::Table, Locations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(StockLevels::Table, StockLevels::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // สต็อกของสินค้าหนึ่งตัวมีได้แถวเดียวต่อหนึ่งคลัง
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_stock_levels_location_product")
+                    .table(StockLevels::Table)
+                    .col(StockLevels::LocationId)
+                    .col(StockLevels::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create StockMovements Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockMovements::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(StockMovements::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(StockMovements::LocationId).uuid().not_null())
+                    .col(ColumnDef::new(StockMovements::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(StockMovements::QuantityChange).integer().not_null())
+                    .col(
+                        ColumnDef::new(StockMovements::Reason)
+                            .custom(MovementReason::Enum)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(StockMovements::ReferenceId).uuid())
+                    .col(ColumnDef::new(StockMovements::Note).string())
+                    .col(ColumnDef::new(StockMovements::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(StockMovements::Table, StockMovements::LocationId)
+                            .to(Locations::Table, Locations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(StockMovements::Table, StockMovements::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // คลังที่ใช้จัดส่งของแต่ละรายการในคำสั่งซื้อ
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItems::Table)
+                    .add_column(ColumnDef::new(OrderItems::LocationId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_order_items_location_id")
+                            .from_tbl(OrderItems::Table)
+                            .from_col(OrderItems::LocationId)
+                            .to_tbl(Locations::Table)
+                            .to_col(Locations::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItems::Table)
+                    .drop_foreign_key(Alias::new("fk_order_items_location_id"))
+                    .drop_column(OrderItems::LocationId)
+                    .to_owned(),
+            )
+            .await?;
+        manager.drop_table(Table::drop().table(StockMovements::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(StockLevels::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(Locations::Table).to_owned()).await?;
+        manager.drop_type(Type::drop().name(MovementReason::Enum).to_owned()).await?;
+        manager.drop_type(Type::drop().name(LocationKind::Enum).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum Locations {
+    Table,
+    Id,
+    Code,
+    Name,
+    Kind,
+    Priority,
+    IsActive,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum StockLevels {
+    Table,
+    Id,
+    LocationId,
+    ProductId,
+    Quantity,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum StockMovements {
+    Table,
+    Id,
+    LocationId,
+    ProductId,
+    QuantityChange,
+    Reason,
+    ReferenceId,
+    Note,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum LocationKind {
+    #[iden = "location_kind"]
+    Enum,
+    Warehouse,
+    Shop,
+}
+
+#[derive(Iden)]
+pub enum MovementReason {
+    #[iden = "movement_reason"]
+    Enum,
+    Adjustment,
+    Transfer,
+    Fulfilment,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum OrderItems {
+    Table,
+    LocationId,
+}
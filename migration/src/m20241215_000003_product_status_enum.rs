@@ -0,0 +1,93 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(ProductStatus::Enum)
+                    .values([
+                        ProductStatus::Available,
+                        ProductStatus::Reserved,
+                        ProductStatus::Sold,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // แปลงคอลัมน์ status เดิมที่เป็น string ให้เป็น enum
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE products \
+                 ALTER COLUMN status DROP DEFAULT, \
+                 ALTER COLUMN status TYPE product_status USING status::product_status, \
+                 ALTER COLUMN status SET DEFAULT 'available'",
+            )
+            .await?;
+
+        // สินค้าชิ้นเดียวในโลก จะถูกจองตอนสั่งซื้อและขายแล้วตอนชำระเงิน
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(
+                        ColumnDef::new(Products::IsOneOfAKind)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_column(Products::IsOneOfAKind)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE products \
+                 ALTER COLUMN status DROP DEFAULT, \
+                 ALTER COLUMN status TYPE varchar USING status::text, \
+                 ALTER COLUMN status SET DEFAULT 'available'",
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(ProductStatus::Enum).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum ProductStatus {
+    #[iden = "product_status"]
+    Enum,
+    Available,
+    Reserved,
+    Sold,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    #[iden = "is_one_of_a_kind"]
+    IsOneOfAKind,
+}
@@ -0,0 +1,133 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(ColumnDef::new(Products::DeletedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        // เดิม FK เป็น ON DELETE CASCADE ทำให้ลบสินค้าแล้วรายการในคำสั่งซื้อเก่าหายไปด้วย
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItems::Table)
+                    .drop_foreign_key(Alias::new("order_items_product_id_fkey"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_order_items_product_id")
+                            .from_tbl(OrderItems::Table)
+                            .from_col(OrderItems::ProductId)
+                            .to_tbl(Products::Table)
+                            .to_col(Products::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // บทบาทผู้ใช้ สำหรับ endpoint ของผู้ดูแลระบบ
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(UserRole::Enum)
+                    .values([UserRole::Customer, UserRole::Admin])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(
+                        ColumnDef::new(Users::Role)
+                            .custom(UserRole::Enum)
+                            .not_null()
+                            .default("customer"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::Role)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(Type::drop().name(UserRole::Enum).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItems::Table)
+                    .drop_foreign_key(Alias::new("fk_order_items_product_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("order_items_product_id_fkey")
+                            .from_tbl(OrderItems::Table)
+                            .from_col(OrderItems::ProductId)
+                            .to_tbl(Products::Table)
+                            .to_col(Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_column(Products::DeletedAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum UserRole {
+    #[iden = "user_role"]
+    Enum,
+    Customer,
+    Admin,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+    DeletedAt,
+}
+
+#[derive(Iden)]
+enum OrderItems {
+    Table,
+    ProductId,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Role,
+}
@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create ProductImages Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductImages::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ProductImages::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(ProductImages::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(ProductImages::Position).integer().not_null().default(0))
+                    .col(ColumnDef::new(ProductImages::IsPrimary).boolean().not_null().default(false))
+                    .col(ColumnDef::new(ProductImages::AltText).string())
+                    .col(ColumnDef::new(ProductImages::ContentType).string().not_null())
+                    .col(ColumnDef::new(ProductImages::SizeBytes).big_integer().not_null())
+                    .col(ColumnDef::new(ProductImages::Width).integer().not_null())
+                    .col(ColumnDef::new(ProductImages::Height).integer().not_null())
+                    .col(ColumnDef::new(ProductImages::OriginalKey).string().not_null())
+                    .col(ColumnDef::new(ProductImages::MediumKey).string().not_null())
+                    .col(ColumnDef::new(ProductImages::ThumbnailKey).string().not_null())
+                    .col(ColumnDef::new(ProductImages::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductImages::Table, ProductImages::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // สินค้าหนึ่งตัวมีรูปหลักได้รูปเดียว
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX idx_product_images_primary \
+                 ON product_images (product_id) WHERE is_primary",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(ProductImages::Table).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum ProductImages {
+    Table,
+    Id,
+    ProductId,
+    Position,
+    IsPrimary,
+    AltText,
+    ContentType,
+    SizeBytes,
+    Width,
+    Height,
+    OriginalKey,
+    MediumKey,
+    ThumbnailKey,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
@@ -0,0 +1,172 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(PriceChangeReason::Enum)
+                    .values([
+                        PriceChangeReason::Manual,
+                        PriceChangeReason::Import,
+                        PriceChangeReason::SaleStart,
+                        PriceChangeReason::SaleEnd,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(PriceScheduleStatus::Enum)
+                    .values([
+                        PriceScheduleStatus::Scheduled,
+                        PriceScheduleStatus::Active,
+                        PriceScheduleStatus::Completed,
+                        PriceScheduleStatus::Cancelled,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create PriceSchedules Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceSchedules::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PriceSchedules::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(PriceSchedules::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(PriceSchedules::SalePrice).decimal().not_null())
+                    .col(ColumnDef::new(PriceSchedules::StartsAt).timestamp_with_time_zone().not_null())
+                    .col(ColumnDef::new(PriceSchedules::EndsAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(PriceSchedules::OriginalPrice).decimal())
+                    .col(
+                        ColumnDef::new(PriceSchedules::Status)
+                            .custom(PriceScheduleStatus::Enum)
+                            .not_null()
+                            .default("scheduled"),
+                    )
+                    .col(ColumnDef::new(PriceSchedules::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PriceSchedules::Table, PriceSchedules::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create ProductPriceHistory Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductPriceHistory::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ProductPriceHistory::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(ProductPriceHistory::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(ProductPriceHistory::OldPrice).decimal().not_null())
+                    .col(ColumnDef::new(ProductPriceHistory::NewPrice).decimal().not_null())
+                    .col(
+                        ColumnDef::new(ProductPriceHistory::Reason)
+                            .custom(PriceChangeReason::Enum)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProductPriceHistory::ScheduleId).uuid())
+                    .col(ColumnDef::new(ProductPriceHistory::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductPriceHistory::Table, ProductPriceHistory::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductPriceHistory::Table, ProductPriceHistory::ScheduleId)
+                            .to(PriceSchedules::Table, PriceSchedules::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_price_history_product_created")
+                    .table(ProductPriceHistory::Table)
+                    .col(ProductPriceHistory::ProductId)
+                    .col(ProductPriceHistory::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(ProductPriceHistory::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(PriceSchedules::Table).to_owned()).await?;
+        manager.drop_type(Type::drop().name(PriceScheduleStatus::Enum).to_owned()).await?;
+        manager.drop_type(Type::drop().name(PriceChangeReason::Enum).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum PriceSchedules {
+    Table,
+    Id,
+    ProductId,
+    SalePrice,
+    StartsAt,
+    EndsAt,
+    OriginalPrice,
+    Status,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum ProductPriceHistory {
+    Table,
+    Id,
+    ProductId,
+    OldPrice,
+    NewPrice,
+    Reason,
+    ScheduleId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum PriceChangeReason {
+    #[iden = "price_change_reason"]
+    Enum,
+    Manual,
+    Import,
+    SaleStart,
+    SaleEnd,
+}
+
+#[derive(Iden)]
+pub enum PriceScheduleStatus {
+    #[iden = "price_schedule_status"]
+    Enum,
+    Scheduled,
+    Active,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
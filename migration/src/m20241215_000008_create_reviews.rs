@@ -0,0 +1,151 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(ReviewStatus::Enum)
+                    .values([ReviewStatus::Pending, ReviewStatus::Approved, ReviewStatus::Rejected])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create Reviews Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Reviews::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Reviews::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Reviews::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(Reviews::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Reviews::Rating)
+                            .integer()
+                            .not_null()
+                            .check(Expr::col(Reviews::Rating).between(1, 5)),
+                    )
+                    .col(ColumnDef::new(Reviews::Body).text())
+                    .col(
+                        ColumnDef::new(Reviews::Status)
+                            .custom(ReviewStatus::Enum)
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(ColumnDef::new(Reviews::ModerationNote).string())
+                    .col(ColumnDef::new(Reviews::ModeratedBy).uuid())
+                    .col(ColumnDef::new(Reviews::ModeratedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Reviews::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Reviews::Table, Reviews::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Reviews::Table, Reviews::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Reviews::Table, Reviews::ModeratedBy)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // ลูกค้าหนึ่งคนรีวิวสินค้าหนึ่งตัวได้ครั้งเดียว
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_reviews_product_user")
+                    .table(Reviews::Table)
+                    .col(Reviews::ProductId)
+                    .col(Reviews::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // คะแนนเฉลี่ยและจำนวนรีวิวที่อนุมัติแล้ว เก็บไว้ที่สินค้าเพื่อใช้เรียงลำดับ
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(ColumnDef::new(Products::RatingAverage).decimal_len(3, 2))
+                    .add_column(
+                        ColumnDef::new(Products::ReviewCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_column(Products::RatingAverage)
+                    .drop_column(Products::ReviewCount)
+                    .to_owned(),
+            )
+            .await?;
+        manager.drop_table(Table::drop().table(Reviews::Table).to_owned()).await?;
+        manager.drop_type(Type::drop().name(ReviewStatus::Enum).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum Reviews {
+    Table,
+    Id,
+    ProductId,
+    UserId,
+    Rating,
+    Body,
+    Status,
+    ModerationNote,
+    ModeratedBy,
+    ModeratedAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum ReviewStatus {
+    #[iden = "review_status"]
+    Enum,
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+    RatingAverage,
+    ReviewCount,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,164 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(AttributeDataType::Enum)
+                    .values([
+                        AttributeDataType::Text,
+                        AttributeDataType::Number,
+                        AttributeDataType::Boolean,
+                        AttributeDataType::Enumeration,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create AttributeDefinitions Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(AttributeDefinitions::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(AttributeDefinitions::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(AttributeDefinitions::Code).string_len(64).not_null().unique_key())
+                    .col(ColumnDef::new(AttributeDefinitions::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(AttributeDefinitions::DataType)
+                            .custom(AttributeDataType::Enum)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AttributeDefinitions::Unit).string_len(32))
+                    .col(ColumnDef::new(AttributeDefinitions::AllowedValues).json_binary())
+                    .col(
+                        ColumnDef::new(AttributeDefinitions::IsFilterable)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(ColumnDef::new(AttributeDefinitions::CreatedAt).timestamp_with_time_zone().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create ProductAttributeValues Table
+        // เก็บค่าแยกคอลัมน์ตามชนิดข้อมูล เพื่อให้กรองและเรียงด้วย SQL ได้ตรงชนิด
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductAttributeValues::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ProductAttributeValues::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(ProductAttributeValues::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(ProductAttributeValues::AttributeId).uuid().not_null())
+                    .col(ColumnDef::new(ProductAttributeValues::TextValue).string())
+                    .col(ColumnDef::new(ProductAttributeValues::NumberValue).decimal())
+                    .col(ColumnDef::new(ProductAttributeValues::BooleanValue).boolean())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductAttributeValues::Table, ProductAttributeValues::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductAttributeValues::Table, ProductAttributeValues::AttributeId)
+                            .to(AttributeDefinitions::Table, AttributeDefinitions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_attribute_values_product_attribute")
+                    .table(ProductAttributeValues::Table)
+                    .col(ProductAttributeValues::ProductId)
+                    .col(ProductAttributeValues::AttributeId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_attribute_values_attribute_text")
+                    .table(ProductAttributeValues::Table)
+                    .col(ProductAttributeValues::AttributeId)
+                    .col(ProductAttributeValues::TextValue)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_attribute_values_attribute_number")
+                    .table(ProductAttributeValues::Table)
+                    .col(ProductAttributeValues::AttributeId)
+                    .col(ProductAttributeValues::NumberValue)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(ProductAttributeValues::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(AttributeDefinitions::Table).to_owned()).await?;
+        manager.drop_type(Type::drop().name(AttributeDataType::Enum).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum AttributeDefinitions {
+    Table,
+    Id,
+    Code,
+    Name,
+    DataType,
+    Unit,
+    AllowedValues,
+    IsFilterable,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum ProductAttributeValues {
+    Table,
+    Id,
+    ProductId,
+    AttributeId,
+    TextValue,
+    NumberValue,
+    BooleanValue,
+}
+
+#[derive(Iden)]
+pub enum AttributeDataType {
+    #[iden = "attribute_data_type"]
+    Enum,
+    Text,
+    Number,
+    Boolean,
+    #[iden = "enum"]
+    Enumeration,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
@@ -0,0 +1,168 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create Categories Table
+        // name และ description ในตารางหลักคือข้อความในภาษาเริ่มต้น
+        manager
+            .create_table(
+                Table::create()
+                    .table(Categories::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Categories::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Categories::Slug).string_len(64).not_null().unique_key())
+                    .col(ColumnDef::new(Categories::Name).string().not_null())
+                    .col(ColumnDef::new(Categories::Description).text())
+                    .col(ColumnDef::new(Categories::CreatedAt).timestamp_with_time_zone().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(ColumnDef::new(Products::CategoryId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_products_category_id")
+                            .from_tbl(Products::Table)
+                            .from_col(Products::CategoryId)
+                            .to_tbl(Categories::Table)
+                            .to_col(Categories::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create ProductTranslations Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductTranslations::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ProductTranslations::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(ProductTranslations::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(ProductTranslations::Locale).string_len(16).not_null())
+                    .col(ColumnDef::new(ProductTranslations::Name).string().not_null())
+                    .col(ColumnDef::new(ProductTranslations::Description).text())
+                    .col(ColumnDef::new(ProductTranslations::UpdatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductTranslations::Table, ProductTranslations::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_translations_product_locale")
+                    .table(ProductTranslations::Table)
+                    .col(ProductTranslations::ProductId)
+                    .col(ProductTranslations::Locale)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create CategoryTranslations Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(CategoryTranslations::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CategoryTranslations::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(CategoryTranslations::CategoryId).uuid().not_null())
+                    .col(ColumnDef::new(CategoryTranslations::Locale).string_len(16).not_null())
+                    .col(ColumnDef::new(CategoryTranslations::Name).string().not_null())
+                    .col(ColumnDef::new(CategoryTranslations::Description).text())
+                    .col(ColumnDef::new(CategoryTranslations::UpdatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CategoryTranslations::Table, CategoryTranslations::CategoryId)
+                            .to(Categories::Table, Categories::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_category_translations_category_locale")
+                    .table(CategoryTranslations::Table)
+                    .col(CategoryTranslations::CategoryId)
+                    .col(CategoryTranslations::Locale)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(CategoryTranslations::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(ProductTranslations::Table).to_owned()).await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_foreign_key(Alias::new("fk_products_category_id"))
+                    .drop_column(Products::CategoryId)
+                    .to_owned(),
+            )
+            .await?;
+        manager.drop_table(Table::drop().table(Categories::Table).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum Categories {
+    Table,
+    Id,
+    Slug,
+    Name,
+    Description,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum ProductTranslations {
+    Table,
+    Id,
+    ProductId,
+    Locale,
+    Name,
+    Description,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum CategoryTranslations {
+    Table,
+    Id,
+    CategoryId,
+    Locale,
+    Name,
+    Description,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+    CategoryId,
+}
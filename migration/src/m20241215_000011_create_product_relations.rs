@@ -0,0 +1,99 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(RelationSource::Enum)
+                    .values([RelationSource::Computed, RelationSource::Pinned])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create ProductRelations Table
+        // computed มาจาก batch job ที่นับการซื้อร่วมกัน ส่วน pinned คือที่ทีม merchandise ตั้งเอง
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductRelations::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ProductRelations::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(ProductRelations::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(ProductRelations::RelatedProductId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ProductRelations::Source)
+                            .custom(RelationSource::Enum)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProductRelations::Score).integer().not_null().default(0))
+                    .col(ColumnDef::new(ProductRelations::Position).integer().not_null().default(0))
+                    .col(ColumnDef::new(ProductRelations::UpdatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductRelations::Table, ProductRelations::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductRelations::Table, ProductRelations::RelatedProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_relations_product_related")
+                    .table(ProductRelations::Table)
+                    .col(ProductRelations::ProductId)
+                    .col(ProductRelations::RelatedProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(ProductRelations::Table).to_owned()).await?;
+        manager.drop_type(Type::drop().name(RelationSource::Enum).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum ProductRelations {
+    Table,
+    Id,
+    ProductId,
+    RelatedProductId,
+    Source,
+    Score,
+    Position,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum RelationSource {
+    #[iden = "relation_source"]
+    Enum,
+    Computed,
+    Pinned,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
@@ -0,0 +1,107 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create Wishlists Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Wishlists::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Wishlists::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Wishlists::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Wishlists::Name).string().not_null())
+                    .col(ColumnDef::new(Wishlists::ShareToken).string_len(64).unique_key())
+                    .col(ColumnDef::new(Wishlists::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Wishlists::Table, Wishlists::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create WishlistItems Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(WishlistItems::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(WishlistItems::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(WishlistItems::WishlistId).uuid().not_null())
+                    .col(ColumnDef::new(WishlistItems::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(WishlistItems::AddedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(WishlistItems::Table, WishlistItems::WishlistId)
+                            .to(Wishlists::Table, Wishlists::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(WishlistItems::Table, WishlistItems::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_wishlist_items_wishlist_product")
+                    .table(WishlistItems::Table)
+                    .col(WishlistItems::WishlistId)
+                    .col(WishlistItems::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(WishlistItems::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(Wishlists::Table).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum Wishlists {
+    Table,
+    Id,
+    UserId,
+    Name,
+    ShareToken,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum WishlistItems {
+    Table,
+    Id,
+    WishlistId,
+    ProductId,
+    AddedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
@@ -0,0 +1,106 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(AlertKind::Enum)
+                    .values([AlertKind::BackInStock, AlertKind::PriceDrop])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create ProductAlerts Table
+        // last_notified_price ใช้กันแจ้งเตือนซ้ำ: แจ้งราคาลดอีกครั้งเฉพาะเมื่อต่ำกว่าครั้งก่อน
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductAlerts::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ProductAlerts::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(ProductAlerts::UserId).uuid().not_null())
+                    .col(ColumnDef::new(ProductAlerts::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(ProductAlerts::Kind).custom(AlertKind::Enum).not_null())
+                    .col(ColumnDef::new(ProductAlerts::TargetPrice).decimal())
+                    .col(ColumnDef::new(ProductAlerts::IsActive).boolean().not_null().default(true))
+                    .col(ColumnDef::new(ProductAlerts::LastNotifiedPrice).decimal())
+                    .col(ColumnDef::new(ProductAlerts::LastNotifiedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ProductAlerts::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductAlerts::Table, ProductAlerts::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ProductAlerts::Table, ProductAlerts::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_alerts_user_product_kind")
+                    .table(ProductAlerts::Table)
+                    .col(ProductAlerts::UserId)
+                    .col(ProductAlerts::ProductId)
+                    .col(ProductAlerts::Kind)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(ProductAlerts::Table).to_owned()).await?;
+        manager.drop_type(Type::drop().name(AlertKind::Enum).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum ProductAlerts {
+    Table,
+    Id,
+    UserId,
+    ProductId,
+    Kind,
+    TargetPrice,
+    IsActive,
+    LastNotifiedPrice,
+    LastNotifiedAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum AlertKind {
+    #[iden = "alert_kind"]
+    Enum,
+    BackInStock,
+    PriceDrop,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
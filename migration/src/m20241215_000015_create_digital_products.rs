@@ -0,0 +1,211 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(ProductType::Enum)
+                    .values([ProductType::Physical, ProductType::Digital])
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(
+                        ColumnDef::new(Products::ProductType)
+                            .custom(ProductType::Enum)
+                            .not_null()
+                            .default("physical"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // คำสั่งซื้อที่มีแต่สินค้าดิจิทัลไม่ต้องจัดส่ง
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .add_column(
+                        ColumnDef::new(Orders::RequiresShipping)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create DigitalAssets Table
+        // storage_key ชี้ไปที่ไฟล์ใน private store ซึ่งไม่ได้เปิดให้เข้าถึงตรง ๆ
+        manager
+            .create_table(
+                Table::create()
+                    .table(DigitalAssets::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(DigitalAssets::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(DigitalAssets::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(DigitalAssets::FileName).string().not_null())
+                    .col(ColumnDef::new(DigitalAssets::ContentType).string().not_null())
+                    .col(ColumnDef::new(DigitalAssets::SizeBytes).big_integer().not_null())
+                    .col(ColumnDef::new(DigitalAssets::StorageKey).string().not_null())
+                    .col(ColumnDef::new(DigitalAssets::MaxDownloads).integer())
+                    .col(ColumnDef::new(DigitalAssets::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DigitalAssets::Table, DigitalAssets::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_digital_assets_product_id")
+                    .table(DigitalAssets::Table)
+                    .col(DigitalAssets::ProductId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create DownloadGrants Table
+        // สิทธิ์ดาวน์โหลดต่อคำสั่งซื้อและไฟล์ สร้างตอนชำระเงิน max_downloads คัดลอกจากไฟล์ ณ ตอนนั้น
+        manager
+            .create_table(
+                Table::create()
+                    .table(DownloadGrants::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(DownloadGrants::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(DownloadGrants::OrderId).uuid().not_null())
+                    .col(ColumnDef::new(DownloadGrants::UserId).uuid().not_null())
+                    .col(ColumnDef::new(DownloadGrants::AssetId).uuid().not_null())
+                    .col(ColumnDef::new(DownloadGrants::DownloadCount).integer().not_null().default(0))
+                    .col(ColumnDef::new(DownloadGrants::MaxDownloads).integer())
+                    .col(ColumnDef::new(DownloadGrants::LastDownloadedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(DownloadGrants::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DownloadGrants::Table, DownloadGrants::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DownloadGrants::Table, DownloadGrants::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DownloadGrants::Table, DownloadGrants::AssetId)
+                            .to(DigitalAssets::Table, DigitalAssets::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_download_grants_order_asset")
+                    .table(DownloadGrants::Table)
+                    .col(DownloadGrants::OrderId)
+                    .col(DownloadGrants::AssetId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(DownloadGrants::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(DigitalAssets::Table).to_owned()).await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .drop_column(Orders::RequiresShipping)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_column(Products::ProductType)
+                    .to_owned(),
+            )
+            .await?;
+        manager.drop_type(Type::drop().name(ProductType::Enum).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum DigitalAssets {
+    Table,
+    Id,
+    ProductId,
+    FileName,
+    ContentType,
+    SizeBytes,
+    StorageKey,
+    MaxDownloads,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum DownloadGrants {
+    Table,
+    Id,
+    OrderId,
+    UserId,
+    AssetId,
+    DownloadCount,
+    MaxDownloads,
+    LastDownloadedAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum ProductType {
+    #[iden = "product_type"]
+    Enum,
+    Physical,
+    Digital,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+    ProductType,
+}
+
+#[derive(Iden)]
+enum Orders {
+    Table,
+    Id,
+    RequiresShipping,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,128 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(ProductType::Enum)
+                    .add_value(ProductType::Bundle)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create BundleComponents Table
+        // สินค้าในชุด ราคาขายอยู่ที่ตัว bundle ส่วน component ใช้ตัดสต็อกและคำนวณจำนวนที่ขายได้
+        manager
+            .create_table(
+                Table::create()
+                    .table(BundleComponents::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(BundleComponents::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(BundleComponents::BundleId).uuid().not_null())
+                    .col(ColumnDef::new(BundleComponents::ComponentId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(BundleComponents::Quantity)
+                            .integer()
+                            .not_null()
+                            .check(Expr::col(BundleComponents::Quantity).gt(0)),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(BundleComponents::Table, BundleComponents::BundleId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(BundleComponents::Table, BundleComponents::ComponentId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_bundle_components_bundle_component")
+                    .table(BundleComponents::Table)
+                    .col(BundleComponents::BundleId)
+                    .col(BundleComponents::ComponentId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // แถวของ component ในคำสั่งซื้อชี้กลับไปที่แถวของ bundle
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItems::Table)
+                    .add_column(ColumnDef::new(OrderItems::ParentItemId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_order_items_parent_item_id")
+                            .from_tbl(OrderItems::Table)
+                            .from_col(OrderItems::ParentItemId)
+                            .to_tbl(OrderItems::Table)
+                            .to_col(OrderItems::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItems::Table)
+                    .drop_foreign_key(Alias::new("fk_order_items_parent_item_id"))
+                    .drop_column(OrderItems::ParentItemId)
+                    .to_owned(),
+            )
+            .await?;
+        manager.drop_table(Table::drop().table(BundleComponents::Table).to_owned()).await?;
+        // Postgres ลบค่าออกจาก enum ไม่ได้ ค่า bundle จึงยังอยู่ใน product_type
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum BundleComponents {
+    Table,
+    Id,
+    BundleId,
+    ComponentId,
+    Quantity,
+}
+
+#[derive(Iden)]
+pub enum ProductType {
+    #[iden = "product_type"]
+    Enum,
+    Bundle,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum OrderItems {
+    Table,
+    Id,
+    ParentItemId,
+}
@@ -0,0 +1,269 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(LedgerEntryKind::Enum)
+                    .values([
+                        LedgerEntryKind::Sale,
+                        LedgerEntryKind::Commission,
+                        LedgerEntryKind::Reversal,
+                        LedgerEntryKind::Payout,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create Vendors Table
+        // ร้านค้าพาร์ทเนอร์ หนึ่งบัญชีผู้ใช้เป็นเจ้าของได้หนึ่งร้าน commission_rate เป็นสัดส่วน เช่น 0.1 = 10%
+        manager
+            .create_table(
+                Table::create()
+                    .table(Vendors::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Vendors::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Vendors::UserId).uuid().not_null().unique_key())
+                    .col(ColumnDef::new(Vendors::Name).string().not_null())
+                    .col(
+                        ColumnDef::new(Vendors::CommissionRate)
+                            .decimal_len(5, 4)
+                            .not_null()
+                            .check(Expr::col(Vendors::CommissionRate).between(0, 1)),
+                    )
+                    .col(ColumnDef::new(Vendors::IsActive).boolean().not_null().default(true))
+                    .col(ColumnDef::new(Vendors::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Vendors::Table, Vendors::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // สินค้าที่ไม่มี vendor_id เป็นของแพลตฟอร์มเอง
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(ColumnDef::new(Products::VendorId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_products_vendor_id")
+                            .from_tbl(Products::Table)
+                            .from_col(Products::VendorId)
+                            .to_tbl(Vendors::Table)
+                            .to_col(Vendors::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create VendorOrders Table
+        // คำสั่งซื้อย่อยต่อร้าน เก็บอัตรา commission ณ ตอนสั่งซื้อไว้ด้วย
+        manager
+            .create_table(
+                Table::create()
+                    .table(VendorOrders::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(VendorOrders::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(VendorOrders::OrderId).uuid().not_null())
+                    .col(ColumnDef::new(VendorOrders::VendorId).uuid().not_null())
+                    .col(ColumnDef::new(VendorOrders::Subtotal).decimal().not_null())
+                    .col(ColumnDef::new(VendorOrders::CommissionRate).decimal_len(5, 4).not_null())
+                    .col(ColumnDef::new(VendorOrders::CommissionAmount).decimal().not_null())
+                    .col(ColumnDef::new(VendorOrders::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VendorOrders::Table, VendorOrders::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VendorOrders::Table, VendorOrders::VendorId)
+                            .to(Vendors::Table, Vendors::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_vendor_orders_order_vendor")
+                    .table(VendorOrders::Table)
+                    .col(VendorOrders::OrderId)
+                    .col(VendorOrders::VendorId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItems::Table)
+                    .add_column(ColumnDef::new(OrderItems::VendorOrderId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_order_items_vendor_order_id")
+                            .from_tbl(OrderItems::Table)
+                            .from_col(OrderItems::VendorOrderId)
+                            .to_tbl(VendorOrders::Table)
+                            .to_col(VendorOrders::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create VendorLedgerEntries Table
+        // amount มีเครื่องหมาย: ยอดขายเป็นบวก commission และการจ่ายเงินเป็นลบ ยอดคงเหลือคือผลรวม
+        manager
+            .create_table(
+                Table::create()
+                    .table(VendorLedgerEntries::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(VendorLedgerEntries::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(VendorLedgerEntries::VendorId).uuid().not_null())
+                    .col(ColumnDef::new(VendorLedgerEntries::VendorOrderId).uuid())
+                    .col(ColumnDef::new(VendorLedgerEntries::Kind).custom(LedgerEntryKind::Enum).not_null())
+                    .col(ColumnDef::new(VendorLedgerEntries::Amount).decimal().not_null())
+                    .col(ColumnDef::new(VendorLedgerEntries::Reference).string())
+                    .col(ColumnDef::new(VendorLedgerEntries::CreatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VendorLedgerEntries::Table, VendorLedgerEntries::VendorId)
+                            .to(Vendors::Table, Vendors::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VendorLedgerEntries::Table, VendorLedgerEntries::VendorOrderId)
+                            .to(VendorOrders::Table, VendorOrders::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_vendor_ledger_entries_vendor_id")
+                    .table(VendorLedgerEntries::Table)
+                    .col(VendorLedgerEntries::VendorId)
+                    .col(VendorLedgerEntries::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(VendorLedgerEntries::Table).to_owned()).await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItems::Table)
+                    .drop_foreign_key(Alias::new("fk_order_items_vendor_order_id"))
+                    .drop_column(OrderItems::VendorOrderId)
+                    .to_owned(),
+            )
+            .await?;
+        manager.drop_table(Table::drop().table(VendorOrders::Table).to_owned()).await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_foreign_key(Alias::new("fk_products_vendor_id"))
+                    .drop_column(Products::VendorId)
+                    .to_owned(),
+            )
+            .await?;
+        manager.drop_table(Table::drop().table(Vendors::Table).to_owned()).await?;
+        manager.drop_type(Type::drop().name(LedgerEntryKind::Enum).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum Vendors {
+    Table,
+    Id,
+    UserId,
+    Name,
+    CommissionRate,
+    IsActive,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum VendorOrders {
+    Table,
+    Id,
+    OrderId,
+    VendorId,
+    Subtotal,
+    CommissionRate,
+    CommissionAmount,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum VendorLedgerEntries {
+    Table,
+    Id,
+    VendorId,
+    VendorOrderId,
+    Kind,
+    Amount,
+    Reference,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum LedgerEntryKind {
+    #[iden = "ledger_entry_kind"]
+    Enum,
+    Sale,
+    Commission,
+    Reversal,
+    Payout,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    VendorId,
+}
+
+#[derive(Iden)]
+enum Orders {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum OrderItems {
+    Table,
+    VendorOrderId,
+}
@@ -0,0 +1,102 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create GuestCarts Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(GuestCarts::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(GuestCarts::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(GuestCarts::CreatedAt).timestamp_with_time_zone().not_null())
+                    .col(ColumnDef::new(GuestCarts::UpdatedAt).timestamp_with_time_zone().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // ใช้หาตะกร้าที่ไม่ได้ใช้งานนานจนหมดอายุ
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_guest_carts_updated_at")
+                    .table(GuestCarts::Table)
+                    .col(GuestCarts::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create GuestCartItems Table
+        manager
+            .create_table(
+                Table::create()
+                    .table(GuestCartItems::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(GuestCartItems::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(GuestCartItems::GuestCartId).uuid().not_null())
+                    .col(ColumnDef::new(GuestCartItems::ProductId).uuid().not_null())
+                    .col(ColumnDef::new(GuestCartItems::Quantity).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(GuestCartItems::Table, GuestCartItems::GuestCartId)
+                            .to(GuestCarts::Table, GuestCarts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(GuestCartItems::Table, GuestCartItems::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_guest_cart_items_cart_product")
+                    .table(GuestCartItems::Table)
+                    .col(GuestCartItems::GuestCartId)
+                    .col(GuestCartItems::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(GuestCartItems::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(GuestCarts::Table).to_owned()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+pub enum GuestCarts {
+    Table,
+    Id,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum GuestCartItems {
+    Table,
+    Id,
+    GuestCartId,
+    ProductId,
+    Quantity,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
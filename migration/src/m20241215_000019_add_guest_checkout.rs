@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // คำสั่งซื้อของ guest ไม่มี user_id ใช้อีเมลและที่อยู่จัดส่งแทน
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .modify_column(ColumnDef::new(Orders::UserId).uuid().null())
+                    .add_column(ColumnDef::new(Orders::GuestEmail).string())
+                    .add_column(ColumnDef::new(Orders::ShippingAddress).json_binary())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_orders_guest_email")
+                    .table(Orders::Table)
+                    .col(Orders::GuestEmail)
+                    .to_owned(),
+            )
+            .await?;
+
+        // อีเมลที่ยืนยันแล้วเท่านั้นที่ใช้รับคำสั่งซื้อของ guest เข้าบัญชีได้
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(ColumnDef::new(Users::EmailVerifiedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::EmailVerifiedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_orders_guest_email")
+                    .table(Orders::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        // คำสั่งซื้อของ guest ที่ยังไม่ถูกรับเข้าบัญชีไม่มีเจ้าของ ต้องลบก่อนบังคับ user_id
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Orders::Table)
+                    .and_where(Expr::col(Orders::UserId).is_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .drop_column(Orders::ShippingAddress)
+                    .drop_column(Orders::GuestEmail)
+                    .modify_column(ColumnDef::new(Orders::UserId).uuid().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Orders {
+    Table,
+    UserId,
+    GuestEmail,
+    ShippingAddress,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    EmailVerifiedAt,
+}
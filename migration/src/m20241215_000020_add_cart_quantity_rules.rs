@@ -0,0 +1,122 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // รวมแถวที่ซ้ำกันของสินค้าเดียวกันไว้ในแถวแรก แล้วลบแถวที่จำนวนไม่ถูกต้องทิ้ง
+        db.execute_unprepared(
+            "UPDATE cart SET quantity = merged.quantity \
+             FROM (SELECT MIN(id::text)::uuid AS id, SUM(quantity) AS quantity \
+                   FROM cart GROUP BY user_id, product_id HAVING COUNT(*) > 1) AS merged \
+             WHERE cart.id = merged.id",
+        )
+        .await?;
+        db.execute_unprepared(
+            "DELETE FROM cart c USING cart keep \
+             WHERE c.user_id = keep.user_id AND c.product_id = keep.product_id \
+             AND keep.id::text < c.id::text",
+        )
+        .await?;
+        db.execute_unprepared("DELETE FROM cart WHERE quantity <= 0").await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cart_user_product")
+                    .table(Cart::Table)
+                    .col(Cart::UserId)
+                    .col(Cart::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        db.execute_unprepared(
+            "ALTER TABLE cart ADD CONSTRAINT chk_cart_quantity_positive CHECK (quantity > 0)",
+        )
+        .await?;
+        db.execute_unprepared("DELETE FROM guest_cart_items WHERE quantity <= 0").await?;
+        db.execute_unprepared(
+            "ALTER TABLE guest_cart_items \
+             ADD CONSTRAINT chk_guest_cart_items_quantity_positive CHECK (quantity > 0)",
+        )
+        .await?;
+
+        // จำนวนขั้นต่ำ สูงสุด และจำนวนที่เพิ่มทีละเท่าไรต่อสินค้า
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .add_column(
+                        ColumnDef::new(Products::MinQuantity)
+                            .integer()
+                            .not_null()
+                            .default(1)
+                            .check(Expr::col(Products::MinQuantity).gte(1)),
+                    )
+                    .add_column(ColumnDef::new(Products::MaxQuantity).integer())
+                    .add_column(
+                        ColumnDef::new(Products::QuantityStep)
+                            .integer()
+                            .not_null()
+                            .default(1)
+                            .check(Expr::col(Products::QuantityStep).gte(1)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Products::Table)
+                    .drop_column(Products::QuantityStep)
+                    .drop_column(Products::MaxQuantity)
+                    .drop_column(Products::MinQuantity)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE guest_cart_items DROP CONSTRAINT chk_guest_cart_items_quantity_positive",
+        )
+        .await?;
+        db.execute_unprepared("ALTER TABLE cart DROP CONSTRAINT chk_cart_quantity_positive")
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_cart_user_product")
+                    .table(Cart::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Cart {
+    Table,
+    UserId,
+    ProductId,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    MinQuantity,
+    MaxQuantity,
+    QuantityStep,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ราคาและสถานะของสินค้าที่ลูกค้าเห็นตอนใส่ตะกร้า แถวเดิมใช้ค่าปัจจุบันของสินค้า
+        for table in ["cart", "guest_cart_items"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(ColumnDef::new(CartLine::PriceSnapshot).decimal())
+                        .add_column(
+                            ColumnDef::new(CartLine::StatusSnapshot)
+                                .custom(Alias::new("product_status")),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+
+            let db = manager.get_connection();
+            db.execute_unprepared(&format!(
+                "UPDATE {table} SET price_snapshot = products.price, status_snapshot = products.status \
+                 FROM products WHERE products.id = {table}.product_id"
+            ))
+            .await?;
+
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .modify_column(ColumnDef::new(CartLine::PriceSnapshot).decimal().not_null())
+                        .modify_column(
+                            ColumnDef::new(CartLine::StatusSnapshot)
+                                .custom(Alias::new("product_status"))
+                                .not_null(),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in ["guest_cart_items", "cart"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(CartLine::StatusSnapshot)
+                        .drop_column(CartLine::PriceSnapshot)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// คอลัมน์ที่ใช้ร่วมกันของ cart และ guest_cart_items
+#[derive(Iden)]
+enum CartLine {
+    PriceSnapshot,
+    StatusSnapshot,
+}
@@ -0,0 +1,247 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // ตะกร้าที่ตั้งชื่อได้ ผู้ใช้หนึ่งคนมีได้หลายตะกร้า แต่ใช้งานอยู่ได้ทีละตะกร้า
+        manager
+            .create_table(
+                Table::create()
+                    .table(Carts::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Carts::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Carts::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Carts::Name).string().not_null())
+                    .col(ColumnDef::new(Carts::IsActive).boolean().not_null().default(false))
+                    .col(ColumnDef::new(Carts::CreatedAt).timestamp_with_time_zone().not_null())
+                    .col(ColumnDef::new(Carts::UpdatedAt).timestamp_with_time_zone().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Carts::Table, Carts::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_carts_user_name")
+                    .table(Carts::Table)
+                    .col(Carts::UserId)
+                    .col(Carts::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "CREATE UNIQUE INDEX idx_carts_user_active ON carts (user_id) WHERE is_active",
+        )
+        .await?;
+
+        // แถวเดิมในตะกร้าย้ายเข้าตะกร้าเริ่มต้นของผู้ใช้แต่ละคน
+        db.execute_unprepared(
+            "INSERT INTO carts (id, user_id, name, is_active, created_at, updated_at) \
+             SELECT gen_random_uuid(), user_id, 'Default', true, now(), now() \
+             FROM cart GROUP BY user_id",
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CartItems::Table)
+                    .add_column(ColumnDef::new(CartItems::CartId).uuid())
+                    .to_owned(),
+            )
+            .await?;
+        db.execute_unprepared(
+            "UPDATE cart SET cart_id = carts.id FROM carts WHERE carts.user_id = cart.user_id",
+        )
+        .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CartItems::Table)
+                    .modify_column(ColumnDef::new(CartItems::CartId).uuid().not_null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_cart_cart_id")
+                            .from_tbl(CartItems::Table)
+                            .from_col(CartItems::CartId)
+                            .to_tbl(Carts::Table)
+                            .to_col(Carts::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // สินค้าหนึ่งรายการซ้ำได้ในตะกร้าต่างกัน แต่ไม่ซ้ำในตะกร้าเดียวกัน
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_cart_user_product")
+                    .table(CartItems::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cart_cart_product")
+                    .table(CartItems::Table)
+                    .col(CartItems::CartId)
+                    .col(CartItems::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // รายการบันทึกไว้ซื้อภายหลัง แยกจากตะกร้าและไม่ถูกนับตอนสั่งซื้อ
+        manager
+            .create_table(
+                Table::create()
+                    .table(SavedCartItems::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(SavedCartItems::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(SavedCartItems::UserId).uuid().not_null())
+                    .col(ColumnDef::new(SavedCartItems::ProductId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(SavedCartItems::Quantity)
+                            .integer()
+                            .not_null()
+                            .check(Expr::col(SavedCartItems::Quantity).gt(0)),
+                    )
+                    .col(
+                        ColumnDef::new(SavedCartItems::SavedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SavedCartItems::Table, SavedCartItems::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(SavedCartItems::Table, SavedCartItems::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_saved_cart_items_user_product")
+                    .table(SavedCartItems::Table)
+                    .col(SavedCartItems::UserId)
+                    .col(SavedCartItems::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SavedCartItems::Table).to_owned())
+            .await?;
+
+        // เหลือไว้เฉพาะแถวของตะกร้าที่ใช้งานอยู่ ให้ตรงกับ unique index เดิม
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "DELETE FROM cart USING carts WHERE carts.id = cart.cart_id AND NOT carts.is_active",
+        )
+        .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_cart_cart_product")
+                    .table(CartItems::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cart_user_product")
+                    .table(CartItems::Table)
+                    .col(CartItems::UserId)
+                    .col(CartItems::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(CartItems::Table)
+                    .drop_foreign_key(Alias::new("fk_cart_cart_id"))
+                    .drop_column(CartItems::CartId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Carts::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Carts {
+    Table,
+    Id,
+    UserId,
+    Name,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// ตาราง `cart` เก็บแถวของสินค้าในตะกร้า
+#[derive(Iden)]
+enum CartItems {
+    #[iden = "cart"]
+    Table,
+    CartId,
+    UserId,
+    ProductId,
+}
+
+#[derive(Iden)]
+enum SavedCartItems {
+    Table,
+    Id,
+    UserId,
+    ProductId,
+    Quantity,
+    SavedAt,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
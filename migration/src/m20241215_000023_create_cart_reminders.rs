@@ -0,0 +1,146 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // จำนวนครั้งที่ส่งอีเมลเตือนไปแล้วในรอบนี้ นับใหม่เมื่อสั่งซื้อจากตะกร้า
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Carts::Table)
+                    .add_column(
+                        ColumnDef::new(Carts::ReminderCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(ColumnDef::new(Carts::LastRemindedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_carts_updated_at")
+                    .table(Carts::Table)
+                    .col(Carts::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // ประวัติการส่งอีเมลเตือนใช้คำนวณอัตราการกู้คืน
+        // cart_id ไม่มี foreign key เพื่อให้รายงานยังอยู่หลังตะกร้าถูกลบ
+        manager
+            .create_table(
+                Table::create()
+                    .table(CartReminders::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CartReminders::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(CartReminders::CartId).uuid().not_null())
+                    .col(ColumnDef::new(CartReminders::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(CartReminders::SentAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CartReminders::ClickedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(CartReminders::RecoveredAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(CartReminders::OrderId).uuid())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CartReminders::Table, CartReminders::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CartReminders::Table, CartReminders::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cart_reminders_cart_id")
+                    .table(CartReminders::Table)
+                    .col(CartReminders::CartId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cart_reminders_sent_at")
+                    .table(CartReminders::Table)
+                    .col(CartReminders::SentAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CartReminders::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_carts_updated_at")
+                    .table(Carts::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Carts::Table)
+                    .drop_column(Carts::LastRemindedAt)
+                    .drop_column(Carts::ReminderCount)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Carts {
+    Table,
+    UpdatedAt,
+    ReminderCount,
+    LastRemindedAt,
+}
+
+#[derive(Iden)]
+enum CartReminders {
+    Table,
+    Id,
+    CartId,
+    UserId,
+    SentAt,
+    ClickedAt,
+    RecoveredAt,
+    OrderId,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Orders {
+    Table,
+    Id,
+}
@@ -0,0 +1,333 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::prelude::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(DiscountType::Enum)
+                    .values([DiscountType::Percentage, DiscountType::FixedAmount])
+                    .to_owned(),
+            )
+            .await?;
+
+        // โค้ดเก็บเป็นตัวพิมพ์ใหญ่เสมอ การเทียบโค้ดจึงไม่สนตัวพิมพ์
+        // usage_limit และ per_user_limit เป็น null คือไม่จำกัด
+        manager
+            .create_table(
+                Table::create()
+                    .table(Coupons::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Coupons::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Coupons::Code).string_len(64).not_null().unique_key())
+                    .col(ColumnDef::new(Coupons::Description).string())
+                    .col(
+                        ColumnDef::new(Coupons::DiscountType)
+                            .custom(DiscountType::Enum)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Coupons::Amount)
+                            .decimal()
+                            .not_null()
+                            .check(Expr::col(Coupons::Amount).gt(0)),
+                    )
+                    .col(ColumnDef::new(Coupons::MinSubtotal).decimal())
+                    .col(ColumnDef::new(Coupons::StartsAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Coupons::EndsAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Coupons::UsageLimit).integer())
+                    .col(ColumnDef::new(Coupons::PerUserLimit).integer())
+                    .col(
+                        ColumnDef::new(Coupons::RedemptionCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(Coupons::IsActive).boolean().not_null().default(true))
+                    .col(ColumnDef::new(Coupons::CreatedAt).timestamp_with_time_zone().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // ถ้าคูปองมีรายการสินค้าหรือหมวดหมู่ ส่วนลดคิดเฉพาะสินค้าที่ตรงเงื่อนไข
+        manager
+            .create_table(
+                Table::create()
+                    .table(CouponProducts::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CouponProducts::CouponId).uuid().not_null())
+                    .col(ColumnDef::new(CouponProducts::ProductId).uuid().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(CouponProducts::CouponId)
+                            .col(CouponProducts::ProductId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CouponProducts::Table, CouponProducts::CouponId)
+                            .to(Coupons::Table, Coupons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CouponProducts::Table, CouponProducts::ProductId)
+                            .to(Products::Table, Products::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CouponCategories::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CouponCategories::CouponId).uuid().not_null())
+                    .col(ColumnDef::new(CouponCategories::CategoryId).uuid().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(CouponCategories::CouponId)
+                            .col(CouponCategories::CategoryId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CouponCategories::Table, CouponCategories::CouponId)
+                            .to(Coupons::Table, Coupons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CouponCategories::Table, CouponCategories::CategoryId)
+                            .to(Categories::Table, Categories::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // การใช้คูปองหนึ่งครั้งต่อคำสั่งซื้อ ใช้นับสิทธิ์ต่อผู้ใช้
+        manager
+            .create_table(
+                Table::create()
+                    .table(CouponRedemptions::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(CouponRedemptions::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(CouponRedemptions::CouponId).uuid().not_null())
+                    .col(ColumnDef::new(CouponRedemptions::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(CouponRedemptions::OrderId)
+                            .uuid()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(CouponRedemptions::Discount).decimal().not_null())
+                    .col(
+                        ColumnDef::new(CouponRedemptions::RedeemedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CouponRedemptions::Table, CouponRedemptions::CouponId)
+                            .to(Coupons::Table, Coupons::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CouponRedemptions::Table, CouponRedemptions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CouponRedemptions::Table, CouponRedemptions::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_coupon_redemptions_coupon_user")
+                    .table(CouponRedemptions::Table)
+                    .col(CouponRedemptions::CouponId)
+                    .col(CouponRedemptions::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // คูปองที่ใส่ไว้ในตะกร้า และคูปองกับส่วนลดที่ใช้จริงในคำสั่งซื้อ
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Carts::Table)
+                    .add_column(ColumnDef::new(Carts::CouponId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_carts_coupon_id")
+                            .from_tbl(Carts::Table)
+                            .from_col(Carts::CouponId)
+                            .to_tbl(Coupons::Table)
+                            .to_col(Coupons::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .add_column(ColumnDef::new(Orders::CouponId).uuid())
+                    .add_column(
+                        ColumnDef::new(Orders::DiscountTotal)
+                            .decimal()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_orders_coupon_id")
+                            .from_tbl(Orders::Table)
+                            .from_col(Orders::CouponId)
+                            .to_tbl(Coupons::Table)
+                            .to_col(Coupons::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .drop_foreign_key(Alias::new("fk_orders_coupon_id"))
+                    .drop_column(Orders::DiscountTotal)
+                    .drop_column(Orders::CouponId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Carts::Table)
+                    .drop_foreign_key(Alias::new("fk_carts_coupon_id"))
+                    .drop_column(Carts::CouponId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CouponRedemptions::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CouponCategories::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CouponProducts::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Coupons::Table).to_owned())
+            .await?;
+        manager
+            .drop_type(Type::drop().name(DiscountType::Enum).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum DiscountType {
+    #[iden = "discount_type"]
+    Enum,
+    Percentage,
+    FixedAmount,
+}
+
+#[derive(Iden)]
+enum Coupons {
+    Table,
+    Id,
+    Code,
+    Description,
+    DiscountType,
+    Amount,
+    MinSubtotal,
+    StartsAt,
+    EndsAt,
+    UsageLimit,
+    PerUserLimit,
+    RedemptionCount,
+    IsActive,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum CouponProducts {
+    Table,
+    CouponId,
+    ProductId,
+}
+
+#[derive(Iden)]
+enum CouponCategories {
+    Table,
+    CouponId,
+    CategoryId,
+}
+
+#[derive(Iden)]
+enum CouponRedemptions {
+    Table,
+    Id,
+    CouponId,
+    UserId,
+    OrderId,
+    Discount,
+    RedeemedAt,
+}
+
+#[derive(Iden)]
+enum Carts {
+    Table,
+    CouponId,
+}
+
+#[derive(Iden)]
+enum Orders {
+    Table,
+    Id,
+    CouponId,
+    DiscountTotal,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Products {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+    Id,
+}
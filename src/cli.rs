@@ -0,0 +1,93 @@
+use crate::config::AppConfig;
+use crate::services::notification::LogNotificationChannel;
+use crate::services::{catalog_csv_service, recommendation_service};
+use sea_orm::DatabaseConnection;
+
+const USAGE: &str = "Usage:
+  sea-ecm import-products <file.csv> [--dry-run]
+  sea-ecm export-products [file.csv]
+  sea-ecm recompute-recommendations";
+
+/// รันคำสั่ง CLI แทนการเปิด HTTP server คืนค่า exit code
+pub async fn run(db: &DatabaseConnection, config: &AppConfig, args: &[String]) -> i32 {
+    match args.first().map(String::as_str) {
+        Some("import-products") => import_products(db, &args[1..]).await,
+        Some("export-products") => export_products(db, &args[1..]).await,
+        Some("recompute-recommendations") => recompute_recommendations(db, config).await,
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    }
+}
+
+async fn import_products(db: &DatabaseConnection, args: &[String]) -> i32 {
+    let dry_run = args.iter().any(|arg| arg == "--dry-run");
+    let Some(path) = args.iter().find(|arg| !arg.starts_with("--")) else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path, e);
+            return 1;
+        }
+    };
+
+    match catalog_csv_service::import_products(db, &LogNotificationChannel, &data, dry_run).await {
+        Ok(report) => {
+            for error in &report.errors {
+                eprintln!("line {}: {}", error.line, error.message);
+            }
+            println!(
+                "{}created: {}, updated: {}, errors: {}",
+                if report.dry_run { "[dry run] " } else { "" },
+                report.created,
+                report.updated,
+                report.errors.len()
+            );
+            if report.errors.is_empty() { 0 } else { 1 }
+        }
+        Err(e) => {
+            eprintln!("Import failed: {}", e);
+            1
+        }
+    }
+}
+
+async fn export_products(db: &DatabaseConnection, args: &[String]) -> i32 {
+    let csv = match catalog_csv_service::export_products(db).await {
+        Ok(csv) => csv,
+        Err(e) => {
+            eprintln!("Export failed: {}", e);
+            return 1;
+        }
+    };
+
+    let result = match args.first() {
+        Some(path) => std::fs::write(path, csv),
+        None => std::io::Write::write_all(&mut std::io::stdout(), &csv),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Failed to write CSV: {}", e);
+            1
+        }
+    }
+}
+
+async fn recompute_recommendations(db: &DatabaseConnection, config: &AppConfig) -> i32 {
+    match recommendation_service::recompute_relations(db, config.related_products_limit).await {
+        Ok(count) => {
+            println!("saved {} related products", count);
+            0
+        }
+        Err(e) => {
+            eprintln!("Recompute failed: {}", e);
+            1
+        }
+    }
+}
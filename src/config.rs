@@ -1,8 +1,39 @@
 use crate::error::ApiError;
+use rust_decimal::Decimal;
+use crate::services::guest_cart_service::CartMergeStrategy;
+use crate::services::inventory_service::AllocationStrategyKind;
+use std::str::FromStr;
 
 #[derive(Clone)]
 pub struct AppConfig {
     pub jwt_secret: String,
+    pub allocation_strategy: AllocationStrategyKind,
+    pub media_root: String,
+    pub media_base_url: String,
+    pub max_image_bytes: usize,
+    pub price_scheduler_interval_secs: u64,
+    pub recommendation_interval_secs: u64,
+    pub related_products_limit: usize,
+    pub default_locale: String,
+    pub supported_locales: Vec<String>,
+    pub private_media_root: String,
+    pub max_digital_file_bytes: usize,
+    pub download_secret: String,
+    pub download_url_ttl_secs: i64,
+    pub cart_token_secret: String,
+    pub cart_merge_strategy: CartMergeStrategy,
+    pub guest_cart_ttl_secs: i64,
+    pub guest_cart_cleanup_interval_secs: u64,
+    pub order_token_secret: String,
+    pub email_verification_secret: String,
+    pub email_verification_ttl_secs: i64,
+    pub tax_rate: Decimal,
+    pub cart_reminder_interval_secs: u64,
+    pub cart_idle_secs: i64,
+    pub cart_reminder_max: i32,
+    pub cart_reminder_secret: String,
+    pub cart_reminder_url: String,
+    pub cart_retention_secs: i64,
 }
 
 impl AppConfig {
@@ -10,16 +41,146 @@ impl AppConfig {
         // เพิ่ม logging เพื่อติดตาม
         println!("กำลังโหลด AppConfig...");
         
-        std::env::var("JWT_SECRET")
-            .map(|secret| {
+        let jwt_secret = std::env::var("JWT_SECRET")
+            .inspect(|_| {
                 println!("โหลด JWT_SECRET สำเร็จ");
-                Self { jwt_secret: secret }
             })
             .map_err(|_| {
                 println!("โหลด JWT_SECRET ล้มเหลว");
                 ApiError::AuthenticationError(
                     "ไม่พบ JWT_SECRET ในตัวแปรสภาพแวดล้อม".to_string()
                 )
-            })
+            })?;
+
+        // กลยุทธ์เลือกคลังจัดส่ง ค่าเริ่มต้นคือ priority
+        let allocation_strategy = match std::env::var("ALLOCATION_STRATEGY") {
+            Ok(value) => AllocationStrategyKind::from_str(&value).map_err(|_| {
+                ApiError::ValidationError(format!("ALLOCATION_STRATEGY ไม่ถูกต้อง: {}", value))
+            })?,
+            Err(_) => AllocationStrategyKind::Priority,
+        };
+
+        // ที่เก็บไฟล์รูปภาพสินค้าและ URL ที่ใช้เสิร์ฟ path นี้ไม่ต้องเข้าสู่ระบบ
+        // จึงต้องขึ้นต้นด้วย / และห้ามเป็น root ไม่อย่างนั้นทุก route จะไม่ต้องเข้าสู่ระบบ
+        let media_root = std::env::var("MEDIA_ROOT").unwrap_or_else(|_| "./media".to_string());
+        let raw_media_base_url =
+            std::env::var("MEDIA_BASE_URL").unwrap_or_else(|_| "/media".to_string());
+        let media_base_url = raw_media_base_url.trim_end_matches('/').to_string();
+        if media_base_url.is_empty() || !media_base_url.starts_with('/') {
+            return Err(ApiError::ValidationError(format!(
+                "MEDIA_BASE_URL ไม่ถูกต้อง: {}",
+                raw_media_base_url
+            )));
+        }
+        let max_image_bytes = parse_env("MAX_IMAGE_BYTES", 5 * 1024 * 1024)?;
+
+        // รอบการตรวจราคาที่ตั้งเวลาไว้
+        let price_scheduler_interval_secs = parse_env("PRICE_SCHEDULER_INTERVAL_SECS", 60)?;
+
+        // รอบคำนวณสินค้าที่ซื้อร่วมกัน และจำนวนสินค้าแนะนำที่เก็บต่อสินค้า
+        let recommendation_interval_secs = parse_env("RECOMMENDATION_INTERVAL_SECS", 3600)?;
+        let related_products_limit = parse_env("RELATED_PRODUCTS_LIMIT", 10)?;
+
+        // ภาษาของหน้าร้าน ภาษาเริ่มต้นคือภาษาของข้อความในตาราง products
+        let default_locale = std::env::var("DEFAULT_LOCALE")
+            .unwrap_or_else(|_| "th".to_string())
+            .to_lowercase();
+        let mut supported_locales: Vec<String> = std::env::var("SUPPORTED_LOCALES")
+            .unwrap_or_else(|_| "th,en".to_string())
+            .split(',')
+            .map(|locale| locale.trim().to_lowercase())
+            .filter(|locale| !locale.is_empty())
+            .collect();
+        if !supported_locales.contains(&default_locale) {
+            supported_locales.insert(0, default_locale.clone());
+        }
+
+        // ไฟล์สินค้าดิจิทัลเก็บแยกจากรูปภาพและไม่เสิร์ฟสาธารณะ
+        // ลิงก์ดาวน์โหลดเซ็นด้วย DOWNLOAD_SECRET ถ้าไม่ได้ตั้งไว้ใช้ JWT_SECRET แทน
+        let private_media_root =
+            std::env::var("PRIVATE_MEDIA_ROOT").unwrap_or_else(|_| "./private_media".to_string());
+        let max_digital_file_bytes = parse_env("MAX_DIGITAL_FILE_BYTES", 100 * 1024 * 1024)?;
+        let download_secret =
+            std::env::var("DOWNLOAD_SECRET").unwrap_or_else(|_| jwt_secret.clone());
+        let download_url_ttl_secs = parse_env("DOWNLOAD_URL_TTL_SECS", 15 * 60)?;
+
+        // ตะกร้าของผู้ใช้ที่ยังไม่เข้าสู่ระบบ token เซ็นด้วย CART_TOKEN_SECRET (ค่าเริ่มต้นคือ JWT_SECRET)
+        // ตะกร้าที่ไม่ได้แตะนานเกิน GUEST_CART_TTL_SECS จะหมดอายุ
+        let cart_token_secret =
+            std::env::var("CART_TOKEN_SECRET").unwrap_or_else(|_| jwt_secret.clone());
+        let cart_merge_strategy = match std::env::var("CART_MERGE_STRATEGY") {
+            Ok(value) => CartMergeStrategy::from_str(&value).map_err(|_| {
+                ApiError::ValidationError(format!("CART_MERGE_STRATEGY ไม่ถูกต้อง: {}", value))
+            })?,
+            Err(_) => CartMergeStrategy::Sum,
+        };
+        let guest_cart_ttl_secs = parse_env("GUEST_CART_TTL_SECS", 30 * 24 * 60 * 60)?;
+        let guest_cart_cleanup_interval_secs = parse_env("GUEST_CART_CLEANUP_INTERVAL_SECS", 3600)?;
+
+        // token ดูคำสั่งซื้อของ guest และ token ยืนยันอีเมล ถ้าไม่ได้ตั้ง secret ไว้ใช้ JWT_SECRET แทน
+        let order_token_secret =
+            std::env::var("ORDER_TOKEN_SECRET").unwrap_or_else(|_| jwt_secret.clone());
+        let email_verification_secret =
+            std::env::var("EMAIL_VERIFICATION_SECRET").unwrap_or_else(|_| jwt_secret.clone());
+        let email_verification_ttl_secs = parse_env("EMAIL_VERIFICATION_TTL_SECS", 24 * 60 * 60)?;
+
+        // อัตราภาษีที่ใช้ประมาณยอดในหน้าตะกร้า เช่น 0.07 ค่าเริ่มต้นคือไม่คิดภาษี
+        let tax_rate: Decimal = parse_env("TAX_RATE", Decimal::ZERO)?;
+        if tax_rate < Decimal::ZERO || tax_rate > Decimal::ONE {
+            return Err(ApiError::ValidationError(format!("TAX_RATE ไม่ถูกต้อง: {}", tax_rate)));
+        }
+
+        // อีเมลเตือนตะกร้าที่ไม่ได้แตะนานเกิน CART_IDLE_SECS ส่งได้ไม่เกิน CART_REMINDER_MAX ครั้ง
+        // ลิงก์ในอีเมลคือ CART_REMINDER_URL ต่อด้วย token ที่เซ็นด้วย CART_REMINDER_SECRET
+        // ตะกร้าที่ไม่ได้แตะนานเกิน CART_RETENTION_SECS จะถูกลบทิ้ง
+        let cart_reminder_interval_secs = parse_env("CART_REMINDER_INTERVAL_SECS", 15 * 60)?;
+        let cart_idle_secs = parse_env("CART_IDLE_SECS", 24 * 60 * 60)?;
+        let cart_reminder_max = parse_env("CART_REMINDER_MAX", 2)?;
+        let cart_reminder_secret =
+            std::env::var("CART_REMINDER_SECRET").unwrap_or_else(|_| jwt_secret.clone());
+        let cart_reminder_url =
+            std::env::var("CART_REMINDER_URL").unwrap_or_else(|_| "/cart-reminders".to_string());
+        let cart_retention_secs = parse_env("CART_RETENTION_SECS", 90 * 24 * 60 * 60)?;
+
+        Ok(Self {
+            jwt_secret,
+            allocation_strategy,
+            media_root,
+            media_base_url,
+            max_image_bytes,
+            price_scheduler_interval_secs,
+            recommendation_interval_secs,
+            related_products_limit,
+            default_locale,
+            supported_locales,
+            private_media_root,
+            max_digital_file_bytes,
+            download_secret,
+            download_url_ttl_secs,
+            cart_token_secret,
+            cart_merge_strategy,
+            guest_cart_ttl_secs,
+            guest_cart_cleanup_interval_secs,
+            order_token_secret,
+            email_verification_secret,
+            email_verification_ttl_secs,
+            tax_rate,
+            cart_reminder_interval_secs,
+            cart_idle_secs,
+            cart_reminder_max,
+            cart_reminder_secret,
+            cart_reminder_url,
+            cart_retention_secs,
+        })
     }
-}
\ No newline at end of file
+}
+
+/// อ่านค่าตัวเลขจาก env ถ้าไม่ได้ตั้งไว้ใช้ค่าเริ่มต้น
+fn parse_env<T: std::str::FromStr>(key: &str, default: T) -> Result<T, ApiError> {
+    match std::env::var(key) {
+        Ok(value) => value
+            .parse()
+            .map_err(|_| ApiError::ValidationError(format!("{} ไม่ถูกต้อง: {}", key, value))),
+        Err(_) => Ok(default),
+    }
+}
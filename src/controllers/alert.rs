@@ -0,0 +1,51 @@
+use crate::entity::sea_orm_active_enums::AlertKind;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::alert_service;
+use actix_web::{web, HttpRequest, HttpResponse};
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// การสมัครรับแจ้งเตือนทั้งหมดของผู้ใช้
+pub async fn get_alerts(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let alerts = alert_service::list_alerts(&db, current_user_id(&req)?).await?;
+    Ok(HttpResponse::Ok().json(alerts))
+}
+
+#[derive(Deserialize)]
+pub struct SubscribeAlertRequest {
+    pub kind: AlertKind,
+    pub target_price: Option<Decimal>,
+}
+
+pub async fn subscribe_alert(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+    data: web::Json<SubscribeAlertRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let alert = alert_service::subscribe(
+        &db,
+        current_user_id(&req)?,
+        product_id.into_inner(),
+        data.kind,
+        data.target_price,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(alert))
+}
+
+pub async fn unsubscribe_alert(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, AlertKind)>, // Tuple ของ (product_id, kind)
+) -> Result<HttpResponse, ApiError> {
+    let (product_id, kind) = path.into_inner();
+    alert_service::unsubscribe(&db, current_user_id(&req)?, product_id, kind).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,90 @@
+use crate::entity::sea_orm_active_enums::AttributeDataType;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::{attribute_service, auth::require_admin, vendor_service};
+use actix_web::{web, HttpRequest, HttpResponse};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+use validator::Validate;
+
+// code ใช้เป็นชื่อ query param (`attr.<code>`) จึงจำกัดให้เป็นตัวพิมพ์เล็ก ตัวเลข และ _
+static CODE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-z0-9_]+$").expect("Invalid attribute code regex"));
+
+pub async fn get_attributes(
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let definitions = attribute_service::list_definitions(&db).await?;
+    Ok(HttpResponse::Ok().json(definitions))
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CreateAttributeRequest {
+    #[validate(
+        length(min = 1, max = 64, message = "must be 1 - 64 characters long"),
+        regex(path = *CODE_REGEX, message = "may only contain a-z, 0-9 and _")
+    )]
+    pub code: String,
+    #[validate(length(min = 1, max = 255, message = "must be 1 - 255 characters long"))]
+    pub name: String,
+    pub data_type: AttributeDataType,
+    #[validate(length(max = 32, message = "must be at most 32 characters long"))]
+    pub unit: Option<String>,
+    pub allowed_values: Option<Vec<String>>,
+    pub is_filterable: Option<bool>,
+}
+
+pub async fn create_attribute(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    data: web::Json<CreateAttributeRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let data = data.into_inner();
+    let definition = attribute_service::create_definition(
+        &db,
+        data.code,
+        data.name,
+        data.data_type,
+        data.unit,
+        data.allowed_values,
+        data.is_filterable.unwrap_or(true),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(definition))
+}
+
+pub async fn delete_attribute(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    attribute_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    attribute_service::delete_definition(&db, attribute_id.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// กำหนดค่า attribute ของสินค้า body เป็น object `{ "<code>": <value> }`
+pub async fn set_product_attributes(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+    data: web::Json<HashMap<String, Value>>,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, *product_id).await?;
+
+    let attributes =
+        attribute_service::set_product_attributes(&db, product_id.into_inner(), data.into_inner())
+            .await?;
+    Ok(HttpResponse::Ok().json(attributes))
+}
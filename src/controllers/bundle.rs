@@ -0,0 +1,41 @@
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::{bundle_service, vendor_service};
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// component ของชุดสินค้าพร้อมจำนวนชุดที่ขายได้
+pub async fn get_bundle_components(
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let bundle = bundle_service::get_bundle(&db, product_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(bundle))
+}
+
+#[derive(Deserialize)]
+pub struct BundleComponentRequest {
+    pub product_id: Uuid,
+    pub quantity: i32,
+}
+
+/// แทนที่ component ทั้งหมดของชุด (ผู้ดูแลระบบหรือร้านเจ้าของชุด)
+pub async fn set_bundle_components(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+    data: web::Json<Vec<BundleComponentRequest>>,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, *product_id).await?;
+
+    let components = data
+        .into_inner()
+        .into_iter()
+        .map(|component| (component.product_id, component.quantity))
+        .collect();
+    let bundle =
+        bundle_service::set_components(&db, product_id.into_inner(), components).await?;
+    Ok(HttpResponse::Ok().json(bundle))
+}
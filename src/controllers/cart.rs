@@ -1,21 +1,40 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use uuid::Uuid;
-use crate::services::cart_service;
+use crate::config::AppConfig;
+use crate::services::{cart_service, coupon_service};
+use crate::services::media_store::MediaStore;
 use sea_orm::DatabaseConnection;
 use serde_json::json;
 use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use validator::Validate;
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, Validate)]
 pub struct AddToCartRequest {
     pub product_id: Uuid,
+    #[validate(range(min = 1, message = "must be at least 1"))]
     pub quantity: i32,
 }
+
+#[derive(serde::Deserialize, Validate)]
+pub struct SetQuantityRequest {
+    #[validate(range(min = 0, message = "must not be negative"))]
+    pub quantity: i32,
+}
+
+#[derive(serde::Deserialize, Validate)]
+pub struct ApplyCouponRequest {
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub code: String,
+}
 /// เพิ่มสินค้าในตะกร้า
 pub async fn add_to_cart(
     db: web::Data<DatabaseConnection>,
     user_id: web::Path<Uuid>,
     req: web::Json<AddToCartRequest>,
 ) -> Result<HttpResponse, ApiError> {
+    req.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
     let cart_item = cart_service::add_to_cart(
         &db,
         user_id.into_inner(),
@@ -26,6 +45,21 @@ pub async fn add_to_cart(
     Ok(HttpResponse::Ok().json(cart_item))
 }
 
+/// ตั้งจำนวนของสินค้าในตะกร้า ส่ง 0 เพื่อเอาออก
+pub async fn set_cart_quantity(
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (user_id, product_id)
+    req: web::Json<SetQuantityRequest>,
+) -> Result<HttpResponse, ApiError> {
+    req.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let (user_id, product_id) = path.into_inner();
+    match cart_service::set_quantity(&db, user_id, product_id, req.quantity).await? {
+        Some(cart_item) => Ok(HttpResponse::Ok().json(cart_item)),
+        None => Ok(HttpResponse::Ok().body("Item removed from cart")),
+    }
+}
+
 /// ลบสินค้าออกจากตะกร้า
 pub async fn remove_from_cart(
     db: web::Data<DatabaseConnection>,
@@ -45,6 +79,15 @@ pub async fn calculate_cart_total(
     Ok(HttpResponse::Ok().json(json!({ "total_price": total_price })))
 }
 
+/// ยืนยันราคาและสถานะสินค้าที่เปลี่ยนไป เพื่อให้สั่งซื้อต่อได้
+pub async fn acknowledge_cart_changes(
+    db: web::Data<DatabaseConnection>,
+    user_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let acknowledged = cart_service::acknowledge_changes(&db, user_id.into_inner(), None).await?;
+    Ok(HttpResponse::Ok().json(json!({ "acknowledged": acknowledged })))
+}
+
 /// ล้างตะกร้าสินค้า
 pub async fn clear_cart(
     db: web::Data<DatabaseConnection>,
@@ -54,11 +97,54 @@ pub async fn clear_cart(
     Ok(HttpResponse::Ok().body("Cart cleared successfully"))
 }
 
-/// ดึงรายการสินค้าในตะกร้า
+/// ดึงตะกร้าพร้อมข้อมูลสินค้า คำเตือน และยอดรวม
 pub async fn get_cart(
     db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    store: web::Data<dyn MediaStore>,
     user_id: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
-    let cart_items = cart_service::get_cart(&db, user_id.into_inner()).await?;
-    Ok(HttpResponse::Ok().json(cart_items))
-}
\ No newline at end of file
+    let cart = cart_service::get_cart(
+        &db,
+        store.get_ref(),
+        config.tax_rate,
+        user_id.into_inner(),
+        None,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(cart))
+}
+/// ใส่คูปองในตะกร้าของตัวเอง คืนค่าส่วนลดที่ได้ตอนนี้
+pub async fn apply_cart_coupon(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    user_id: web::Path<Uuid>,
+    data: web::Json<ApplyCouponRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = own_cart(&req, user_id.into_inner())?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let coupon = coupon_service::apply_to_cart(&db, user_id, &data.code).await?;
+    Ok(HttpResponse::Ok().json(coupon))
+}
+
+/// เอาคูปองออกจากตะกร้าของตัวเอง
+pub async fn remove_cart_coupon(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    user_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = own_cart(&req, user_id.into_inner())?;
+    coupon_service::remove_from_cart(&db, user_id).await?;
+    Ok(HttpResponse::Ok().body("Coupon removed from cart"))
+}
+
+/// user_id ใน path ต้องเป็นผู้ใช้ที่เข้าสู่ระบบอยู่
+fn own_cart(req: &HttpRequest, user_id: Uuid) -> Result<Uuid, ApiError> {
+    if current_user_id(req)? != user_id {
+        return Err(ApiError::Forbidden(
+            "Users can only change their own cart".to_string(),
+        ));
+    }
+    Ok(user_id)
+}
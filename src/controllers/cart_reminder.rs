@@ -0,0 +1,37 @@
+use crate::config::AppConfig;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::auth::require_admin;
+use crate::services::cart_reminder_service;
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+
+/// เปิดตะกร้าจากลิงก์ในอีเมลเตือน (เปิดสาธารณะ ตรวจสิทธิ์ด้วย token ในลิงก์)
+pub async fn restore_cart(
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    token: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let cart =
+        cart_reminder_service::restore_cart(&db, &config.cart_reminder_secret, &token).await?;
+    Ok(HttpResponse::Ok().json(cart))
+}
+
+#[derive(Deserialize)]
+pub struct RecoveryReportQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// อัตราการกู้คืนตะกร้าจากอีเมลเตือน (admin)
+pub async fn get_cart_recovery_report(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    query: web::Query<RecoveryReportQuery>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+    let report = cart_reminder_service::recovery_report(&db, query.from, query.to).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
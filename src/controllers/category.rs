@@ -0,0 +1,66 @@
+use crate::config::AppConfig;
+use crate::error::ApiError;
+use crate::middleware::{auth::current_user_id, locale::request_locale};
+use crate::services::{auth::require_admin, category_service, translation_service};
+use actix_web::{http::header::CONTENT_LANGUAGE, web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+pub async fn get_categories(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+) -> Result<HttpResponse, ApiError> {
+    let locale = request_locale(&req, &config);
+    let mut categories = category_service::list_categories(&db).await?;
+    translation_service::localize_categories(&db, &mut categories, &locale, &config).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header((CONTENT_LANGUAGE, locale))
+        .json(categories))
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CreateCategoryRequest {
+    #[validate(length(min = 1, max = 64, message = "must be 1 - 64 characters long"))]
+    pub slug: String,
+    #[validate(length(min = 1, max = 255, message = "must be 1 - 255 characters long"))]
+    pub name: String,
+    pub description: Option<String>,
+}
+
+pub async fn create_category(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    data: web::Json<CreateCategoryRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let data = data.into_inner();
+    let category =
+        category_service::create_category(&db, data.slug, data.name, data.description).await?;
+    Ok(HttpResponse::Created().json(category))
+}
+
+#[derive(Deserialize)]
+pub struct SetProductCategoryRequest {
+    pub category_id: Option<Uuid>,
+}
+
+pub async fn set_product_category(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+    data: web::Json<SetProductCategoryRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let product =
+        category_service::set_product_category(&db, product_id.into_inner(), data.category_id)
+            .await?;
+    Ok(HttpResponse::Ok().json(product))
+}
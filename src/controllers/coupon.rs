@@ -0,0 +1,123 @@
+use crate::controllers::patch::non_null;
+use crate::entity::sea_orm_active_enums::DiscountType;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::auth::require_admin;
+use crate::services::coupon_service::{self, CouponUpdate, NewCoupon};
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct CreateCouponRequest {
+    #[validate(length(min = 1, max = 64, message = "must be 1 to 64 characters"))]
+    pub code: String,
+    pub description: Option<String>,
+    pub discount_type: DiscountType,
+    pub amount: Decimal,
+    pub min_subtotal: Option<Decimal>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub usage_limit: Option<i32>,
+    pub per_user_limit: Option<i32>,
+    #[serde(default)]
+    pub product_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub category_ids: Vec<Uuid>,
+}
+
+/// คูปองทั้งหมด (เฉพาะผู้ดูแลระบบ)
+pub async fn get_coupons(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let coupons = coupon_service::list_coupons(&db).await?;
+    Ok(HttpResponse::Ok().json(coupons))
+}
+
+/// สร้างคูปอง (เฉพาะผู้ดูแลระบบ)
+pub async fn create_coupon(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    data: web::Json<CreateCouponRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let data = data.into_inner();
+    let coupon = coupon_service::create_coupon(
+        &db,
+        NewCoupon {
+            code: data.code,
+            description: data.description,
+            discount_type: data.discount_type,
+            amount: data.amount,
+            min_subtotal: data.min_subtotal,
+            starts_at: data.starts_at,
+            ends_at: data.ends_at,
+            usage_limit: data.usage_limit,
+            per_user_limit: data.per_user_limit,
+            product_ids: data.product_ids,
+            category_ids: data.category_ids,
+        },
+    )
+    .await?;
+    Ok(HttpResponse::Created().json(coupon))
+}
+
+#[derive(Deserialize)]
+pub struct CouponPatchRequest {
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub description: Option<Option<String>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub amount: Option<Option<Decimal>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub min_subtotal: Option<Option<Decimal>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub starts_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub ends_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub usage_limit: Option<Option<i32>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub per_user_limit: Option<Option<i32>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub is_active: Option<Option<bool>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub product_ids: Option<Option<Vec<Uuid>>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub category_ids: Option<Option<Vec<Uuid>>>,
+}
+
+/// แก้เงื่อนไขหรือปิดคูปอง (เฉพาะผู้ดูแลระบบ)
+pub async fn patch_coupon(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    coupon_id: web::Path<Uuid>,
+    data: web::Json<CouponPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let data = data.into_inner();
+    let changes = CouponUpdate {
+        description: data.description,
+        amount: non_null("amount", data.amount)?,
+        min_subtotal: data.min_subtotal,
+        starts_at: data.starts_at,
+        ends_at: data.ends_at,
+        usage_limit: data.usage_limit,
+        per_user_limit: data.per_user_limit,
+        is_active: non_null("is_active", data.is_active)?,
+        product_ids: non_null("product_ids", data.product_ids)?,
+        category_ids: non_null("category_ids", data.category_ids)?,
+    };
+    let coupon = coupon_service::update_coupon(&db, coupon_id.into_inner(), changes).await?;
+    Ok(HttpResponse::Ok().json(coupon))
+}
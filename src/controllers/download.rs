@@ -0,0 +1,159 @@
+use crate::config::AppConfig;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::{download_service, media_store::PrivateMediaStore, vendor_service};
+use actix_multipart::Multipart;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpRequest, HttpResponse};
+use futures_util::TryStreamExt;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// ไฟล์ที่แนบกับสินค้าดิจิทัล (ผู้ดูแลระบบหรือร้านเจ้าของสินค้า)
+pub async fn get_product_files(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, *product_id).await?;
+
+    let assets = download_service::list_assets(&db, product_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(assets))
+}
+
+/// อัปโหลดแบบ multipart/form-data ฟิลด์ `file` และ `max_downloads` (ไม่บังคับ)
+pub async fn upload_product_file(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<PrivateMediaStore>,
+    config: web::Data<AppConfig>,
+    product_id: web::Path<Uuid>,
+    mut payload: Multipart,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, *product_id).await?;
+
+    let mut file: Option<(Vec<u8>, String, String)> = None;
+    let mut max_downloads: Option<i32> = None;
+
+    while let Some(mut field) = payload
+        .try_next()
+        .await
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        let file_name = field
+            .content_disposition()
+            .and_then(|disposition| disposition.get_filename())
+            .unwrap_or_default()
+            .to_string();
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.essence_str().to_string())
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+
+        // อ่านทีละ chunk และตัดทิ้งทันทีถ้าเกินขนาดที่กำหนด
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field
+            .try_next()
+            .await
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?
+        {
+            if bytes.len() + chunk.len() > config.max_digital_file_bytes {
+                return Err(ApiError::ValidationError(format!(
+                    "File must not exceed {} bytes",
+                    config.max_digital_file_bytes
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        match name.as_str() {
+            "file" => file = Some((bytes, file_name, content_type)),
+            "max_downloads" => {
+                let value = String::from_utf8(bytes).unwrap_or_default();
+                max_downloads = Some(value.trim().parse().map_err(|_| {
+                    ApiError::ValidationError("max_downloads must be a number".to_string())
+                })?)
+            }
+            _ => {}
+        }
+    }
+
+    let (bytes, file_name, content_type) =
+        file.ok_or_else(|| ApiError::ValidationError("Missing file field".to_string()))?;
+
+    let asset = download_service::upload_asset(
+        &db,
+        store.store(),
+        product_id.into_inner(),
+        &file_name,
+        &content_type,
+        bytes,
+        max_downloads,
+    )
+    .await?;
+    Ok(HttpResponse::Created().json(asset))
+}
+
+pub async fn delete_product_file(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<PrivateMediaStore>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (product_id, file_id)
+) -> Result<HttpResponse, ApiError> {
+    let (product_id, asset_id) = path.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    download_service::delete_asset(&db, store.store(), product_id, asset_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// ลิงก์ดาวน์โหลดของคำสั่งซื้อที่ชำระเงินแล้ว ลิงก์มีอายุตาม DOWNLOAD_URL_TTL_SECS
+pub async fn get_order_downloads(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    order_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let links = download_service::get_download_links(
+        &db,
+        &config,
+        current_user_id(&req)?,
+        order_id.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(links))
+}
+
+#[derive(Deserialize)]
+pub struct DownloadQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// ดาวน์โหลดไฟล์ผ่านลิงก์ที่เซ็นไว้ (ไม่ต้องมี token เข้าสู่ระบบ ลายเซ็นคือสิทธิ์)
+pub async fn download_file(
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<PrivateMediaStore>,
+    config: web::Data<AppConfig>,
+    grant_id: web::Path<Uuid>,
+    query: web::Query<DownloadQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let file = download_service::download(
+        &db,
+        store.store(),
+        &config,
+        grant_id.into_inner(),
+        query.expires,
+        &query.signature,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(file.content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(file.file_name)],
+        })
+        .body(file.bytes))
+}
@@ -0,0 +1,236 @@
+use crate::config::AppConfig;
+use crate::controllers::cart::{AddToCartRequest, SetQuantityRequest};
+use crate::entity::orders::ShippingAddress;
+use crate::error::ApiError;
+use crate::services::guest_cart_service::{self, CART_TOKEN_HEADER};
+use crate::services::guest_order_service;
+use crate::services::media_store::MediaStore;
+use crate::services::notification::NotificationChannel;
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+/// id ของตะกร้าจาก token ใน header `X-Cart-Token`
+fn cart_id(req: &HttpRequest, config: &AppConfig) -> Result<Uuid, ApiError> {
+    let token = req
+        .headers()
+        .get(CART_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| ApiError::AuthenticationError("Missing cart token".to_string()))?;
+    guest_cart_service::verify_token(&config.cart_token_secret, token)
+}
+
+/// สร้างตะกร้าใหม่ ต้องเก็บ token ที่ได้ไว้ส่งมากับทุกคำขอของตะกร้านี้
+pub async fn create_guest_cart(
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+) -> Result<HttpResponse, ApiError> {
+    let (cart, token) = guest_cart_service::create_cart(&db, &config.cart_token_secret).await?;
+    Ok(HttpResponse::Created().json(json!({ "cart": cart, "token": token })))
+}
+
+pub async fn add_to_guest_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    data: web::Json<AddToCartRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let cart_item = guest_cart_service::add_to_cart(
+        &db,
+        cart_id(&req, &config)?,
+        config.guest_cart_ttl_secs,
+        data.product_id,
+        data.quantity,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(cart_item))
+}
+
+/// ตั้งจำนวนของสินค้าในตะกร้า ส่ง 0 เพื่อเอาออก
+pub async fn set_guest_cart_quantity(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    product_id: web::Path<Uuid>,
+    data: web::Json<SetQuantityRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let cart_item = guest_cart_service::set_quantity(
+        &db,
+        cart_id(&req, &config)?,
+        config.guest_cart_ttl_secs,
+        product_id.into_inner(),
+        data.quantity,
+    )
+    .await?;
+    match cart_item {
+        Some(cart_item) => Ok(HttpResponse::Ok().json(cart_item)),
+        None => Ok(HttpResponse::Ok().body("Item removed from cart")),
+    }
+}
+
+pub async fn remove_from_guest_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    guest_cart_service::remove_from_cart(
+        &db,
+        cart_id(&req, &config)?,
+        config.guest_cart_ttl_secs,
+        product_id.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().body("Item removed from cart"))
+}
+
+pub async fn clear_guest_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+) -> Result<HttpResponse, ApiError> {
+    guest_cart_service::clear_cart(&db, cart_id(&req, &config)?, config.guest_cart_ttl_secs)
+        .await?;
+    Ok(HttpResponse::Ok().body("Cart cleared successfully"))
+}
+
+pub async fn get_guest_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    store: web::Data<dyn MediaStore>,
+) -> Result<HttpResponse, ApiError> {
+    let cart = guest_cart_service::get_cart(
+        &db,
+        store.get_ref(),
+        config.tax_rate,
+        cart_id(&req, &config)?,
+        config.guest_cart_ttl_secs,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(cart))
+}
+
+/// ยืนยันราคาและสถานะสินค้าที่เปลี่ยนไป เพื่อให้สั่งซื้อต่อได้
+pub async fn acknowledge_guest_cart_changes(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+) -> Result<HttpResponse, ApiError> {
+    let acknowledged = guest_cart_service::acknowledge_changes(
+        &db,
+        cart_id(&req, &config)?,
+        config.guest_cart_ttl_secs,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(json!({ "acknowledged": acknowledged })))
+}
+
+pub async fn calculate_guest_cart_total(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+) -> Result<HttpResponse, ApiError> {
+    let total_price = guest_cart_service::calculate_cart_total(
+        &db,
+        cart_id(&req, &config)?,
+        config.guest_cart_ttl_secs,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(json!({ "total_price": total_price })))
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ShippingAddressRequest {
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub name: String,
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub line1: String,
+    pub line2: Option<String>,
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub city: String,
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub postal_code: String,
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub country: String,
+    pub phone: Option<String>,
+}
+
+impl From<ShippingAddressRequest> for ShippingAddress {
+    fn from(address: ShippingAddressRequest) -> Self {
+        Self {
+            name: address.name,
+            line1: address.line1,
+            line2: address.line2,
+            city: address.city,
+            postal_code: address.postal_code,
+            country: address.country,
+            phone: address.phone,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct GuestCheckoutRequest {
+    #[validate(email(message = "invalid"))]
+    pub email: String,
+    #[validate(nested)]
+    pub shipping_address: ShippingAddressRequest,
+}
+
+/// สั่งซื้อโดยไม่มีบัญชี ได้ access token ไว้ดูสถานะคำสั่งซื้อ
+pub async fn checkout_guest_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    channel: web::Data<dyn NotificationChannel>,
+    data: web::Json<GuestCheckoutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let cart_id = cart_id(&req, &config)?;
+    let data = data.into_inner();
+    let strategy = config.allocation_strategy.strategy();
+    let (order, access_token) = guest_order_service::checkout(
+        &db,
+        channel.get_ref(),
+        &config,
+        strategy.as_ref(),
+        cart_id,
+        data.email,
+        data.shipping_address.into(),
+    )
+    .await?;
+    Ok(HttpResponse::Created().json(json!({ "order": order, "access_token": access_token })))
+}
+
+/// รวมตะกร้า guest เข้าตะกร้าของผู้ใช้หลังเข้าสู่ระบบหรือสมัครสมาชิก
+///
+/// ไม่ทำให้การเข้าสู่ระบบล้มเหลว token ที่ไม่ถูกต้องหรือรวมไม่สำเร็จจะถูกข้ามไป
+pub async fn merge_guest_cart(
+    req: &HttpRequest,
+    db: &DatabaseConnection,
+    config: &AppConfig,
+    user_id: Uuid,
+) {
+    if req.headers().get(CART_TOKEN_HEADER).is_none() {
+        return;
+    }
+    let Ok(cart_id) = cart_id(req, config) else {
+        return;
+    };
+    if let Err(e) =
+        guest_cart_service::merge_into_user_cart(db, cart_id, user_id, config.cart_merge_strategy)
+            .await
+    {
+        eprintln!("Failed to merge guest cart {}: {}", cart_id, e);
+    }
+}
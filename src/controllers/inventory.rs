@@ -0,0 +1,135 @@
+use crate::entity::sea_orm_active_enums::LocationKind;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::notification::NotificationChannel;
+use crate::services::{auth::require_admin, inventory_service, vendor_service};
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+pub async fn get_locations(
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let locations = inventory_service::list_locations(&db).await?;
+    Ok(HttpResponse::Ok().json(locations))
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CreateLocationRequest {
+    #[validate(length(min = 1, max = 32, message = "must be 1 - 32 characters long"))]
+    pub code: String,
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub name: String,
+    pub kind: LocationKind,
+    pub priority: Option<i32>,
+}
+
+/// เพิ่มคลังสินค้า (เฉพาะผู้ดูแลระบบ)
+pub async fn create_location(
+    req: HttpRequest,
+    data: web::Json<CreateLocationRequest>,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let location = inventory_service::create_location(
+        &db,
+        data.code.clone(),
+        data.name.clone(),
+        data.kind,
+        data.priority.unwrap_or(0),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(location))
+}
+
+/// ดึงสต็อกทั้งหมดในคลัง
+pub async fn get_location_stock(
+    db: web::Data<DatabaseConnection>,
+    location_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let levels = inventory_service::get_location_stock(&db, location_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(levels))
+}
+
+/// ดึงสต็อกของสินค้าในทุกคลัง
+pub async fn get_product_stock(
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let levels = inventory_service::get_product_stock(&db, product_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(levels))
+}
+
+#[derive(Deserialize, Validate)]
+pub struct AdjustStockRequest {
+    pub product_id: Uuid,
+    #[validate(range(min = 0, message = "must not be negative"))]
+    pub counted_quantity: i32,
+    pub note: Option<String>,
+}
+
+/// บันทึกผลการนับสต็อก (cycle count) ร้านค้าปรับได้เฉพาะสินค้าของตัวเอง
+pub async fn adjust_stock(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    channel: web::Data<dyn NotificationChannel>,
+    location_id: web::Path<Uuid>,
+    data: web::Json<AdjustStockRequest>,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, data.product_id).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let level = inventory_service::adjust_stock(
+        &db,
+        channel.get_ref(),
+        location_id.into_inner(),
+        data.product_id,
+        data.counted_quantity,
+        data.note.clone(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(level))
+}
+
+#[derive(Deserialize, Validate)]
+pub struct TransferStockRequest {
+    pub product_id: Uuid,
+    pub from_location_id: Uuid,
+    pub to_location_id: Uuid,
+    #[validate(range(min = 1, message = "must be at least 1"))]
+    pub quantity: i32,
+    pub note: Option<String>,
+}
+
+/// ย้ายสต็อกระหว่างคลัง ร้านค้าย้ายได้เฉพาะสินค้าของตัวเอง
+pub async fn transfer_stock(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    channel: web::Data<dyn NotificationChannel>,
+    data: web::Json<TransferStockRequest>,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, data.product_id).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    inventory_service::transfer_stock(
+        &db,
+        channel.get_ref(),
+        data.product_id,
+        data.from_location_id,
+        data.to_location_id,
+        data.quantity,
+        data.note.clone(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().body("Stock transferred successfully"))
+}
@@ -1,6 +1,24 @@
 pub mod product;
+pub mod product_image;
 pub mod user;
 pub mod cart;
+pub mod guest_cart;
+pub mod named_cart;
+pub mod cart_reminder;
 pub mod order;
+pub mod inventory;
+pub mod price;
+pub mod review;
+pub mod attribute;
+pub mod category;
+pub mod translation;
+pub mod recommendation;
+pub mod wishlist;
+pub mod alert;
+pub mod bundle;
+pub mod vendor;
+pub mod coupon;
+pub mod download;
+pub mod patch;
 
 // pub use user::{register, login};
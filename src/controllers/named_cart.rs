@@ -0,0 +1,206 @@
+use crate::config::AppConfig;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::media_store::MediaStore;
+use crate::services::{cart_service, named_cart_service, order_service};
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct CartNameRequest {
+    #[validate(length(min = 1, max = 100, message = "must be 1 - 100 characters long"))]
+    pub name: String,
+}
+
+pub async fn get_carts(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let carts = named_cart_service::list_carts(&db, current_user_id(&req)?).await?;
+    Ok(HttpResponse::Ok().json(carts))
+}
+
+pub async fn create_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    data: web::Json<CartNameRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let cart =
+        named_cart_service::create_cart(&db, current_user_id(&req)?, data.into_inner().name)
+            .await?;
+    Ok(HttpResponse::Created().json(cart))
+}
+
+pub async fn get_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    store: web::Data<dyn MediaStore>,
+    cart_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let cart = named_cart_service::get_cart(
+        &db,
+        store.get_ref(),
+        config.tax_rate,
+        current_user_id(&req)?,
+        cart_id.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(cart))
+}
+
+pub async fn rename_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    cart_id: web::Path<Uuid>,
+    data: web::Json<CartNameRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let cart = named_cart_service::rename_cart(
+        &db,
+        current_user_id(&req)?,
+        cart_id.into_inner(),
+        data.into_inner().name,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(cart))
+}
+
+pub async fn delete_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    cart_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    named_cart_service::delete_cart(&db, current_user_id(&req)?, cart_id.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// สลับตะกร้าที่ใช้งานอยู่ endpoint ของ `/cart/{user_id}` จะทำงานกับตะกร้านี้
+pub async fn activate_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    cart_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let cart =
+        named_cart_service::activate_cart(&db, current_user_id(&req)?, cart_id.into_inner())
+            .await?;
+    Ok(HttpResponse::Ok().json(cart))
+}
+
+/// ยืนยันราคาและสถานะสินค้าที่เปลี่ยนไปในตะกร้านี้
+pub async fn acknowledge_cart_changes(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    cart_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let acknowledged = cart_service::acknowledge_changes(
+        &db,
+        current_user_id(&req)?,
+        Some(cart_id.into_inner()),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(json!({ "acknowledged": acknowledged })))
+}
+
+/// สั่งซื้อจากตะกร้านี้ ไม่จำเป็นต้องเป็นตะกร้าที่ใช้งานอยู่
+pub async fn checkout_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    cart_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let strategy = config.allocation_strategy.strategy();
+    let order = order_service::create_order(
+        &db,
+        current_user_id(&req)?,
+        Some(cart_id.into_inner()),
+        strategy.as_ref(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(order))
+}
+
+#[derive(Deserialize)]
+pub struct MoveCartItemRequest {
+    pub target_cart_id: Uuid,
+}
+
+pub async fn move_cart_item(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (cart_id, product_id)
+    data: web::Json<MoveCartItemRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let (cart_id, product_id) = path.into_inner();
+    let cart_item = named_cart_service::move_item(
+        &db,
+        current_user_id(&req)?,
+        cart_id,
+        product_id,
+        data.target_cart_id,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(cart_item))
+}
+
+pub async fn save_for_later(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (cart_id, product_id)
+) -> Result<HttpResponse, ApiError> {
+    let (cart_id, product_id) = path.into_inner();
+    let saved =
+        named_cart_service::save_for_later(&db, current_user_id(&req)?, cart_id, product_id)
+            .await?;
+    Ok(HttpResponse::Ok().json(saved))
+}
+
+pub async fn get_saved_items(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let items = named_cart_service::list_saved(&db, current_user_id(&req)?).await?;
+    Ok(HttpResponse::Ok().json(items))
+}
+
+pub async fn remove_saved_item(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    named_cart_service::remove_saved(&db, current_user_id(&req)?, product_id.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize)]
+pub struct MoveSavedItemRequest {
+    /// ไม่ระบุคือตะกร้าที่ใช้งานอยู่
+    pub cart_id: Option<Uuid>,
+}
+
+pub async fn move_saved_to_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+    data: Option<web::Json<MoveSavedItemRequest>>,
+) -> Result<HttpResponse, ApiError> {
+    let cart_id = data.and_then(|data| data.cart_id);
+    let cart_item = named_cart_service::move_saved_to_cart(
+        &db,
+        current_user_id(&req)?,
+        product_id.into_inner(),
+        cart_id,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(cart_item))
+}
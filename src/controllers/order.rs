@@ -1,22 +1,42 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use uuid::Uuid;
-use crate::{services::order_service, error::ApiError};
+use crate::{config::AppConfig, services::order_service, error::ApiError};
+use crate::controllers::patch::non_null;
+use crate::middleware::precondition::{etag, if_match_version};
+use crate::middleware::auth::current_user_id;
+use crate::services::auth::{is_admin, require_admin};
+use crate::services::guest_order_service;
+use crate::services::notification::NotificationChannel;
+use crate::services::order_service::{OrderStatus, OrderUpdate};
+use serde::Deserialize;
 use sea_orm::DatabaseConnection;
 
 pub async fn create_order(
     db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
     user_id: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
-    let order = order_service::create_order(&db, user_id.into_inner()).await?;
+    let strategy = config.allocation_strategy.strategy();
+    let order = order_service::create_order(&db, user_id.into_inner(), None, strategy.as_ref()).await?;
     Ok(HttpResponse::Ok().json(order))
 }
 
+/// เจ้าของคำสั่งซื้อหรือผู้ดูแลระบบเท่านั้นที่ดูได้
 pub async fn get_order_details(
+    req: HttpRequest,
     db: web::Data<DatabaseConnection>,
     order_id: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
+    let user_id = current_user_id(&req)?;
     let (order, items) = order_service::get_order_details(&db, order_id.into_inner()).await?;
-    Ok(HttpResponse::Ok().json((order, items)))
+    if order.user_id != Some(user_id) && !is_admin(&db, user_id).await? {
+        return Err(ApiError::Forbidden(
+            "Users can only view their own orders".to_string(),
+        ));
+    }
+    Ok(HttpResponse::Ok()
+        .insert_header(etag(order.version))
+        .json((order, items)))
 }
 
 pub async fn get_order_history(
@@ -27,11 +47,102 @@ pub async fn get_order_history(
     Ok(HttpResponse::Ok().json(orders))
 }
 
+/// ต้องส่ง `If-Match` เป็น ETag ที่ได้จากหน้ารายละเอียดคำสั่งซื้อ (เฉพาะผู้ดูแลระบบ)
 pub async fn update_order_status(
+    req: HttpRequest,
     db: web::Data<DatabaseConnection>,
+    channel: web::Data<dyn NotificationChannel>,
     order_id: web::Path<Uuid>,
     new_status: web::Json<String>,
 ) -> Result<HttpResponse, ApiError> {
-    order_service::update_order_status(&db, order_id.into_inner(), new_status.into_inner()).await?;
-    Ok(HttpResponse::Ok().body("Order status updated successfully"))
+    require_admin(&db, current_user_id(&req)?).await?;
+    let expected_version = if_match_version(&req)?;
+    let order = order_service::update_order_status(
+        &db,
+        channel.get_ref(),
+        order_id.into_inner(),
+        expected_version,
+        new_status.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok()
+        .insert_header(etag(order.version))
+        .body("Order status updated successfully"))
+}
+
+#[derive(Deserialize)]
+pub struct OrderPatchRequest {
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub status: Option<Option<String>>,
+}
+
+/// แก้เฉพาะฟิลด์ที่ส่งมาแบบ JSON Merge Patch ต้องส่ง `If-Match` เหมือนกับ PUT
+///
+/// ผู้ดูแลระบบแก้ได้ทุกคำสั่งซื้อ ลูกค้าทำได้แค่ยกเลิกคำสั่งซื้อของตัวเองที่ยังไม่ชำระเงิน
+pub async fn patch_order(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    channel: web::Data<dyn NotificationChannel>,
+    order_id: web::Path<Uuid>,
+    data: web::Json<OrderPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = current_user_id(&req)?;
+    let expected_version = if_match_version(&req)?;
+    let data = data.into_inner();
+    let changes = OrderUpdate {
+        status: non_null("status", data.status)?
+            .map(|status| order_service::parse_order_status(&status))
+            .transpose()?,
+    };
+
+    let order_id = order_id.into_inner();
+    let order = if is_admin(&db, user_id).await? {
+        order_service::update_order(&db, channel.get_ref(), order_id, expected_version, changes)
+            .await?
+    } else if changes.status == Some(OrderStatus::Cancelled) {
+        order_service::cancel_own_order(
+            &db,
+            channel.get_ref(),
+            user_id,
+            order_id,
+            expected_version,
+        )
+        .await?
+    } else {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    };
+    Ok(HttpResponse::Ok().insert_header(etag(order.version)).json(order))
+}
+
+/// คำสั่งซื้อของ guest ดูได้ด้วย access token ที่ได้ตอนสั่งซื้อ ไม่ต้องเข้าสู่ระบบ
+pub async fn get_guest_order(
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    token: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let (order, items) =
+        guest_order_service::get_order(&db, &config.order_token_secret, &token).await?;
+    Ok(HttpResponse::Ok().json((order, items)))
+}
+
+#[derive(Deserialize)]
+pub struct ClaimOrderRequest {
+    pub access_token: String,
+}
+
+/// รับคำสั่งซื้อที่สั่งแบบ guest เข้าบัญชีของตัวเอง
+pub async fn claim_order(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    data: web::Json<ClaimOrderRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let order = guest_order_service::claim_order(
+        &db,
+        &config.order_token_secret,
+        current_user_id(&req)?,
+        &data.access_token,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(order))
 }
@@ -0,0 +1,14 @@
+//! ตัวช่วยสำหรับ PATCH แบบ JSON Merge Patch (RFC 7396)
+//!
+//! ฟิลด์ใน request ใช้ `Option<Option<T>>` คู่กับ `serde_with::rust::double_option`
+//! ไม่ส่งมา = `None` (คงค่าเดิม), `null` = `Some(None)` (ล้างค่า), มีค่า = `Some(Some(v))`
+use crate::error::ApiError;
+
+/// ฟิลด์ที่ห้ามเป็น null ถ้าส่ง null มาจะได้ 400 แทนการล้างค่า
+pub fn non_null<T>(field: &str, value: Option<Option<T>>) -> Result<Option<T>, ApiError> {
+    match value {
+        Some(None) => Err(ApiError::ValidationError(format!("{}: cannot be null", field))),
+        Some(Some(value)) => Ok(Some(value)),
+        None => Ok(None),
+    }
+}
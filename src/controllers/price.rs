@@ -0,0 +1,60 @@
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::{auth::require_admin, price_service};
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// ประวัติราคาและราคาที่ตั้งเวลาไว้ของสินค้า (เฉพาะผู้ดูแลระบบ)
+pub async fn get_price_history(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let timeline = price_service::get_price_timeline(&db, product_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(timeline))
+}
+
+#[derive(Deserialize)]
+pub struct SchedulePriceRequest {
+    pub sale_price: Decimal,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+pub async fn schedule_price(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+    data: web::Json<SchedulePriceRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let schedule = price_service::schedule_price(
+        &db,
+        product_id.into_inner(),
+        data.sale_price,
+        data.starts_at,
+        data.ends_at,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(schedule))
+}
+
+pub async fn cancel_price_schedule(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (product_id, schedule_id)
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let (product_id, schedule_id) = path.into_inner();
+    let schedule = price_service::cancel_schedule(&db, product_id, schedule_id).await?;
+    Ok(HttpResponse::Ok().json(schedule))
+}
@@ -1,27 +1,76 @@
-use crate::services::product_service;
-use actix_web::{web::{self}, HttpResponse};
+use crate::entity::sea_orm_active_enums::{ProductStatus, ProductType};
+use crate::config::AppConfig;
+use crate::middleware::{
+    auth::current_user_id,
+    locale::request_locale,
+    precondition::{etag, if_match_version},
+};
+use crate::services::{
+    attribute_service, auth::require_admin, catalog_csv_service, media_store::MediaStore,
+    notification::NotificationChannel,
+    product_service, product_service::{NewProduct, ProductListResponse, ProductSort, ProductUpdate}, translation_service,
+    vendor_service,
+};
+use actix_web::{http::header::CONTENT_LANGUAGE, web::{self}, HttpRequest, HttpResponse};
 use rust_decimal::Decimal;
 use sea_orm::DatabaseConnection;
 use serde::Deserialize;
 use serde_with::{serde_as, FromInto};
+use std::collections::HashMap;
 use uuid::Uuid;
+use crate::controllers::patch::non_null;
 use crate::error::ApiError;
+use validator::{Validate, ValidationError};
+
+#[derive(Deserialize)]
+pub struct ProductListQuery {
+    #[serde(default)]
+    pub sort: ProductSort,
+    pub category_id: Option<Uuid>,
+    /// ตัวกรอง attribute ในรูป `attr.<code>=<value>`
+    #[serde(flatten)]
+    pub filters: HashMap<String, String>,
+}
 
 pub async fn get_products(
+    req: HttpRequest,
     db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    store: web::Data<dyn MediaStore>,
+    query: web::Query<ProductListQuery>,
 ) -> Result<HttpResponse, ApiError> {
-    let products = product_service::get_all_products(&db).await?;
-    Ok(HttpResponse::Ok().json(products))
+    let locale = request_locale(&req, &config);
+    let filters = attribute_service::parse_filters(&db, &query.filters).await?;
+    let mut products =
+        product_service::get_all_products(&db, query.sort, query.category_id, &filters).await?;
+    translation_service::localize_products(&db, &mut products, &locale, &config).await?;
+    let items = product_service::build_product_responses(&db, store.get_ref(), products).await?;
+    let facets = attribute_service::get_facets(&db, query.category_id, &filters).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header((CONTENT_LANGUAGE, locale))
+        .json(ProductListResponse { items, facets }))
 }
 
 pub async fn get_product(
+    req: HttpRequest,
     db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    store: web::Data<dyn MediaStore>,
     product_id: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
-    let product = product_service::get_product_by_id(&db, product_id.into_inner())
+    let locale = request_locale(&req, &config);
+    let mut product = product_service::get_product_by_id(&db, product_id.into_inner())
+        .await?;
+    translation_service::localize_products(&db, std::slice::from_mut(&mut product), &locale, &config)
         .await?;
+    let version = product.version;
+    let product = product_service::build_product_response(&db, store.get_ref(), product).await?;
 
-    Ok(HttpResponse::Ok().json(product))
+    Ok(HttpResponse::Ok()
+        .insert_header((CONTENT_LANGUAGE, locale))
+        .insert_header(etag(version))
+        .json(product))
 }
 
 #[serde_as]
@@ -31,79 +80,253 @@ pub struct CreateProductRequest {
     description: Option<String>,
     #[serde_as(as = "FromInto<Decimal>")]
     price: Decimal,
-    status: Option<String>,
+    status: Option<ProductStatus>,
+    is_one_of_a_kind: Option<bool>,
+    sku: Option<String>,
+    product_type: Option<ProductType>,
+    min_quantity: Option<i32>,
+    max_quantity: Option<i32>,
+    quantity_step: Option<i32>,
 }
 
+/// สินค้าที่ร้านค้าสร้างจะเป็นของร้านนั้น ที่ผู้ดูแลระบบสร้างเป็นของแพลตฟอร์ม
 pub async fn create_product(
+    req: HttpRequest,
     data: web::Json<CreateProductRequest>,
     db: web::Data<DatabaseConnection>,
 ) -> Result<HttpResponse, ApiError> {
-    let valid_statuses = vec!["available", "reserved", "sold"];
-    if let Some(status) = &data.status {
-        if !valid_statuses.contains(&status.as_str()) {
-            return Err(ApiError::ValidationError(format!(
-                "Invalid status: {}",
-                status
-            )));
-        }
-    }
-
+    let vendor_id = vendor_service::owner_for_new_product(&db, current_user_id(&req)?).await?;
+    let data = data.into_inner();
     let product = product_service::create_product(
         &db,
-        data.name.clone(),
-        data.description.clone(),
-        data.price,
-        data.status.clone(),
+        NewProduct {
+            name: data.name,
+            description: data.description,
+            price: data.price,
+            status: data.status,
+            is_one_of_a_kind: data.is_one_of_a_kind.unwrap_or(false),
+            sku: data.sku,
+            product_type: data.product_type.unwrap_or_default(),
+            vendor_id,
+            min_quantity: data.min_quantity.unwrap_or(1),
+            max_quantity: data.max_quantity,
+            quantity_step: data.quantity_step.unwrap_or(1),
+        },
     )
     .await?;
 
     Ok(HttpResponse::Created().json(product))
 }
+/// ต้องส่ง `If-Match` เป็น ETag ที่ได้จาก GET ถ้ามีคนแก้ไปก่อนจะได้ 412
 pub async fn update_product(
+    req: HttpRequest,
     product_id: web::Path<Uuid>,
     data: web::Json<CreateProductRequest>,
     db: web::Data<DatabaseConnection>,
+    store: web::Data<dyn MediaStore>,
+    channel: web::Data<dyn NotificationChannel>,
 ) -> Result<HttpResponse, ApiError> {
+    let product_id = product_id.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    let expected_version = if_match_version(&req)?;
+    let data = data.into_inner();
+    let changes = ProductUpdate {
+        name: Some(data.name),
+        description: data.description.map(Some),
+        price: Some(data.price),
+        is_one_of_a_kind: data.is_one_of_a_kind,
+        sku: data.sku.map(Some),
+        product_type: data.product_type,
+        min_quantity: data.min_quantity,
+        max_quantity: data.max_quantity.map(Some),
+        quantity_step: data.quantity_step,
+    };
     let product = product_service::update_product(
         &db,
-        product_id.into_inner(),
-        Some(data.name.clone()),
-        data.description.clone(),
-        Some(data.price),
+        channel.get_ref(),
+        product_id,
+        expected_version,
+        changes,
     )
     .await?;
+    let version = product.version;
+    let product = product_service::build_product_response(&db, store.get_ref(), product).await?;
 
-    Ok(HttpResponse::Ok().json(product))
+    Ok(HttpResponse::Ok().insert_header(etag(version)).json(product))
+}
+#[derive(Deserialize, Validate)]
+pub struct ProductPatchRequest {
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub name: Option<Option<String>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub description: Option<Option<String>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    #[validate(custom(function = "validate_price"))]
+    pub price: Option<Option<Decimal>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub is_one_of_a_kind: Option<Option<bool>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub sku: Option<Option<String>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub product_type: Option<Option<ProductType>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub min_quantity: Option<Option<i32>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub max_quantity: Option<Option<i32>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub quantity_step: Option<Option<i32>>,
+}
+
+fn validate_price(price: &Decimal) -> Result<(), ValidationError> {
+    if *price <= Decimal::ZERO {
+        return Err(ValidationError::new("price").with_message("must be greater than zero".into()));
+    }
+    Ok(())
 }
+
+/// แก้เฉพาะฟิลด์ที่ส่งมา ส่ง `null` เพื่อล้าง description, sku หรือ max_quantity
+pub async fn patch_product(
+    req: HttpRequest,
+    product_id: web::Path<Uuid>,
+    data: web::Json<ProductPatchRequest>,
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<dyn MediaStore>,
+    channel: web::Data<dyn NotificationChannel>,
+) -> Result<HttpResponse, ApiError> {
+    let product_id = product_id.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    let expected_version = if_match_version(&req)?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let data = data.into_inner();
+    let changes = ProductUpdate {
+        name: non_null("name", data.name)?,
+        description: data.description,
+        price: non_null("price", data.price)?,
+        is_one_of_a_kind: non_null("is_one_of_a_kind", data.is_one_of_a_kind)?,
+        sku: data.sku,
+        product_type: non_null("product_type", data.product_type)?,
+        min_quantity: non_null("min_quantity", data.min_quantity)?,
+        max_quantity: data.max_quantity,
+        quantity_step: non_null("quantity_step", data.quantity_step)?,
+    };
+    let product = product_service::update_product(
+        &db,
+        channel.get_ref(),
+        product_id,
+        expected_version,
+        changes,
+    )
+    .await?;
+    let version = product.version;
+    let product = product_service::build_product_response(&db, store.get_ref(), product).await?;
+
+    Ok(HttpResponse::Ok().insert_header(etag(version)).json(product))
+}
+
+
 pub async fn delete_product(
+    req: HttpRequest,
     product_id: web::Path<Uuid>,
     db: web::Data<DatabaseConnection>,
 ) -> Result<HttpResponse, ApiError> {
-    product_service::delete_product(&db, product_id.into_inner()).await?;
+    let product_id = product_id.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    let expected_version = if_match_version(&req)?;
+    product_service::delete_product(&db, product_id, expected_version).await?;
     Ok(HttpResponse::Ok().body("Product deleted successfully"))
 }
 
+/// รายการสินค้าที่ถูกเก็บ (เฉพาะผู้ดูแลระบบ)
+pub async fn get_archived_products(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let products = product_service::get_archived_products(&db).await?;
+    Ok(HttpResponse::Ok().json(products))
+}
+
+/// กู้คืนสินค้าที่ถูกเก็บ (เฉพาะผู้ดูแลระบบ)
+pub async fn restore_product(
+    req: HttpRequest,
+    product_id: web::Path<Uuid>,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let product = product_service::restore_product(&db, product_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(product))
+}
+
 #[derive(Deserialize)]
 pub struct UpdateProductStatusRequest {
-    pub status: String,
+    pub status: ProductStatus,
 }
 
 pub async fn update_product_status(
+    req: HttpRequest,
     product_id: web::Path<Uuid>,
     data: web::Json<UpdateProductStatusRequest>,
     db: web::Data<DatabaseConnection>,
+    channel: web::Data<dyn NotificationChannel>,
 ) -> Result<HttpResponse, ApiError> {
-    let valid_statuses = vec!["available", "reserved", "sold"];
+    let product_id = product_id.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    let expected_version = if_match_version(&req)?;
+    let product = product_service::update_product_status(
+        &db,
+        channel.get_ref(),
+        product_id,
+        expected_version,
+        data.status,
+    )
+    .await?;
 
-    if !valid_statuses.contains(&data.status.as_str()) {
-        return Err(ApiError::ValidationError(format!(
-            "Invalid status: {}",
-            data.status
-        )));
+    Ok(HttpResponse::Ok()
+        .insert_header(etag(product.version))
+        .body("Product status updated successfully"))
+}
+#[derive(Deserialize)]
+pub struct ImportProductsQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// นำเข้าสินค้าจาก CSV (body เป็น text/csv) ถ้ามีแถวผิดจะตอบ 400 พร้อมเลขบรรทัด
+pub async fn import_products(
+    req: HttpRequest,
+    query: web::Query<ImportProductsQuery>,
+    body: web::Bytes,
+    db: web::Data<DatabaseConnection>,
+    channel: web::Data<dyn NotificationChannel>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let report =
+        catalog_csv_service::import_products(&db, channel.get_ref(), &body, query.dry_run)
+            .await?;
+    if report.errors.is_empty() {
+        Ok(HttpResponse::Ok().json(report))
+    } else {
+        Ok(HttpResponse::BadRequest().json(report))
     }
+}
 
-    product_service::update_product_status(&db, product_id.into_inner(), data.status.clone())
-        .await?;
+/// ส่งออกแคตตาล็อกเป็น CSV ในรูปแบบเดียวกับที่นำเข้าได้
+pub async fn export_products(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
 
-    Ok(HttpResponse::Ok().body("Product status updated successfully"))
-}
\ No newline at end of file
+    let csv = catalog_csv_service::export_products(&db).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header(("Content-Disposition", "attachment; filename=\"products.csv\""))
+        .body(csv))
+}
@@ -0,0 +1,154 @@
+use crate::config::AppConfig;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::image_service::{self, ProductImageResponse};
+use crate::services::media_store::MediaStore;
+use crate::services::vendor_service;
+use actix_multipart::Multipart;
+use actix_web::{web, HttpRequest, HttpResponse};
+use futures_util::TryStreamExt;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// รายการรูปของสินค้า เรียงตามลำดับที่ตั้งไว้
+pub async fn get_product_images(
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<dyn MediaStore>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let images = image_service::list_images(&db, product_id.into_inner()).await?;
+    let images: Vec<ProductImageResponse> = images
+        .iter()
+        .map(|image| ProductImageResponse::new(image, store.get_ref()))
+        .collect();
+    Ok(HttpResponse::Ok().json(images))
+}
+
+/// อัปโหลดรูปแบบ multipart/form-data ฟิลด์ `file` และ `alt_text` (ไม่บังคับ)
+pub async fn upload_product_image(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<dyn MediaStore>,
+    config: web::Data<AppConfig>,
+    product_id: web::Path<Uuid>,
+    mut payload: Multipart,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, *product_id).await?;
+    let mut file: Option<(Vec<u8>, String)> = None;
+    let mut alt_text: Option<String> = None;
+
+    while let Some(mut field) = payload
+        .try_next()
+        .await
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.essence_str().to_string())
+            .unwrap_or_default();
+
+        // อ่านทีละ chunk และตัดทิ้งทันทีถ้าเกินขนาดที่กำหนด
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field
+            .try_next()
+            .await
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?
+        {
+            if bytes.len() + chunk.len() > config.max_image_bytes {
+                return Err(ApiError::ValidationError(format!(
+                    "Image must not exceed {} bytes",
+                    config.max_image_bytes
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        match name.as_str() {
+            "file" => file = Some((bytes, content_type)),
+            "alt_text" => {
+                alt_text = Some(String::from_utf8(bytes).map_err(|_| {
+                    ApiError::ValidationError("alt_text must be valid UTF-8".to_string())
+                })?)
+            }
+            _ => {}
+        }
+    }
+
+    let (bytes, content_type) =
+        file.ok_or_else(|| ApiError::ValidationError("Missing file field".to_string()))?;
+
+    let image = image_service::upload_image(
+        &db,
+        store.get_ref(),
+        product_id.into_inner(),
+        bytes,
+        &content_type,
+        alt_text.filter(|text| !text.is_empty()),
+        config.max_image_bytes,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ProductImageResponse::new(&image, store.get_ref())))
+}
+
+#[derive(Deserialize)]
+pub struct UpdateProductImageRequest {
+    pub alt_text: Option<String>,
+    pub is_primary: Option<bool>,
+}
+
+pub async fn update_product_image(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<dyn MediaStore>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (product_id, image_id)
+    data: web::Json<UpdateProductImageRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let (product_id, image_id) = path.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    let data = data.into_inner();
+    let image =
+        image_service::update_image(&db, product_id, image_id, data.alt_text, data.is_primary)
+            .await?;
+    Ok(HttpResponse::Ok().json(ProductImageResponse::new(&image, store.get_ref())))
+}
+
+#[derive(Deserialize)]
+pub struct ReorderProductImagesRequest {
+    pub image_ids: Vec<Uuid>,
+}
+
+pub async fn reorder_product_images(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<dyn MediaStore>,
+    product_id: web::Path<Uuid>,
+    data: web::Json<ReorderProductImagesRequest>,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, *product_id).await?;
+    let images = image_service::reorder_images(
+        &db,
+        product_id.into_inner(),
+        data.into_inner().image_ids,
+    )
+    .await?;
+    let images: Vec<ProductImageResponse> = images
+        .iter()
+        .map(|image| ProductImageResponse::new(image, store.get_ref()))
+        .collect();
+    Ok(HttpResponse::Ok().json(images))
+}
+
+pub async fn delete_product_image(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    store: web::Data<dyn MediaStore>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (product_id, image_id)
+) -> Result<HttpResponse, ApiError> {
+    let (product_id, image_id) = path.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    image_service::delete_image(&db, store.get_ref(), product_id, image_id).await?;
+    Ok(HttpResponse::Ok().body("Image deleted successfully"))
+}
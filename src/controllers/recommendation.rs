@@ -0,0 +1,82 @@
+use crate::config::AppConfig;
+use crate::error::ApiError;
+use crate::middleware::{auth::current_user_id, locale::request_locale};
+use crate::services::{
+    auth::require_admin, media_store::MediaStore, product_service, recommendation_service,
+    translation_service,
+};
+use actix_web::{http::header::CONTENT_LANGUAGE, web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// สินค้าที่มักซื้อร่วมกัน รวมกับที่ทีม merchandise pin ไว้
+pub async fn get_related_products(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    store: web::Data<dyn MediaStore>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let locale = request_locale(&req, &config);
+    let mut products = recommendation_service::get_related_products(
+        &db,
+        product_id.into_inner(),
+        config.related_products_limit,
+    )
+    .await?;
+    translation_service::localize_products(&db, &mut products, &locale, &config).await?;
+    let products = product_service::build_product_responses(&db, store.get_ref(), products).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header((CONTENT_LANGUAGE, locale))
+        .json(products))
+}
+
+pub async fn get_pinned_related_products(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let pins = recommendation_service::get_pinned_relations(&db, product_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(pins))
+}
+
+#[derive(Deserialize)]
+pub struct PinRelatedProductRequest {
+    #[serde(default)]
+    pub position: i32,
+}
+
+pub async fn pin_related_product(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (product_id, related_product_id)
+    data: web::Json<PinRelatedProductRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let (product_id, related_product_id) = path.into_inner();
+    let pin = recommendation_service::pin_related_product(
+        &db,
+        product_id,
+        related_product_id,
+        data.position,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(pin))
+}
+
+pub async fn unpin_related_product(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let (product_id, related_product_id) = path.into_inner();
+    recommendation_service::unpin_related_product(&db, product_id, related_product_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,90 @@
+use crate::entity::sea_orm_active_enums::ReviewStatus;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::{auth::require_admin, review_service};
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct CreateReviewRequest {
+    #[validate(range(min = 1, max = 5, message = "must be between 1 and 5"))]
+    pub rating: i32,
+    #[validate(length(max = 5000, message = "must be at most 5000 characters long"))]
+    pub body: Option<String>,
+}
+
+pub async fn create_review(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+    data: web::Json<CreateReviewRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let review = review_service::create_review(
+        &db,
+        current_user_id(&req)?,
+        product_id.into_inner(),
+        data.rating,
+        data.body.clone(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(review))
+}
+
+pub async fn get_product_reviews(
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let reviews = review_service::get_product_reviews(&db, product_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(reviews))
+}
+
+#[derive(Deserialize)]
+pub struct ReviewListQuery {
+    pub status: Option<ReviewStatus>,
+}
+
+/// คิวรีวิวสำหรับผู้ดูแลระบบ ค่าเริ่มต้นคือรีวิวที่รอตรวจ
+pub async fn get_reviews(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    query: web::Query<ReviewListQuery>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let status = query.status.unwrap_or(ReviewStatus::Pending);
+    let reviews = review_service::get_reviews_by_status(&db, status).await?;
+    Ok(HttpResponse::Ok().json(reviews))
+}
+
+#[derive(Deserialize)]
+pub struct ModerateReviewRequest {
+    pub status: ReviewStatus,
+    pub note: Option<String>,
+}
+
+pub async fn moderate_review(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    review_id: web::Path<Uuid>,
+    data: web::Json<ModerateReviewRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let admin = require_admin(&db, current_user_id(&req)?).await?;
+
+    let review = review_service::moderate_review(
+        &db,
+        review_id.into_inner(),
+        admin.id,
+        data.status,
+        data.note.clone(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(review))
+}
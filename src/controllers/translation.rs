@@ -0,0 +1,135 @@
+use crate::config::AppConfig;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::{auth::require_admin, translation_service, vendor_service};
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct TranslationRequest {
+    #[validate(length(min = 1, max = 255, message = "must be 1 - 255 characters long"))]
+    pub name: String,
+    pub description: Option<String>,
+}
+
+pub async fn get_product_translations(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    product_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    vendor_service::authorize_product(&db, current_user_id(&req)?, *product_id).await?;
+
+    let translations =
+        translation_service::get_product_translations(&db, product_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(translations))
+}
+
+pub async fn put_product_translation(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    path: web::Path<(Uuid, String)>, // Tuple ของ (product_id, locale)
+    data: web::Json<TranslationRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let (product_id, locale) = path.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let data = data.into_inner();
+    let translation = translation_service::upsert_product_translation(
+        &db,
+        &config,
+        product_id,
+        &locale.to_lowercase(),
+        data.name,
+        data.description,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(translation))
+}
+
+pub async fn delete_product_translation(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, String)>,
+) -> Result<HttpResponse, ApiError> {
+    let (product_id, locale) = path.into_inner();
+    vendor_service::authorize_product(&db, current_user_id(&req)?, product_id).await?;
+    translation_service::delete_product_translation(&db, product_id, &locale.to_lowercase())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn get_category_translations(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    category_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let translations =
+        translation_service::get_category_translations(&db, category_id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(translations))
+}
+
+pub async fn put_category_translation(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    path: web::Path<(Uuid, String)>, // Tuple ของ (category_id, locale)
+    data: web::Json<TranslationRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let (category_id, locale) = path.into_inner();
+    let data = data.into_inner();
+    let translation = translation_service::upsert_category_translation(
+        &db,
+        &config,
+        category_id,
+        &locale.to_lowercase(),
+        data.name,
+        data.description,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(translation))
+}
+
+pub async fn delete_category_translation(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, String)>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let (category_id, locale) = path.into_inner();
+    translation_service::delete_category_translation(&db, category_id, &locale.to_lowercase())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize)]
+pub struct MissingTranslationsQuery {
+    pub locale: Option<String>,
+}
+
+/// สินค้าและหมวดหมู่ที่ยังไม่มีคำแปล (เฉพาะผู้ดูแลระบบ)
+pub async fn get_missing_translations(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    query: web::Query<MissingTranslationsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let locale = query.locale.as_deref().map(str::to_lowercase);
+    let missing =
+        translation_service::get_missing_translations(&db, &config, locale.as_deref()).await?;
+    Ok(HttpResponse::Ok().json(missing))
+}
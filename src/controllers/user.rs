@@ -1,6 +1,13 @@
+use crate::config::AppConfig;
+use crate::controllers::guest_cart::merge_guest_cart;
+use crate::entity::sea_orm_active_enums::UserRole;
 use crate::entity::users::{self, ActiveModel};
+use crate::controllers::patch::non_null;
+use crate::middleware::auth::current_user_id;
 use crate::services::auth::{generate_jwt, hash_password, verify_password};
-use actix_web::{web, HttpResponse};
+use crate::services::notification::NotificationChannel;
+use crate::services::user_service::{self, UserProfileUpdate};
+use actix_web::{web, HttpRequest, HttpResponse};
 use once_cell::sync::Lazy;
 use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use serde::Deserialize;
@@ -57,9 +64,12 @@ fn validate_password(password: &str) -> Result<(), ValidationError> {
 }
 
 
+/// ถ้าส่ง `X-Cart-Token` มาด้วย ตะกร้า guest จะถูกรวมเข้าบัญชีใหม่
 pub async fn register(
+    req: HttpRequest,
     data: web::Json<RegisterData>,
     db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
 ) -> Result<HttpResponse, ApiError> {
     match data.validate() {
         Ok(_) => (),
@@ -73,7 +83,8 @@ pub async fn register(
         email: Set(data.email.clone()),
         hashed_password: Set(hashed_password),
         created_at: Set(chrono::Utc::now()),
-        ..Default::default()
+        role: Set(UserRole::Customer),
+        email_verified_at: Set(None),
     };
 
 //     if let Err(err) = new_user.insert(&**db).await {
@@ -83,10 +94,11 @@ pub async fn register(
 
 //     HttpResponse::Ok().body("User registered successfully")
 
-    new_user
+    let user = new_user
         .insert(&**db)
         .await
         .map_err(|_| ApiError::DatabaseError("Failed to create user".to_string()))?;
+    merge_guest_cart(&req, &db, &config, user.id).await;
 
     Ok(HttpResponse::Ok().body("User registered successfully"))
 }
@@ -97,7 +109,13 @@ pub struct LoginData {
     pub password: String,
 }
 
-pub async fn login(data: web::Json<LoginData>, db: web::Data<DatabaseConnection>) -> Result<HttpResponse, ApiError> {
+/// ถ้าส่ง `X-Cart-Token` มาด้วย ตะกร้า guest จะถูกรวมเข้าตะกร้าของผู้ใช้
+pub async fn login(
+    req: HttpRequest,
+    data: web::Json<LoginData>,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+) -> Result<HttpResponse, ApiError> {
     if let Some(users) = users::Entity::find()
         .filter(users::Column::Username.eq(data.username.clone()))
         .one(&**db)
@@ -106,9 +124,79 @@ pub async fn login(data: web::Json<LoginData>, db: web::Data<DatabaseConnection>
     {
         if verify_password(&data.password, &users.hashed_password)? {
             let token = generate_jwt(&users.id.to_string())?;
+            merge_guest_cart(&req, &db, &config, users.id).await;
             return Ok(HttpResponse::Ok().json(token));
         }
     }
 
     Err(ApiError::AuthenticationError("Invalid credentials".to_string()))
 }
+
+pub async fn get_me(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let profile = user_service::get_profile(&db, current_user_id(&req)?).await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+#[derive(Deserialize, Validate)]
+pub struct ProfilePatchRequest {
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    #[validate(length(min = 1, max = 15, message = "must be 1 - 15 characters long"))]
+    pub username: Option<Option<String>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    #[validate(email(message = "invalid"))]
+    pub email: Option<Option<String>>,
+}
+
+/// แก้โปรไฟล์ของตัวเองแบบ JSON Merge Patch ส่งเฉพาะฟิลด์ที่จะเปลี่ยน
+pub async fn patch_me(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    data: web::Json<ProfilePatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let data = data.into_inner();
+    let changes = UserProfileUpdate {
+        username: non_null("username", data.username)?,
+        email: non_null("email", data.email)?,
+    };
+    let profile = user_service::update_profile(&db, current_user_id(&req)?, changes).await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+/// ส่ง token ยืนยันอีเมลไปที่อีเมลของบัญชี
+pub async fn request_email_verification(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    channel: web::Data<dyn NotificationChannel>,
+) -> Result<HttpResponse, ApiError> {
+    user_service::request_email_verification(
+        &db,
+        channel.get_ref(),
+        &config.email_verification_secret,
+        config.email_verification_ttl_secs,
+        current_user_id(&req)?,
+    )
+    .await?;
+    Ok(HttpResponse::Accepted().body("Verification email sent"))
+}
+
+#[derive(Deserialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+pub async fn verify_email(
+    db: web::Data<DatabaseConnection>,
+    config: web::Data<AppConfig>,
+    data: web::Json<VerifyEmailRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let profile =
+        user_service::verify_email(&db, &config.email_verification_secret, &data.token).await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
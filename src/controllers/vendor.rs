@@ -0,0 +1,152 @@
+use crate::controllers::patch::non_null;
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::auth::require_admin;
+use crate::services::vendor_service::{self, VendorUpdate};
+use actix_web::{web, HttpRequest, HttpResponse};
+use rust_decimal::Decimal;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct CreateVendorRequest {
+    pub user_id: Uuid,
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub name: String,
+    pub commission_rate: Decimal,
+}
+
+/// รายชื่อร้านค้าทั้งหมด (เฉพาะผู้ดูแลระบบ)
+pub async fn get_vendors(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let vendors = vendor_service::list_vendors(&db).await?;
+    Ok(HttpResponse::Ok().json(vendors))
+}
+
+/// เปิดร้านให้ผู้ใช้ (เฉพาะผู้ดูแลระบบ)
+pub async fn create_vendor(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    data: web::Json<CreateVendorRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let data = data.into_inner();
+    let vendor =
+        vendor_service::create_vendor(&db, data.user_id, data.name, data.commission_rate).await?;
+    Ok(HttpResponse::Created().json(vendor))
+}
+
+#[derive(Deserialize, Validate)]
+pub struct VendorPatchRequest {
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub name: Option<Option<String>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub commission_rate: Option<Option<Decimal>>,
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub is_active: Option<Option<bool>>,
+}
+
+/// แก้ชื่อ อัตรา commission หรือปิดร้าน (เฉพาะผู้ดูแลระบบ)
+pub async fn patch_vendor(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    vendor_id: web::Path<Uuid>,
+    data: web::Json<VendorPatchRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let data = data.into_inner();
+    let changes = VendorUpdate {
+        name: non_null("name", data.name)?,
+        commission_rate: non_null("commission_rate", data.commission_rate)?,
+        is_active: non_null("is_active", data.is_active)?,
+    };
+    let vendor = vendor_service::update_vendor(&db, vendor_id.into_inner(), changes).await?;
+    Ok(HttpResponse::Ok().json(vendor))
+}
+
+/// สมุดบัญชีของร้าน (เฉพาะผู้ดูแลระบบ)
+pub async fn get_vendor_ledger(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    vendor_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let vendor = vendor_service::find_vendor(&db, vendor_id.into_inner()).await?;
+    let ledger = vendor_service::get_ledger(&db, vendor.id).await?;
+    Ok(HttpResponse::Ok().json(ledger))
+}
+
+#[derive(Deserialize)]
+pub struct PayoutRequest {
+    pub amount: Decimal,
+    pub reference: Option<String>,
+}
+
+/// บันทึกการจ่ายเงินให้ร้าน (เฉพาะผู้ดูแลระบบ)
+pub async fn create_payout(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    vendor_id: web::Path<Uuid>,
+    data: web::Json<PayoutRequest>,
+) -> Result<HttpResponse, ApiError> {
+    require_admin(&db, current_user_id(&req)?).await?;
+
+    let data = data.into_inner();
+    let entry =
+        vendor_service::record_payout(&db, vendor_id.into_inner(), data.amount, data.reference)
+            .await?;
+    Ok(HttpResponse::Created().json(entry))
+}
+
+/// ร้านของผู้ใช้ที่ล็อกอินอยู่
+pub async fn get_my_vendor(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let vendor = vendor_service::require_vendor(&db, current_user_id(&req)?).await?;
+    Ok(HttpResponse::Ok().json(vendor))
+}
+
+/// สินค้าของร้านตัวเอง
+pub async fn get_my_products(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let vendor = vendor_service::require_vendor(&db, current_user_id(&req)?).await?;
+    let products = vendor_service::list_vendor_products(&db, vendor.id).await?;
+    Ok(HttpResponse::Ok().json(products))
+}
+
+/// คำสั่งซื้อย่อยของร้านตัวเอง เห็นเฉพาะแถวสินค้าของร้าน
+pub async fn get_my_orders(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let vendor = vendor_service::require_vendor(&db, current_user_id(&req)?).await?;
+    let orders = vendor_service::list_vendor_orders(&db, vendor.id).await?;
+    Ok(HttpResponse::Ok().json(orders))
+}
+
+/// สมุดบัญชีและยอดคงเหลือของร้านตัวเอง
+pub async fn get_my_ledger(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let vendor = vendor_service::require_vendor(&db, current_user_id(&req)?).await?;
+    let ledger = vendor_service::get_ledger(&db, vendor.id).await?;
+    Ok(HttpResponse::Ok().json(ledger))
+}
@@ -0,0 +1,171 @@
+use crate::error::ApiError;
+use crate::middleware::auth::current_user_id;
+use crate::services::wishlist_service;
+use actix_web::{web, HttpRequest, HttpResponse};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Deserialize, Validate)]
+pub struct WishlistRequest {
+    #[validate(length(min = 1, max = 100, message = "must be 1 - 100 characters long"))]
+    pub name: String,
+}
+
+pub async fn get_wishlists(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+) -> Result<HttpResponse, ApiError> {
+    let wishlists = wishlist_service::list_wishlists(&db, current_user_id(&req)?).await?;
+    Ok(HttpResponse::Ok().json(wishlists))
+}
+
+pub async fn create_wishlist(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    data: web::Json<WishlistRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let wishlist =
+        wishlist_service::create_wishlist(&db, current_user_id(&req)?, data.into_inner().name)
+            .await?;
+    Ok(HttpResponse::Created().json(wishlist))
+}
+
+pub async fn get_wishlist(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    wishlist_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let wishlist =
+        wishlist_service::get_wishlist(&db, current_user_id(&req)?, wishlist_id.into_inner())
+            .await?;
+    Ok(HttpResponse::Ok().json(wishlist))
+}
+
+pub async fn rename_wishlist(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    wishlist_id: web::Path<Uuid>,
+    data: web::Json<WishlistRequest>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let wishlist = wishlist_service::rename_wishlist(
+        &db,
+        current_user_id(&req)?,
+        wishlist_id.into_inner(),
+        data.into_inner().name,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(wishlist))
+}
+
+pub async fn delete_wishlist(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    wishlist_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    wishlist_service::delete_wishlist(&db, current_user_id(&req)?, wishlist_id.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize)]
+pub struct AddWishlistItemRequest {
+    pub product_id: Uuid,
+}
+
+pub async fn add_wishlist_item(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    wishlist_id: web::Path<Uuid>,
+    data: web::Json<AddWishlistItemRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let wishlist = wishlist_service::add_item(
+        &db,
+        current_user_id(&req)?,
+        wishlist_id.into_inner(),
+        data.product_id,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(wishlist))
+}
+
+pub async fn remove_wishlist_item(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, Uuid)>, // Tuple ของ (wishlist_id, product_id)
+) -> Result<HttpResponse, ApiError> {
+    let (wishlist_id, product_id) = path.into_inner();
+    wishlist_service::remove_item(&db, current_user_id(&req)?, wishlist_id, product_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(Deserialize, Validate)]
+pub struct MoveToCartRequest {
+    #[validate(range(min = 1, message = "must be at least 1"))]
+    pub quantity: Option<i32>,
+}
+
+pub async fn move_to_cart(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    path: web::Path<(Uuid, Uuid)>,
+    data: Option<web::Json<MoveToCartRequest>>,
+) -> Result<HttpResponse, ApiError> {
+    let quantity = match data {
+        Some(data) => {
+            data.validate()
+                .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+            data.quantity.unwrap_or(1)
+        }
+        None => 1,
+    };
+
+    let (wishlist_id, product_id) = path.into_inner();
+    let cart_item = wishlist_service::move_to_cart(
+        &db,
+        current_user_id(&req)?,
+        wishlist_id,
+        product_id,
+        quantity,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(cart_item))
+}
+
+pub async fn share_wishlist(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    wishlist_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let wishlist =
+        wishlist_service::share_wishlist(&db, current_user_id(&req)?, wishlist_id.into_inner())
+            .await?;
+    Ok(HttpResponse::Ok().json(wishlist))
+}
+
+pub async fn unshare_wishlist(
+    req: HttpRequest,
+    db: web::Data<DatabaseConnection>,
+    wishlist_id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let wishlist =
+        wishlist_service::unshare_wishlist(&db, current_user_id(&req)?, wishlist_id.into_inner())
+            .await?;
+    Ok(HttpResponse::Ok().json(wishlist))
+}
+
+/// ดูรายการโปรดที่ถูกแชร์ (เปิดสาธารณะ ไม่ต้องมี token เข้าสู่ระบบ)
+pub async fn get_shared_wishlist(
+    db: web::Data<DatabaseConnection>,
+    share_token: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let wishlist = wishlist_service::get_shared_wishlist(&db, &share_token).await?;
+    Ok(HttpResponse::Ok().json(wishlist))
+}
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.2
 
+use super::sea_orm_active_enums::ProductStatus;
 use sea_orm::entity::prelude::*;
 use serde::{Serialize, Deserialize};
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
@@ -7,13 +8,24 @@ use serde::{Serialize, Deserialize};
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
+    pub cart_id: Uuid,
     pub user_id: Uuid,
     pub product_id: Uuid,
     pub quantity: i32,
+    pub price_snapshot: Decimal,
+    pub status_snapshot: ProductStatus,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::carts::Entity",
+        from = "Column::CartId",
+        to = "super::carts::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Carts,
     #[sea_orm(
         belongs_to = "super::products::Entity",
         from = "Column::ProductId",
@@ -32,6 +44,12 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::carts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Carts.def()
+    }
+}
+
 impl Related<super::products::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Products.def()
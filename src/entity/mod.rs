@@ -2,8 +2,40 @@
 
 pub mod prelude;
 
+pub mod attribute_definitions;
+pub mod bundle_components;
 pub mod cart;
+pub mod cart_reminders;
+pub mod carts;
+pub mod categories;
+pub mod category_translations;
+pub mod coupon_categories;
+pub mod coupon_products;
+pub mod coupon_redemptions;
+pub mod coupons;
+pub mod digital_assets;
+pub mod download_grants;
+pub mod guest_cart_items;
+pub mod guest_carts;
+pub mod locations;
 pub mod order_items;
 pub mod orders;
+pub mod price_schedules;
+pub mod product_alerts;
+pub mod product_attribute_values;
+pub mod product_images;
+pub mod product_price_history;
+pub mod product_relations;
+pub mod product_translations;
 pub mod products;
+pub mod reviews;
+pub mod saved_cart_items;
+pub mod sea_orm_active_enums;
+pub mod stock_levels;
+pub mod stock_movements;
 pub mod users;
+pub mod vendor_ledger_entries;
+pub mod vendor_orders;
+pub mod vendors;
+pub mod wishlist_items;
+pub mod wishlists;
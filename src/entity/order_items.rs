@@ -13,10 +13,29 @@ pub struct Model {
     pub product_id: Uuid,
     pub quantity: i32,
     pub price: Decimal,
+    pub location_id: Option<Uuid>,
+    pub parent_item_id: Option<Uuid>,
+    pub vendor_order_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentItemId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    SelfRef,
+    #[sea_orm(
+        belongs_to = "super::locations::Entity",
+        from = "Column::LocationId",
+        to = "super::locations::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Locations,
     #[sea_orm(
         belongs_to = "super::orders::Entity",
         from = "Column::OrderId",
@@ -30,9 +49,23 @@ pub enum Relation {
         from = "Column::ProductId",
         to = "super::products::Column::Id",
         on_update = "NoAction",
-        on_delete = "Cascade"
+        on_delete = "Restrict"
     )]
     Products,
+    #[sea_orm(
+        belongs_to = "super::vendor_orders::Entity",
+        from = "Column::VendorOrderId",
+        to = "super::vendor_orders::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    VendorOrders,
+}
+
+impl Related<super::locations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Locations.def()
+    }
 }
 
 impl Related<super::orders::Entity> for Entity {
@@ -47,4 +80,10 @@ impl Related<super::products::Entity> for Entity {
     }
 }
 
+impl Related<super::vendor_orders::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VendorOrders.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
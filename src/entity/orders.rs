@@ -1,6 +1,7 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.2
 
 use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue, FromJsonQueryResult};
 use serde::{Deserialize, Serialize};
 
 
@@ -9,14 +10,41 @@ use serde::{Deserialize, Serialize};
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
-    pub user_id: Uuid,
+    pub user_id: Option<Uuid>,
     pub total_price: Decimal,
     pub status: String,
     pub created_at: DateTimeUtc,
+    pub version: i32,
+    pub requires_shipping: bool,
+    pub guest_email: Option<String>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub shipping_address: Option<ShippingAddress>,
+    pub coupon_id: Option<Uuid>,
+    pub discount_total: Decimal,
+}
+
+/// ที่อยู่จัดส่ง เก็บเป็น jsonb
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct ShippingAddress {
+    pub name: String,
+    pub line1: String,
+    pub line2: Option<String>,
+    pub city: String,
+    pub postal_code: String,
+    pub country: String,
+    pub phone: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::coupons::Entity",
+        from = "Column::CouponId",
+        to = "super::coupons::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Coupons,
     #[sea_orm(has_many = "super::order_items::Entity")]
     OrderItems,
     #[sea_orm(
@@ -27,6 +55,20 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Users,
+    #[sea_orm(has_many = "super::download_grants::Entity")]
+    DownloadGrants,
+    #[sea_orm(has_many = "super::vendor_orders::Entity")]
+    VendorOrders,
+    #[sea_orm(has_many = "super::cart_reminders::Entity")]
+    CartReminders,
+    #[sea_orm(has_many = "super::coupon_redemptions::Entity")]
+    CouponRedemptions,
+}
+
+impl Related<super::coupons::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Coupons.def()
+    }
 }
 
 impl Related<super::order_items::Entity> for Entity {
@@ -41,4 +83,42 @@ impl Related<super::users::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::download_grants::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DownloadGrants.def()
+    }
+}
+
+impl Related<super::vendor_orders::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VendorOrders.def()
+    }
+}
+
+impl Related<super::cart_reminders::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CartReminders.def()
+    }
+}
+
+impl Related<super::coupon_redemptions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CouponRedemptions.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// เพิ่ม version ทุกครั้งที่แก้แถว เพื่อให้ ETag เดิมใช้ไม่ได้อีก
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert {
+            if let ActiveValue::Unchanged(version) = self.version {
+                self.version = ActiveValue::Set(version + 1);
+            }
+        }
+        Ok(self)
+    }
+}
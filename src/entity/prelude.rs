@@ -1,7 +1,38 @@
 // //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.2
 
+// pub use super::attribute_definitions::Entity as AttributeDefinitions;
+// pub use super::bundle_components::Entity as BundleComponents;
 // pub use super::cart::Entity as Cart;
+// pub use super::cart_reminders::Entity as CartReminders;
+// pub use super::carts::Entity as Carts;
+// pub use super::categories::Entity as Categories;
+// pub use super::category_translations::Entity as CategoryTranslations;
+// pub use super::coupon_categories::Entity as CouponCategories;
+// pub use super::coupon_products::Entity as CouponProducts;
+// pub use super::coupon_redemptions::Entity as CouponRedemptions;
+// pub use super::coupons::Entity as Coupons;
+// pub use super::digital_assets::Entity as DigitalAssets;
+// pub use super::download_grants::Entity as DownloadGrants;
+// pub use super::guest_cart_items::Entity as GuestCartItems;
+// pub use super::guest_carts::Entity as GuestCarts;
+// pub use super::locations::Entity as Locations;
 // pub use super::order_items::Entity as OrderItems;
 // pub use super::orders::Entity as Orders;
+// pub use super::price_schedules::Entity as PriceSchedules;
+// pub use super::product_alerts::Entity as ProductAlerts;
+// pub use super::product_attribute_values::Entity as ProductAttributeValues;
+// pub use super::product_images::Entity as ProductImages;
+// pub use super::product_price_history::Entity as ProductPriceHistory;
+// pub use super::product_relations::Entity as ProductRelations;
+// pub use super::product_translations::Entity as ProductTranslations;
 // pub use super::products::Entity as Products;
+// pub use super::reviews::Entity as Reviews;
+// pub use super::saved_cart_items::Entity as SavedCartItems;
+// pub use super::stock_levels::Entity as StockLevels;
+// pub use super::stock_movements::Entity as StockMovements;
 // pub use super::users::Entity as Users;
+// pub use super::vendor_ledger_entries::Entity as VendorLedgerEntries;
+// pub use super::vendor_orders::Entity as VendorOrders;
+// pub use super::vendors::Entity as Vendors;
+// pub use super::wishlist_items::Entity as WishlistItems;
+// pub use super::wishlists::Entity as Wishlists;
@@ -1,6 +1,8 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.2
 
+use super::sea_orm_active_enums::{ProductStatus, ProductType};
 use sea_orm::entity::prelude::*;
+use sea_orm::ActiveValue;
 use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "products")]
@@ -10,16 +12,73 @@ pub struct Model {
     pub name: String,
     pub description: Option<String>,
     pub price: Decimal,
-    pub status: String,
+    pub status: ProductStatus,
     pub created_at: DateTimeUtc,
+    pub is_one_of_a_kind: bool,
+    pub deleted_at: Option<DateTimeUtc>,
+    #[sea_orm(unique)]
+    pub sku: Option<String>,
+    pub rating_average: Option<Decimal>,
+    pub review_count: i32,
+    pub category_id: Option<Uuid>,
+    pub version: i32,
+    pub product_type: ProductType,
+    pub vendor_id: Option<Uuid>,
+    pub min_quantity: i32,
+    pub max_quantity: Option<i32>,
+    pub quantity_step: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::categories::Entity",
+        from = "Column::CategoryId",
+        to = "super::categories::Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    Categories,
+    #[sea_orm(
+        belongs_to = "super::vendors::Entity",
+        from = "Column::VendorId",
+        to = "super::vendors::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    Vendors,
     #[sea_orm(has_many = "super::cart::Entity")]
     Cart,
     #[sea_orm(has_many = "super::order_items::Entity")]
     OrderItems,
+    #[sea_orm(has_many = "super::stock_levels::Entity")]
+    StockLevels,
+    #[sea_orm(has_many = "super::stock_movements::Entity")]
+    StockMovements,
+    #[sea_orm(has_many = "super::product_images::Entity")]
+    ProductImages,
+    #[sea_orm(has_many = "super::price_schedules::Entity")]
+    PriceSchedules,
+    #[sea_orm(has_many = "super::product_price_history::Entity")]
+    ProductPriceHistory,
+    #[sea_orm(has_many = "super::reviews::Entity")]
+    Reviews,
+    #[sea_orm(has_many = "super::product_attribute_values::Entity")]
+    ProductAttributeValues,
+    #[sea_orm(has_many = "super::product_translations::Entity")]
+    ProductTranslations,
+    #[sea_orm(has_many = "super::wishlist_items::Entity")]
+    WishlistItems,
+    #[sea_orm(has_many = "super::product_alerts::Entity")]
+    ProductAlerts,
+    #[sea_orm(has_many = "super::digital_assets::Entity")]
+    DigitalAssets,
+    #[sea_orm(has_many = "super::guest_cart_items::Entity")]
+    GuestCartItems,
+    #[sea_orm(has_many = "super::saved_cart_items::Entity")]
+    SavedCartItems,
+    #[sea_orm(has_many = "super::coupon_products::Entity")]
+    CouponProducts,
 }
 
 impl Related<super::cart::Entity> for Entity {
@@ -34,4 +93,114 @@ impl Related<super::order_items::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::stock_levels::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockLevels.def()
+    }
+}
+
+impl Related<super::stock_movements::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockMovements.def()
+    }
+}
+
+impl Related<super::product_images::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductImages.def()
+    }
+}
+
+impl Related<super::price_schedules::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PriceSchedules.def()
+    }
+}
+
+impl Related<super::product_price_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductPriceHistory.def()
+    }
+}
+
+impl Related<super::reviews::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reviews.def()
+    }
+}
+
+impl Related<super::product_attribute_values::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductAttributeValues.def()
+    }
+}
+
+impl Related<super::categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Categories.def()
+    }
+}
+
+impl Related<super::product_translations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductTranslations.def()
+    }
+}
+
+impl Related<super::wishlist_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WishlistItems.def()
+    }
+}
+
+impl Related<super::product_alerts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductAlerts.def()
+    }
+}
+
+impl Related<super::digital_assets::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DigitalAssets.def()
+    }
+}
+
+impl Related<super::vendors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vendors.def()
+    }
+}
+
+impl Related<super::guest_cart_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GuestCartItems.def()
+    }
+}
+
+impl Related<super::saved_cart_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SavedCartItems.def()
+    }
+}
+
+impl Related<super::coupon_products::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CouponProducts.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// เพิ่ม version ทุกครั้งที่แก้แถว เพื่อให้ ETag เดิมใช้ไม่ได้อีก
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert {
+            if let ActiveValue::Unchanged(version) = self.version {
+                self.version = ActiveValue::Set(version + 1);
+            }
+        }
+        Ok(self)
+    }
+}
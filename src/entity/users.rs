@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.2
 
+use super::sea_orm_active_enums::UserRole;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -13,6 +14,8 @@ pub struct Model {
     pub email: String,
     pub hashed_password: String,
     pub created_at: DateTimeUtc,
+    pub role: UserRole,
+    pub email_verified_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -21,6 +24,24 @@ pub enum Relation {
     Cart,
     #[sea_orm(has_many = "super::orders::Entity")]
     Orders,
+    #[sea_orm(has_many = "super::reviews::Entity")]
+    Reviews,
+    #[sea_orm(has_many = "super::wishlists::Entity")]
+    Wishlists,
+    #[sea_orm(has_many = "super::product_alerts::Entity")]
+    ProductAlerts,
+    #[sea_orm(has_many = "super::download_grants::Entity")]
+    DownloadGrants,
+    #[sea_orm(has_one = "super::vendors::Entity")]
+    Vendors,
+    #[sea_orm(has_many = "super::carts::Entity")]
+    Carts,
+    #[sea_orm(has_many = "super::saved_cart_items::Entity")]
+    SavedCartItems,
+    #[sea_orm(has_many = "super::cart_reminders::Entity")]
+    CartReminders,
+    #[sea_orm(has_many = "super::coupon_redemptions::Entity")]
+    CouponRedemptions,
 }
 
 impl Related<super::cart::Entity> for Entity {
@@ -35,4 +56,58 @@ impl Related<super::orders::Entity> for Entity {
     }
 }
 
+impl Related<super::reviews::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reviews.def()
+    }
+}
+
+impl Related<super::wishlists::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Wishlists.def()
+    }
+}
+
+impl Related<super::product_alerts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductAlerts.def()
+    }
+}
+
+impl Related<super::download_grants::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DownloadGrants.def()
+    }
+}
+
+impl Related<super::vendors::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vendors.def()
+    }
+}
+
+impl Related<super::carts::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Carts.def()
+    }
+}
+
+impl Related<super::saved_cart_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SavedCartItems.def()
+    }
+}
+
+impl Related<super::cart_reminders::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CartReminders.def()
+    }
+}
+
+impl Related<super::coupon_redemptions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CouponRedemptions.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -19,6 +19,18 @@ pub enum ApiError {
     #[display("Authentication error: {}", _0)]
     AuthenticationError(String),
 
+    #[display("Forbidden: {}", _0)]
+    Forbidden(String),
+
+    #[display("Conflict: {}", _0)]
+    Conflict(String),
+
+    #[display("Precondition failed: {}", _0)]
+    PreconditionFailed(String),
+
+    #[display("Precondition required: {}", _0)]
+    PreconditionRequired(String),
+
     #[display("Internal server error")]
     InternalServerError,
 }
@@ -53,6 +65,22 @@ impl error::ResponseError for ApiError {
                 error: "AuthenticationError".to_string(),
                 message: message.clone(),
             },
+            ApiError::Forbidden(message) => ErrorResponse {
+                error: "Forbidden".to_string(),
+                message: message.clone(),
+            },
+            ApiError::Conflict(message) => ErrorResponse {
+                error: "Conflict".to_string(),
+                message: message.clone(),
+            },
+            ApiError::PreconditionFailed(message) => ErrorResponse {
+                error: "PreconditionFailed".to_string(),
+                message: message.clone(),
+            },
+            ApiError::PreconditionRequired(message) => ErrorResponse {
+                error: "PreconditionRequired".to_string(),
+                message: message.clone(),
+            },
             ApiError::InternalServerError => ErrorResponse {
                 error: "InternalServerError".to_string(),
                 message: "An unexpected error occurred".to_string(),
@@ -68,6 +96,10 @@ impl error::ResponseError for ApiError {
             ApiError::ValidationError(_) => StatusCode::BAD_REQUEST,
             ApiError::NotFound(_) => StatusCode::NOT_FOUND,
             ApiError::AuthenticationError(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            ApiError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
         }
     }
 }
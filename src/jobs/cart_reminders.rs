@@ -0,0 +1,31 @@
+use crate::config::AppConfig;
+use crate::services::cart_reminder_service;
+use crate::services::notification::NotificationChannel;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// เริ่ม task เบื้องหลังที่ส่งอีเมลเตือนตะกร้าที่ถูกทิ้งไว้ และลบตะกร้าที่เก่าเกินระยะเก็บรักษา
+pub fn spawn(
+    db: DatabaseConnection,
+    channel: Arc<dyn NotificationChannel>,
+    config: AppConfig,
+    interval: Duration,
+) {
+    actix_web::rt::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match cart_reminder_service::send_due_reminders(&db, channel.as_ref(), &config).await {
+                Ok(0) => {}
+                Ok(count) => println!("ส่งอีเมลเตือนตะกร้าที่ถูกทิ้งไว้ {} ฉบับ", count),
+                Err(e) => eprintln!("Cart reminders failed: {}", e),
+            }
+            match cart_reminder_service::purge_stale_carts(&db, config.cart_retention_secs).await {
+                Ok(0) => {}
+                Ok(count) => println!("ลบตะกร้าที่เก่าเกินระยะเก็บรักษา {} ตะกร้า", count),
+                Err(e) => eprintln!("Cart retention cleanup failed: {}", e),
+            }
+        }
+    });
+}
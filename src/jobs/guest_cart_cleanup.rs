@@ -0,0 +1,18 @@
+use crate::services::guest_cart_service;
+use sea_orm::DatabaseConnection;
+use std::time::Duration;
+
+/// เริ่ม task เบื้องหลังที่คอยลบตะกร้า guest ที่หมดอายุ
+pub fn spawn(db: DatabaseConnection, interval: Duration, ttl_secs: i64) {
+    actix_web::rt::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match guest_cart_service::purge_expired(&db, ttl_secs).await {
+                Ok(0) => {}
+                Ok(count) => println!("ลบตะกร้า guest ที่หมดอายุแล้ว {} ตะกร้า", count),
+                Err(e) => eprintln!("Guest cart cleanup failed: {}", e),
+            }
+        }
+    });
+}
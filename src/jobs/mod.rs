@@ -0,0 +1,4 @@
+pub mod price_scheduler;
+pub mod recommendations;
+pub mod guest_cart_cleanup;
+pub mod cart_reminders;
@@ -0,0 +1,20 @@
+use crate::services::notification::NotificationChannel;
+use crate::services::price_service;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// เริ่ม task เบื้องหลังที่คอยเปิดและปิดราคาลดตามเวลาที่ตั้งไว้
+pub fn spawn(db: DatabaseConnection, channel: Arc<dyn NotificationChannel>, interval: Duration) {
+    actix_web::rt::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match price_service::apply_due_schedules(&db, channel.as_ref()).await {
+                Ok(0) => {}
+                Ok(count) => println!("ปรับราคาตามกำหนดเวลาแล้ว {} รายการ", count),
+                Err(e) => eprintln!("Price scheduler failed: {}", e),
+            }
+        }
+    });
+}
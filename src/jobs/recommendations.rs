@@ -0,0 +1,17 @@
+use crate::services::recommendation_service;
+use sea_orm::DatabaseConnection;
+use std::time::Duration;
+
+/// เริ่ม task เบื้องหลังที่คำนวณสินค้าที่ซื้อร่วมกันใหม่เป็นรอบ ๆ
+pub fn spawn(db: DatabaseConnection, interval: Duration, limit: usize) {
+    actix_web::rt::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match recommendation_service::recompute_relations(&db, limit).await {
+                Ok(count) => println!("คำนวณสินค้าแนะนำใหม่แล้ว {} รายการ", count),
+                Err(e) => eprintln!("Recommendation job failed: {}", e),
+            }
+        }
+    });
+}
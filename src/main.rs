@@ -4,13 +4,20 @@ use actix_web::{
     HttpServer,
 };
 use dotenv::dotenv;
+use error::ApiError;
 use middleware::auth::AuthMiddleware;
 use sea_orm::Database;
+use services::media_store::{LocalMediaStore, MediaStore, PrivateMediaStore};
+use services::notification::{LogNotificationChannel, NotificationChannel};
 use std::env;
+use std::sync::Arc;
+use std::time::Duration;
 
+mod cli;
 mod controllers;
 mod entity;
 mod error;
+mod jobs;
 mod middleware;
 mod routes;
 mod services;
@@ -44,14 +51,74 @@ async fn main() -> std::io::Result<()> {
         }
     };
 
+    // มี argument เมื่อไหร่ถือเป็นคำสั่ง CLI เช่น import-products
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(&db, &app_config, &args).await);
+    }
+
+    let notification_channel: Arc<dyn NotificationChannel> = Arc::new(LogNotificationChannel);
+    jobs::price_scheduler::spawn(
+        db.clone(),
+        notification_channel.clone(),
+        Duration::from_secs(app_config.price_scheduler_interval_secs),
+    );
+    jobs::recommendations::spawn(
+        db.clone(),
+        Duration::from_secs(app_config.recommendation_interval_secs),
+        app_config.related_products_limit,
+    );
+    jobs::guest_cart_cleanup::spawn(
+        db.clone(),
+        Duration::from_secs(app_config.guest_cart_cleanup_interval_secs),
+        app_config.guest_cart_ttl_secs,
+    );
+
+    let local_store = LocalMediaStore::new(&app_config.media_root, &app_config.media_base_url);
+    let media_root = local_store.root().clone();
+    let media_store: Arc<dyn MediaStore> = Arc::new(local_store);
+    // ไฟล์สินค้าดิจิทัลไม่ได้ mount เป็น static files โหลดได้ผ่านลิงก์ที่เซ็นไว้เท่านั้น
+    let private_store = web::Data::new(PrivateMediaStore::new(Arc::new(LocalMediaStore::new(
+        &app_config.private_media_root,
+        "",
+    ))));
+    jobs::cart_reminders::spawn(
+        db.clone(),
+        notification_channel.clone(),
+        app_config.clone(),
+        Duration::from_secs(app_config.cart_reminder_interval_secs),
+    );
+
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(db.clone()))
             .app_data(web::Data::new(app_config.clone()))
+            .app_data(web::Data::from(media_store.clone()))
+            .app_data(private_store.clone())
+            .app_data(web::Data::from(notification_channel.clone()))
+            // ให้ JSON ที่ parse ไม่ผ่าน (เช่น status ที่ไม่รู้จัก) ตอบกลับในรูปแบบ ApiError
+            .app_data(web::JsonConfig::default().error_handler(|err, _| {
+                ApiError::ValidationError(err.to_string()).into()
+            }))
             .configure(routes::configure_auth_routes)
             .configure(routes::configure_product_routes)
             .configure(routes::configure_cart_routes)
+            .configure(routes::configure_guest_cart_routes)
+            .configure(routes::configure_named_cart_routes)
+            .configure(routes::configure_cart_reminder_routes)
             .configure(routes::configure_order_routes)
+            .configure(routes::configure_inventory_routes)
+            .configure(routes::configure_review_routes)
+            .configure(routes::configure_attribute_routes)
+            .configure(routes::configure_category_routes)
+            .configure(routes::configure_translation_routes)
+            .configure(routes::configure_wishlist_routes)
+            .configure(routes::configure_alert_routes)
+            .configure(routes::configure_user_routes)
+            .configure(routes::configure_download_routes)
+            .configure(routes::configure_vendor_routes)
+            .configure(routes::configure_coupon_routes)
+            .service(actix_files::Files::new(&app_config.media_base_url, &media_root))
             .wrap(AuthMiddleware)
     })
     .bind("127.0.0.1:8080")?
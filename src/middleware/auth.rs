@@ -1,12 +1,19 @@
-use crate::{config::AppConfig, services::auth::Claims};
+use crate::{
+    config::AppConfig, error::ApiError, routes::downloads::DOWNLOAD_PATH,
+    routes::cart_reminders::CART_REMINDER_PATH, routes::guest_cart::GUEST_CART_PATH,
+    routes::order::GUEST_ORDER_PATH,
+    routes::wishlists::SHARED_WISHLIST_PATH,
+    services::auth::Claims,
+};
 use actix_web::{
-    body::BoxBody, dev::{Service, ServiceRequest, ServiceResponse, Transform}, web, Error, HttpMessage, HttpResponse
+    body::BoxBody, dev::{Service, ServiceRequest, ServiceResponse, Transform}, web, Error, HttpMessage, HttpRequest, HttpResponse
 };
 use futures_util::future::{ok, LocalBoxFuture, Ready};
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::Deserialize;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 
@@ -55,7 +62,26 @@ where
         let config = req.app_data::<web::Data<AppConfig>>().cloned();
 
         Box::pin(async move {
-            if req.path().starts_with("/auth") {
+            // ไฟล์รูปภาพสินค้าและรายการโปรดที่ถูกแชร์เปิดให้ทุกคนเข้าถึงได้
+            let is_public_media = config.as_ref().is_some_and(|cfg| {
+                req.path()
+                    .strip_prefix(cfg.media_base_url.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            });
+            let is_shared_wishlist = req.path().starts_with(SHARED_WISHLIST_PATH);
+            let is_signed_download = req.path().starts_with(DOWNLOAD_PATH);
+            let is_guest_cart = req.path().starts_with(GUEST_CART_PATH);
+            let is_guest_order = req.path().starts_with(GUEST_ORDER_PATH);
+            let is_cart_reminder = req.path().starts_with(CART_REMINDER_PATH);
+
+            if req.path().starts_with("/auth")
+                || is_public_media
+                || is_shared_wishlist
+                || is_signed_download
+                || is_guest_cart
+                || is_guest_order
+                || is_cart_reminder
+            {
                 return service.call(req).await.map(|res| res.map_into_boxed_body());
             }
 
@@ -73,9 +99,7 @@ where
             // ตรวจสอบ Header Authorization
             if let Some(auth_header) = req.headers().get("Authorization") {
                 if let Ok(auth_str) = auth_header.to_str() {
-                    if auth_str.starts_with("Bearer ") {
-                        let token = &auth_str[7..];
-
+                    if let Some(token) = auth_str.strip_prefix("Bearer ") {
                         // ตรวจสอบ JWT Token
                         match decode::<Claims>(
                             token,
@@ -112,3 +136,11 @@ where
         })
     }
 }
+
+/// ดึง user id จาก JWT ที่ middleware ใส่ไว้ใน extensions
+pub fn current_user_id(req: &HttpRequest) -> Result<Uuid, ApiError> {
+    req.extensions()
+        .get::<String>()
+        .and_then(|sub| Uuid::parse_str(sub).ok())
+        .ok_or_else(|| ApiError::AuthenticationError("Missing or invalid token".to_string()))
+}
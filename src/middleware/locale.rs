@@ -0,0 +1,50 @@
+use crate::config::AppConfig;
+use actix_web::{http::header::ACCEPT_LANGUAGE, web, HttpRequest};
+use std::collections::HashMap;
+
+/// เลือกภาษาของคำขอ: `?lang=` ก่อน แล้วจึง `Accept-Language` ตามค่า q
+/// ถ้าไม่ตรงกับภาษาที่รองรับเลยให้ใช้ภาษาเริ่มต้น
+pub fn request_locale(req: &HttpRequest, config: &AppConfig) -> String {
+    let from_query = web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .ok()
+        .and_then(|query| query.get("lang").cloned());
+    if let Some(locale) = from_query.and_then(|lang| match_supported(&lang, config)) {
+        return locale;
+    }
+
+    let accept_language = req
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let mut candidates: Vec<(&str, f32)> = accept_language
+        .split(',')
+        .filter_map(|part| {
+            let mut pieces = part.trim().split(';');
+            let tag = pieces.next()?.trim();
+            let quality = pieces
+                .find_map(|piece| piece.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse().ok())?;
+            (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    candidates
+        .into_iter()
+        .find_map(|(tag, _)| match_supported(tag, config))
+        .unwrap_or_else(|| config.default_locale.clone())
+}
+
+/// เทียบแท็กภาษากับที่รองรับ เช่น `en-US` ใช้ `en` ได้
+fn match_supported(tag: &str, config: &AppConfig) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    let primary = tag.split(['-', '_']).next().unwrap_or_default();
+    config
+        .supported_locales
+        .iter()
+        .find(|locale| **locale == tag)
+        .or_else(|| config.supported_locales.iter().find(|locale| *locale == primary))
+        .cloned()
+}
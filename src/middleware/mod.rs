@@ -1,2 +1,4 @@
 pub mod auth;
+pub mod locale;
+pub mod precondition;
 // // pub use auth::auth_middleware;
@@ -0,0 +1,34 @@
+use crate::error::ApiError;
+use actix_web::http::header::{EntityTag, ETag, IfMatch};
+use actix_web::{HttpMessage, HttpRequest};
+
+/// ETag ของแถวที่มีคอลัมน์ version ใช้ค่า version ตรง ๆ
+pub fn etag(version: i32) -> ETag {
+    ETag(EntityTag::new_strong(version.to_string()))
+}
+
+/// อ่าน version ที่ client คาดหวังจาก `If-Match`
+///
+/// ไม่ส่งมาเลยได้ 428, `*` ได้ None (ยอมรับทุก version)
+/// ETag ที่ไม่ใช่ของเรา (weak หรือไม่ใช่ตัวเลข) ถือว่าไม่ตรงและได้ 412
+pub fn if_match_version(req: &HttpRequest) -> Result<Option<i32>, ApiError> {
+    let if_match = req.get_header::<IfMatch>().ok_or_else(|| {
+        ApiError::PreconditionRequired("If-Match header is required".to_string())
+    })?;
+
+    let tags = match if_match {
+        IfMatch::Any => return Ok(None),
+        IfMatch::Items(tags) => tags,
+    };
+    match tags.as_slice() {
+        [tag] if !tag.weak => tag.tag().parse().map(Some).map_err(|_| {
+            ApiError::PreconditionFailed(format!("Unknown ETag \"{}\"", tag.tag()))
+        }),
+        [_] => Err(ApiError::PreconditionFailed(
+            "Weak ETags cannot be used with If-Match".to_string(),
+        )),
+        _ => Err(ApiError::ValidationError(
+            "If-Match must contain exactly one ETag".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,7 @@
+use actix_web::web;
+
+use crate::controllers::alert::get_alerts;
+
+pub fn configure_alert_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/alerts").route("", web::get().to(get_alerts)));
+}
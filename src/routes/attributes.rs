@@ -0,0 +1,12 @@
+use actix_web::web;
+
+use crate::controllers::attribute::{create_attribute, delete_attribute, get_attributes};
+
+pub fn configure_attribute_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/attributes")
+            .route("", web::get().to(get_attributes))
+            .route("", web::post().to(create_attribute))
+            .route("/{id}", web::delete().to(delete_attribute)),
+    );
+}
@@ -1,11 +1,12 @@
 use actix_web::web;
 
-use crate::controllers::user::{login, register};
+use crate::controllers::user::{login, register, verify_email};
 
 pub fn configure_auth_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/auth")
             .route("/register", web::post().to(register))
-            .route("/login", web::post().to(login)),
+            .route("/login", web::post().to(login))
+            .route("/verify-email", web::post().to(verify_email)),
     );
 }
@@ -1,14 +1,18 @@
 use actix_web::web;
 
-use crate::controllers::cart::{add_to_cart, remove_from_cart,clear_cart,get_cart,calculate_cart_total};
+use crate::controllers::cart::{add_to_cart, remove_from_cart,clear_cart,get_cart,calculate_cart_total,set_cart_quantity,acknowledge_cart_changes,apply_cart_coupon,remove_cart_coupon};
 
 
 pub fn configure_cart_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/cart/{user_id}")
             .route("/add", web::post().to(add_to_cart))
+            .route("/items/{product_id}", web::put().to(set_cart_quantity))
             .route("/remove/{product_id}", web::delete().to(remove_from_cart))
             .route("/clear", web::delete().to(clear_cart))
+            .route("/acknowledge", web::post().to(acknowledge_cart_changes))
+            .route("/coupon", web::post().to(apply_cart_coupon))
+            .route("/coupon", web::delete().to(remove_cart_coupon))
             .route("", web::get().to(get_cart))
             .route("/total", web::get().to(calculate_cart_total)),
     );
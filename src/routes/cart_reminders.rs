@@ -0,0 +1,13 @@
+use actix_web::web;
+
+use crate::controllers::cart_reminder::{get_cart_recovery_report, restore_cart};
+
+/// path ของลิงก์ในอีเมลเตือนตะกร้า ตรวจสิทธิ์ด้วย token ในลิงก์แทน token เข้าสู่ระบบ
+pub const CART_REMINDER_PATH: &str = "/cart-reminders/";
+
+pub fn configure_cart_reminder_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/cart-reminders").route("/{token}", web::get().to(restore_cart)))
+        .service(
+            web::scope("/reports").route("/cart-recovery", web::get().to(get_cart_recovery_report)),
+        );
+}
@@ -0,0 +1,26 @@
+use actix_web::web;
+
+use crate::controllers::named_cart::{
+    acknowledge_cart_changes, activate_cart, checkout_cart, create_cart, delete_cart, get_cart,
+    get_carts, get_saved_items, move_cart_item, move_saved_to_cart, remove_saved_item,
+    rename_cart, save_for_later,
+};
+
+pub fn configure_named_cart_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/carts")
+            .route("/saved", web::get().to(get_saved_items))
+            .route("/saved/{product_id}", web::delete().to(remove_saved_item))
+            .route("/saved/{product_id}/move-to-cart", web::post().to(move_saved_to_cart))
+            .route("", web::get().to(get_carts))
+            .route("", web::post().to(create_cart))
+            .route("/{id}", web::get().to(get_cart))
+            .route("/{id}", web::put().to(rename_cart))
+            .route("/{id}", web::delete().to(delete_cart))
+            .route("/{id}/activate", web::post().to(activate_cart))
+            .route("/{id}/acknowledge", web::post().to(acknowledge_cart_changes))
+            .route("/{id}/checkout", web::post().to(checkout_cart))
+            .route("/{id}/items/{product_id}/move", web::post().to(move_cart_item))
+            .route("/{id}/items/{product_id}/save-for-later", web::post().to(save_for_later)),
+    );
+}
@@ -0,0 +1,17 @@
+use actix_web::web;
+
+use crate::controllers::category::{create_category, get_categories};
+use crate::controllers::translation::{
+    delete_category_translation, get_category_translations, put_category_translation,
+};
+
+pub fn configure_category_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/categories")
+            .route("", web::get().to(get_categories))
+            .route("", web::post().to(create_category))
+            .route("/{id}/translations", web::get().to(get_category_translations))
+            .route("/{id}/translations/{locale}", web::put().to(put_category_translation))
+            .route("/{id}/translations/{locale}", web::delete().to(delete_category_translation)),
+    );
+}
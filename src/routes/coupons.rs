@@ -0,0 +1,12 @@
+use actix_web::web;
+
+use crate::controllers::coupon::{create_coupon, get_coupons, patch_coupon};
+
+pub fn configure_coupon_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/coupons")
+            .route("", web::get().to(get_coupons))
+            .route("", web::post().to(create_coupon))
+            .route("/{id}", web::patch().to(patch_coupon)),
+    );
+}
@@ -0,0 +1,10 @@
+use actix_web::web;
+
+use crate::controllers::download::download_file;
+
+/// path ของลิงก์ดาวน์โหลดที่เซ็นไว้ ตรวจสิทธิ์ด้วยลายเซ็นแทน token เข้าสู่ระบบ
+pub const DOWNLOAD_PATH: &str = "/downloads/";
+
+pub fn configure_download_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/downloads").route("/{grant_id}", web::get().to(download_file)));
+}
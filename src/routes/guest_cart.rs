@@ -0,0 +1,24 @@
+use actix_web::web;
+
+use crate::controllers::guest_cart::{
+    acknowledge_guest_cart_changes, add_to_guest_cart, calculate_guest_cart_total, checkout_guest_cart, clear_guest_cart,
+    create_guest_cart, get_guest_cart, remove_from_guest_cart, set_guest_cart_quantity,
+};
+
+/// path ของตะกร้าที่ใช้ได้โดยไม่ต้องเข้าสู่ระบบ ตรวจสิทธิ์ด้วย token ของตะกร้าแทน
+pub const GUEST_CART_PATH: &str = "/guest-cart";
+
+pub fn configure_guest_cart_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope(GUEST_CART_PATH)
+            .route("", web::post().to(create_guest_cart))
+            .route("", web::get().to(get_guest_cart))
+            .route("/add", web::post().to(add_to_guest_cart))
+            .route("/items/{product_id}", web::put().to(set_guest_cart_quantity))
+            .route("/remove/{product_id}", web::delete().to(remove_from_guest_cart))
+            .route("/clear", web::delete().to(clear_guest_cart))
+            .route("/total", web::get().to(calculate_guest_cart_total))
+            .route("/acknowledge", web::post().to(acknowledge_guest_cart_changes))
+            .route("/checkout", web::post().to(checkout_guest_cart)),
+    );
+}
@@ -0,0 +1,18 @@
+use actix_web::web;
+
+use crate::controllers::inventory::{
+    adjust_stock, create_location, get_location_stock, get_locations, get_product_stock,
+    transfer_stock,
+};
+
+pub fn configure_inventory_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/inventory")
+            .route("/locations", web::get().to(get_locations))
+            .route("/locations", web::post().to(create_location))
+            .route("/locations/{location_id}/stock", web::get().to(get_location_stock))
+            .route("/locations/{location_id}/adjustments", web::post().to(adjust_stock))
+            .route("/products/{product_id}/stock", web::get().to(get_product_stock))
+            .route("/transfers", web::post().to(transfer_stock)),
+    );
+}
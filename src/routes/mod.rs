@@ -1,9 +1,37 @@
 pub mod auth;
 pub mod products;
 pub mod cart;
+pub mod guest_cart;
+pub mod carts;
+pub mod cart_reminders;
 pub mod order;
+pub mod inventory;
+pub mod reviews;
+pub mod attributes;
+pub mod categories;
+pub mod translations;
+pub mod wishlists;
+pub mod alerts;
+pub mod users;
+pub mod downloads;
+pub mod vendors;
+pub mod coupons;
 
 pub use auth::configure_auth_routes;
 pub use products::configure_product_routes;
 pub use cart::configure_cart_routes;
+pub use guest_cart::configure_guest_cart_routes;
+pub use carts::configure_named_cart_routes;
+pub use cart_reminders::configure_cart_reminder_routes;
 pub use order::configure_order_routes;
+pub use inventory::configure_inventory_routes;
+pub use reviews::configure_review_routes;
+pub use attributes::configure_attribute_routes;
+pub use categories::configure_category_routes;
+pub use translations::configure_translation_routes;
+pub use wishlists::configure_wishlist_routes;
+pub use alerts::configure_alert_routes;
+pub use users::configure_user_routes;
+pub use downloads::configure_download_routes;
+pub use vendors::configure_vendor_routes;
+pub use coupons::configure_coupon_routes;
@@ -1,16 +1,24 @@
 use actix_web::web;
 
-use crate::controllers::order::{create_order, get_order_details, get_order_history, update_order_status};
-
-
+use crate::controllers::download::get_order_downloads;
+use crate::controllers::order::{
+    claim_order, create_order, get_guest_order, get_order_details, get_order_history,
+    patch_order, update_order_status,
+};
 
+/// path ของคำสั่งซื้อ guest ตรวจสิทธิ์ด้วย access token แทน token เข้าสู่ระบบ
+pub const GUEST_ORDER_PATH: &str = "/guest-orders/";
 
 pub fn configure_order_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/orders")
+            .route("/claim", web::post().to(claim_order))
             .route("/{user_id}/create", web::post().to(create_order))
             .route("/{order_id}/details", web::get().to(get_order_details))
             .route("/{user_id}/history", web::get().to(get_order_history))
-            .route("/{order_id}/status", web::put().to(update_order_status)),
-    );
+            .route("/{order_id}/status", web::put().to(update_order_status))
+            .route("/{order_id}", web::patch().to(patch_order))
+            .route("/{order_id}/downloads", web::get().to(get_order_downloads)),
+    )
+    .service(web::scope("/guest-orders").route("/{token}", web::get().to(get_guest_order)));
 }
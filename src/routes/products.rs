@@ -1,17 +1,69 @@
 use crate::controllers::product::{
-    create_product, delete_product, get_product, get_products, update_product,
+    create_product, delete_product, export_products, get_archived_products, get_product,
+    get_products, import_products, patch_product, restore_product, update_product,
     update_product_status,
 };
+use crate::controllers::alert::{subscribe_alert, unsubscribe_alert};
+use crate::controllers::attribute::set_product_attributes;
+use crate::controllers::bundle::{get_bundle_components, set_bundle_components};
+use crate::controllers::category::set_product_category;
+use crate::controllers::download::{delete_product_file, get_product_files, upload_product_file};
+use crate::controllers::price::{cancel_price_schedule, get_price_history, schedule_price};
+use crate::controllers::recommendation::{
+    get_pinned_related_products, get_related_products, pin_related_product, unpin_related_product,
+};
+use crate::controllers::review::{create_review, get_product_reviews};
+use crate::controllers::translation::{
+    delete_product_translation, get_product_translations, put_product_translation,
+};
+use crate::controllers::product_image::{
+    delete_product_image, get_product_images, reorder_product_images, update_product_image,
+    upload_product_image,
+};
 use actix_web::web;
 
 pub fn configure_product_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/products")
             .route("", web::get().to(get_products))
+            .route("/archived", web::get().to(get_archived_products))
+            .route("/export", web::get().to(export_products))
+            .route("/import", web::post().to(import_products))
             .route("/{id}", web::get().to(get_product))
             .route("", web::post().to(create_product))
             .route("/{id}", web::put().to(update_product))
+            .route("/{id}", web::patch().to(patch_product))
             .route("/{id}", web::delete().to(delete_product))
-            .route("/{id}/status", web::put().to(update_product_status)),
+            .route("/{id}/status", web::put().to(update_product_status))
+            .route("/{id}/restore", web::post().to(restore_product))
+            .route("/{id}/images", web::get().to(get_product_images))
+            .route("/{id}/images", web::post().to(upload_product_image))
+            .route("/{id}/images/order", web::put().to(reorder_product_images))
+            .route("/{id}/images/{image_id}", web::put().to(update_product_image))
+            .route("/{id}/images/{image_id}", web::delete().to(delete_product_image))
+            .route("/{id}/price-history", web::get().to(get_price_history))
+            .route("/{id}/price-schedules", web::post().to(schedule_price))
+            .route(
+                "/{id}/price-schedules/{schedule_id}",
+                web::delete().to(cancel_price_schedule),
+            )
+            .route("/{id}/reviews", web::get().to(get_product_reviews))
+            .route("/{id}/reviews", web::post().to(create_review))
+            .route("/{id}/attributes", web::put().to(set_product_attributes))
+            .route("/{id}/category", web::put().to(set_product_category))
+            .route("/{id}/translations", web::get().to(get_product_translations))
+            .route("/{id}/translations/{locale}", web::put().to(put_product_translation))
+            .route("/{id}/translations/{locale}", web::delete().to(delete_product_translation))
+            .route("/{id}/related", web::get().to(get_related_products))
+            .route("/{id}/related/pins", web::get().to(get_pinned_related_products))
+            .route("/{id}/related/pins/{related_id}", web::put().to(pin_related_product))
+            .route("/{id}/related/pins/{related_id}", web::delete().to(unpin_related_product))
+            .route("/{id}/alerts", web::post().to(subscribe_alert))
+            .route("/{id}/alerts/{kind}", web::delete().to(unsubscribe_alert))
+            .route("/{id}/files", web::get().to(get_product_files))
+            .route("/{id}/files", web::post().to(upload_product_file))
+            .route("/{id}/files/{file_id}", web::delete().to(delete_product_file))
+            .route("/{id}/components", web::get().to(get_bundle_components))
+            .route("/{id}/components", web::put().to(set_bundle_components)),
     );
 }
@@ -0,0 +1,11 @@
+use actix_web::web;
+
+use crate::controllers::review::{get_reviews, moderate_review};
+
+pub fn configure_review_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/reviews")
+            .route("", web::get().to(get_reviews))
+            .route("/{id}/moderation", web::put().to(moderate_review)),
+    );
+}
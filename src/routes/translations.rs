@@ -0,0 +1,9 @@
+use actix_web::web;
+
+use crate::controllers::translation::get_missing_translations;
+
+pub fn configure_translation_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/translations").route("/missing", web::get().to(get_missing_translations)),
+    );
+}
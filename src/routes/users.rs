@@ -0,0 +1,12 @@
+use actix_web::web;
+
+use crate::controllers::user::{get_me, patch_me, request_email_verification};
+
+pub fn configure_user_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/users")
+            .route("/me", web::get().to(get_me))
+            .route("/me", web::patch().to(patch_me))
+            .route("/me/email-verification", web::post().to(request_email_verification)),
+    );
+}
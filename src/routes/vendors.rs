@@ -0,0 +1,24 @@
+use actix_web::web;
+
+use crate::controllers::vendor::{
+    create_payout, create_vendor, get_my_ledger, get_my_orders, get_my_products, get_my_vendor,
+    get_vendor_ledger, get_vendors, patch_vendor,
+};
+
+pub fn configure_vendor_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/vendors")
+            .route("", web::get().to(get_vendors))
+            .route("", web::post().to(create_vendor))
+            .route("/{id}", web::patch().to(patch_vendor))
+            .route("/{id}/ledger", web::get().to(get_vendor_ledger))
+            .route("/{id}/payouts", web::post().to(create_payout)),
+    )
+    .service(
+        web::scope("/vendor")
+            .route("", web::get().to(get_my_vendor))
+            .route("/products", web::get().to(get_my_products))
+            .route("/orders", web::get().to(get_my_orders))
+            .route("/ledger", web::get().to(get_my_ledger)),
+    );
+}
@@ -0,0 +1,27 @@
+use actix_web::web;
+
+use crate::controllers::wishlist::{
+    add_wishlist_item, create_wishlist, delete_wishlist, get_shared_wishlist, get_wishlist,
+    get_wishlists, move_to_cart, remove_wishlist_item, rename_wishlist, share_wishlist,
+    unshare_wishlist,
+};
+
+/// path ของลิงก์แชร์ที่เปิดให้ดูได้โดยไม่ต้องเข้าสู่ระบบ
+pub const SHARED_WISHLIST_PATH: &str = "/wishlists/shared/";
+
+pub fn configure_wishlist_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/wishlists")
+            .route("/shared/{token}", web::get().to(get_shared_wishlist))
+            .route("", web::get().to(get_wishlists))
+            .route("", web::post().to(create_wishlist))
+            .route("/{id}", web::get().to(get_wishlist))
+            .route("/{id}", web::put().to(rename_wishlist))
+            .route("/{id}", web::delete().to(delete_wishlist))
+            .route("/{id}/items", web::post().to(add_wishlist_item))
+            .route("/{id}/items/{product_id}", web::delete().to(remove_wishlist_item))
+            .route("/{id}/items/{product_id}/move-to-cart", web::post().to(move_to_cart))
+            .route("/{id}/share", web::post().to(share_wishlist))
+            .route("/{id}/share", web::delete().to(unshare_wishlist)),
+    );
+}
@@ -0,0 +1,283 @@
+use crate::entity::sea_orm_active_enums::{AlertKind, ProductStatus};
+use crate::entity::{product_alerts, products, users};
+use crate::error::ApiError;
+use crate::services::notification::{Notification, NotificationChannel};
+use crate::services::{inventory_service, product_service};
+use rust_decimal::Decimal;
+use sea_orm::{entity::*, query::*, DatabaseConnection};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub async fn list_alerts(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<Vec<product_alerts::Model>, ApiError> {
+    product_alerts::Entity::find()
+        .filter(product_alerts::Column::UserId.eq(user_id))
+        .order_by_desc(product_alerts::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// สมัครรับแจ้งเตือนของสินค้า ถ้าเคยสมัครชนิดเดียวกันไว้จะเปิดใช้ใหม่และล้างสถานะการแจ้งเตือน
+pub async fn subscribe(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    product_id: Uuid,
+    kind: AlertKind,
+    target_price: Option<Decimal>,
+) -> Result<product_alerts::Model, ApiError> {
+    product_service::get_product_by_id(db, product_id).await?;
+
+    match (kind, target_price) {
+        (AlertKind::PriceDrop, Some(price)) if price > Decimal::ZERO => {}
+        (AlertKind::PriceDrop, _) => {
+            return Err(ApiError::ValidationError(
+                "target_price must be greater than zero for price drop alerts".to_string(),
+            ))
+        }
+        (AlertKind::BackInStock, Some(_)) => {
+            return Err(ApiError::ValidationError(
+                "target_price is only supported for price drop alerts".to_string(),
+            ))
+        }
+        (AlertKind::BackInStock, None) => {}
+    }
+
+    let existing = product_alerts::Entity::find()
+        .filter(product_alerts::Column::UserId.eq(user_id))
+        .filter(product_alerts::Column::ProductId.eq(product_id))
+        .filter(product_alerts::Column::Kind.eq(kind))
+        .one(db)
+        .await?;
+
+    let alert = match existing {
+        Some(alert) => {
+            let mut active_model: product_alerts::ActiveModel = alert.into();
+            active_model.target_price = Set(target_price);
+            active_model.is_active = Set(true);
+            active_model.last_notified_price = Set(None);
+            active_model.last_notified_at = Set(None);
+            active_model.update(db).await?
+        }
+        None => {
+            product_alerts::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                user_id: Set(user_id),
+                product_id: Set(product_id),
+                kind: Set(kind),
+                target_price: Set(target_price),
+                is_active: Set(true),
+                last_notified_price: Set(None),
+                last_notified_at: Set(None),
+                created_at: Set(chrono::Utc::now()),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+    Ok(alert)
+}
+
+pub async fn unsubscribe(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    product_id: Uuid,
+    kind: AlertKind,
+) -> Result<(), ApiError> {
+    let result = product_alerts::Entity::delete_many()
+        .filter(product_alerts::Column::UserId.eq(user_id))
+        .filter(product_alerts::Column::ProductId.eq(product_id))
+        .filter(product_alerts::Column::Kind.eq(kind))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(ApiError::NotFound("Alert subscription not found".to_string()));
+    }
+    Ok(())
+}
+
+/// ตรวจการเปลี่ยนแปลงของสินค้าแล้วส่งแจ้งเตือนให้ผู้ที่สมัครไว้
+///
+/// เรียกหลังบันทึกสินค้าเสร็จ ถ้าส่งไม่สำเร็จจะเขียน log ไว้แต่ไม่ทำให้การแก้สินค้าล้มเหลว
+pub async fn notify_product_change(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    before: &products::Model,
+    after: &products::Model,
+) {
+    if let Err(e) = dispatch(db, channel, before, after).await {
+        eprintln!("Product alert dispatch failed for {}: {}", after.id, e);
+    }
+}
+
+/// แจ้งเตือนสินค้าหลายรายการที่เปลี่ยนโดยไม่ผ่าน `update_product` เช่น ยกเลิกคำสั่งซื้อหรือราคาตามเวลา
+///
+/// `before` คือสินค้าก่อนเปลี่ยน ต้องเรียกหลัง commit แล้ว จะดึงค่าปัจจุบันมาเทียบเอง
+pub async fn notify_products_changed(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    before: &[products::Model],
+) {
+    if before.is_empty() {
+        return;
+    }
+    let product_ids: Vec<Uuid> = before.iter().map(|product| product.id).collect();
+    let after = match products::Entity::find()
+        .filter(products::Column::Id.is_in(product_ids))
+        .all(db)
+        .await
+    {
+        Ok(after) => after,
+        Err(e) => {
+            eprintln!("Product alert dispatch failed: {}", e);
+            return;
+        }
+    };
+    for product in &after {
+        if let Some(before) = before.iter().find(|before| before.id == product.id) {
+            notify_product_change(db, channel, before, product).await;
+        }
+    }
+}
+
+/// แจ้งสินค้ากลับมามีของ เมื่อสต็อกรวมเปลี่ยนจาก 0 เป็นมากกว่า 0
+///
+/// `stock_before` คือผลของ `inventory_service::stock_totals` ก่อนเปลี่ยน ต้องเรียกหลัง commit แล้ว
+pub async fn notify_restocked(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    stock_before: &HashMap<Uuid, i64>,
+) {
+    if let Err(e) = dispatch_restocked(db, channel, stock_before).await {
+        eprintln!("Back-in-stock alert dispatch failed: {}", e);
+    }
+}
+
+async fn dispatch_restocked(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    stock_before: &HashMap<Uuid, i64>,
+) -> Result<(), ApiError> {
+    let out_of_stock: Vec<Uuid> = stock_before
+        .iter()
+        .filter(|(_, quantity)| **quantity <= 0)
+        .map(|(product_id, _)| *product_id)
+        .collect();
+    if out_of_stock.is_empty() {
+        return Ok(());
+    }
+
+    let stock_after = inventory_service::stock_totals(db, &out_of_stock).await?;
+    let products = products::Entity::find()
+        .filter(products::Column::Id.is_in(out_of_stock))
+        .filter(products::Column::DeletedAt.is_null())
+        .filter(products::Column::Status.eq(ProductStatus::Available))
+        .all(db)
+        .await?;
+    for product in products {
+        if stock_after.get(&product.id).is_some_and(|quantity| *quantity > 0) {
+            notify_back_in_stock(db, channel, &product).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn dispatch(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    before: &products::Model,
+    after: &products::Model,
+) -> Result<(), ApiError> {
+    if after.deleted_at.is_some() || after.status != ProductStatus::Available {
+        return Ok(());
+    }
+
+    if before.status != ProductStatus::Available {
+        notify_back_in_stock(db, channel, after).await?;
+    }
+    if after.price < before.price {
+        notify_price_drop(db, channel, after).await?;
+    }
+    Ok(())
+}
+
+/// แจ้งครั้งเดียวแล้วปิดการสมัคร ถ้าอยากได้อีกต้องสมัครใหม่
+async fn notify_back_in_stock(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    product: &products::Model,
+) -> Result<(), ApiError> {
+    let alerts = active_alerts(db, product.id, AlertKind::BackInStock).await?;
+    let notification = Notification {
+        subject: format!("{} is back in stock", product.name),
+        body: format!("{} is available again at {}.", product.name, product.price),
+    };
+
+    for (alert, user) in alerts {
+        if let Err(e) = channel.send(&user.email, &notification).await {
+            eprintln!("Failed to send alert {}: {}", alert.id, e);
+            continue;
+        }
+
+        let mut active_model: product_alerts::ActiveModel = alert.into();
+        active_model.is_active = Set(false);
+        active_model.last_notified_at = Set(Some(chrono::Utc::now()));
+        active_model.update(db).await?;
+    }
+    Ok(())
+}
+
+/// แจ้งเมื่อราคาต่ำกว่าหรือเท่ากับราคาเป้าหมาย และต่ำกว่าราคาที่เคยแจ้งไปครั้งล่าสุด
+async fn notify_price_drop(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    product: &products::Model,
+) -> Result<(), ApiError> {
+    let alerts = active_alerts(db, product.id, AlertKind::PriceDrop).await?;
+    let notification = Notification {
+        subject: format!("Price drop on {}", product.name),
+        body: format!("{} is now {}.", product.name, product.price),
+    };
+
+    for (alert, user) in alerts {
+        let reached_target = alert
+            .target_price
+            .is_some_and(|target| product.price <= target);
+        let already_notified = alert
+            .last_notified_price
+            .is_some_and(|notified| product.price >= notified);
+        if !reached_target || already_notified {
+            continue;
+        }
+
+        if let Err(e) = channel.send(&user.email, &notification).await {
+            eprintln!("Failed to send alert {}: {}", alert.id, e);
+            continue;
+        }
+
+        let mut active_model: product_alerts::ActiveModel = alert.into();
+        active_model.last_notified_price = Set(Some(product.price));
+        active_model.last_notified_at = Set(Some(chrono::Utc::now()));
+        active_model.update(db).await?;
+    }
+    Ok(())
+}
+
+async fn active_alerts(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    kind: AlertKind,
+) -> Result<Vec<(product_alerts::Model, users::Model)>, ApiError> {
+    Ok(product_alerts::Entity::find()
+        .find_also_related(users::Entity)
+        .filter(product_alerts::Column::ProductId.eq(product_id))
+        .filter(product_alerts::Column::Kind.eq(kind))
+        .filter(product_alerts::Column::IsActive.eq(true))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(alert, user)| user.map(|user| (alert, user)))
+        .collect())
+}
@@ -0,0 +1,463 @@
+use crate::entity::sea_orm_active_enums::AttributeDataType;
+use crate::entity::{attribute_definitions, product_attribute_values, products};
+use crate::error::ApiError;
+use crate::services::product_service;
+use rust_decimal::Decimal;
+use sea_orm::{
+    entity::*, query::*, sea_query::Expr, DatabaseConnection, Select, TransactionTrait,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// query param ที่ขึ้นต้นด้วย prefix นี้คือตัวกรอง attribute เช่น `attr.brand=acme,globex`
+pub const FILTER_PREFIX: &str = "attr.";
+
+/// ค่า attribute ของสินค้าหนึ่งตัว แสดงพร้อมชื่อและหน่วย
+#[derive(Serialize)]
+pub struct ProductAttributeResponse {
+    pub code: String,
+    pub name: String,
+    pub data_type: AttributeDataType,
+    pub unit: Option<String>,
+    pub value: Value,
+}
+
+#[derive(Serialize)]
+pub struct FacetValue {
+    pub value: Value,
+    pub count: i64,
+}
+
+/// จำนวนสินค้าต่อค่าของ attribute หนึ่งตัว สำหรับทำแถบตัวกรอง
+#[derive(Serialize)]
+pub struct Facet {
+    pub code: String,
+    pub name: String,
+    pub data_type: AttributeDataType,
+    pub unit: Option<String>,
+    pub values: Vec<FacetValue>,
+}
+
+/// เงื่อนไขกรองที่แปลงจาก query string แล้ว
+pub enum FilterCondition {
+    /// ค่าใดค่าหนึ่งในรายการ (text และ enum)
+    OneOf(Vec<String>),
+    /// ตัวเลขค่าใดค่าหนึ่งในรายการ
+    NumberOneOf(Vec<Decimal>),
+    /// ช่วงตัวเลข `min..max` เว้นข้างใดข้างหนึ่งได้
+    NumberRange(Option<Decimal>, Option<Decimal>),
+    Boolean(bool),
+}
+
+/// ค่าที่แยกลงคอลัมน์ text_value, number_value, boolean_value
+type StoredValue = (Option<String>, Option<Decimal>, Option<bool>);
+
+/// หนึ่งแถวของผลนับ facet: attribute_id, ค่าทั้งสามคอลัมน์ และจำนวน
+type FacetRow = (Uuid, Option<String>, Option<Decimal>, Option<bool>, i64);
+
+pub struct AttributeFilter {
+    pub attribute: attribute_definitions::Model,
+    pub condition: FilterCondition,
+}
+
+pub async fn list_definitions(
+    db: &DatabaseConnection,
+) -> Result<Vec<attribute_definitions::Model>, ApiError> {
+    attribute_definitions::Entity::find()
+        .order_by_asc(attribute_definitions::Column::Code)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+pub async fn create_definition(
+    db: &DatabaseConnection,
+    code: String,
+    name: String,
+    data_type: AttributeDataType,
+    unit: Option<String>,
+    allowed_values: Option<Vec<String>>,
+    is_filterable: bool,
+) -> Result<attribute_definitions::Model, ApiError> {
+    match (data_type, &allowed_values) {
+        (AttributeDataType::Enum, Some(values)) if !values.is_empty() => {}
+        (AttributeDataType::Enum, _) => {
+            return Err(ApiError::ValidationError(
+                "allowed_values is required for enum attributes".to_string(),
+            ))
+        }
+        (_, Some(_)) => {
+            return Err(ApiError::ValidationError(
+                "allowed_values is only supported for enum attributes".to_string(),
+            ))
+        }
+        (_, None) => {}
+    }
+
+    let existing = attribute_definitions::Entity::find()
+        .filter(attribute_definitions::Column::Code.eq(code.clone()))
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        return Err(ApiError::Conflict(format!("Attribute with code {} already exists", code)));
+    }
+
+    let definition = attribute_definitions::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        code: Set(code),
+        name: Set(name),
+        data_type: Set(data_type),
+        unit: Set(unit),
+        allowed_values: Set(allowed_values.map(Value::from)),
+        is_filterable: Set(is_filterable),
+        created_at: Set(chrono::Utc::now()),
+    };
+
+    definition.insert(db).await.map_err(ApiError::from)
+}
+
+/// ลบนิยาม attribute พร้อมค่าที่ผูกกับสินค้าทั้งหมด (cascade)
+pub async fn delete_definition(db: &DatabaseConnection, attribute_id: Uuid) -> Result<(), ApiError> {
+    let result = attribute_definitions::Entity::delete_by_id(attribute_id)
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(ApiError::NotFound(format!("Attribute with ID {} not found", attribute_id)));
+    }
+    Ok(())
+}
+
+/// ดึง attribute ของสินค้าหลายตัวในคำสั่งเดียว จัดกลุ่มตาม product_id
+pub async fn attributes_for_products(
+    db: &DatabaseConnection,
+    product_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<ProductAttributeResponse>>, ApiError> {
+    let rows = product_attribute_values::Entity::find()
+        .find_also_related(attribute_definitions::Entity)
+        .filter(product_attribute_values::Column::ProductId.is_in(product_ids.iter().copied()))
+        .order_by_asc(attribute_definitions::Column::Code)
+        .all(db)
+        .await?;
+
+    let mut grouped: HashMap<Uuid, Vec<ProductAttributeResponse>> = HashMap::new();
+    for (value, definition) in rows {
+        let Some(definition) = definition else { continue };
+        grouped
+            .entry(value.product_id)
+            .or_default()
+            .push(ProductAttributeResponse {
+                value: stored_value(&value),
+                code: definition.code,
+                name: definition.name,
+                data_type: definition.data_type,
+                unit: definition.unit,
+            });
+    }
+    Ok(grouped)
+}
+
+/// แทนที่ค่า attribute ของสินค้าด้วยชุดที่ส่งมา ค่า null หมายถึงลบค่านั้นออก
+pub async fn set_product_attributes(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    values: HashMap<String, Value>,
+) -> Result<Vec<ProductAttributeResponse>, ApiError> {
+    product_service::get_product_by_id(db, product_id).await?;
+
+    let definitions = definitions_by_code(db).await?;
+    let mut rows = Vec::new();
+    for (code, value) in values {
+        let definition = definitions
+            .get(&code)
+            .ok_or_else(|| ApiError::ValidationError(format!("Unknown attribute: {}", code)))?;
+        rows.push((definition.id, typed_value(definition, value)?));
+    }
+
+    let txn = db.begin().await?;
+    for (attribute_id, value) in rows {
+        product_attribute_values::Entity::delete_many()
+            .filter(product_attribute_values::Column::ProductId.eq(product_id))
+            .filter(product_attribute_values::Column::AttributeId.eq(attribute_id))
+            .exec(&txn)
+            .await?;
+
+        let Some((text_value, number_value, boolean_value)) = value else { continue };
+        let row = product_attribute_values::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            product_id: Set(product_id),
+            attribute_id: Set(attribute_id),
+            text_value: Set(text_value),
+            number_value: Set(number_value),
+            boolean_value: Set(boolean_value),
+        };
+        row.insert(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(attributes_for_products(db, &[product_id])
+        .await?
+        .remove(&product_id)
+        .unwrap_or_default())
+}
+
+/// แปลง query param `attr.<code>` เป็นตัวกรองตามชนิดของ attribute
+pub async fn parse_filters(
+    db: &DatabaseConnection,
+    params: &HashMap<String, String>,
+) -> Result<Vec<AttributeFilter>, ApiError> {
+    let requested: Vec<(&str, &str)> = params
+        .iter()
+        .filter_map(|(key, value)| {
+            key.strip_prefix(FILTER_PREFIX).map(|code| (code, value.as_str()))
+        })
+        .collect();
+    if requested.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut definitions = definitions_by_code(db).await?;
+    let mut filters = Vec::new();
+    for (code, raw) in requested {
+        let attribute = definitions
+            .remove(code)
+            .filter(|definition| definition.is_filterable)
+            .ok_or_else(|| {
+                ApiError::ValidationError(format!("Unknown filter attribute: {}", code))
+            })?;
+        let condition = parse_condition(&attribute, raw)?;
+        filters.push(AttributeFilter { attribute, condition });
+    }
+    Ok(filters)
+}
+
+/// เพิ่มเงื่อนไข `products.id IN (...)` ต่อหนึ่งตัวกรอง
+pub fn apply_filters<'a>(
+    mut query: Select<products::Entity>,
+    filters: impl IntoIterator<Item = &'a AttributeFilter>,
+) -> Select<products::Entity> {
+    for filter in filters {
+        let mut matching = product_attribute_values::Entity::find()
+            .select_only()
+            .column(product_attribute_values::Column::ProductId)
+            .filter(product_attribute_values::Column::AttributeId.eq(filter.attribute.id));
+        matching = match &filter.condition {
+            FilterCondition::OneOf(values) => matching
+                .filter(product_attribute_values::Column::TextValue.is_in(values.clone())),
+            FilterCondition::NumberOneOf(values) => matching
+                .filter(product_attribute_values::Column::NumberValue.is_in(values.clone())),
+            FilterCondition::NumberRange(min, max) => {
+                if let Some(min) = min {
+                    matching = matching.filter(product_attribute_values::Column::NumberValue.gte(*min));
+                }
+                if let Some(max) = max {
+                    matching = matching.filter(product_attribute_values::Column::NumberValue.lte(*max));
+                }
+                matching
+            }
+            FilterCondition::Boolean(value) => {
+                matching.filter(product_attribute_values::Column::BooleanValue.eq(*value))
+            }
+        };
+        query = query.filter(products::Column::Id.in_subquery(matching.into_query()));
+    }
+    query
+}
+
+/// นับจำนวนสินค้าต่อค่าของทุก attribute ที่กรองได้
+///
+/// attribute ที่ถูกกรองอยู่จะนับจากผลที่ใช้ตัวกรองอื่นทั้งหมดยกเว้นตัวเอง
+/// เพื่อให้ตัวเลือกอื่นของ attribute เดียวกันยังแสดงจำนวนที่เลือกเพิ่มได้
+/// ระบุ `category_id` เพื่อนับเฉพาะสินค้าในหมวดหมู่ที่แสดงอยู่
+pub async fn get_facets(
+    db: &DatabaseConnection,
+    category_id: Option<Uuid>,
+    filters: &[AttributeFilter],
+) -> Result<Vec<Facet>, ApiError> {
+    let definitions: Vec<attribute_definitions::Model> = attribute_definitions::Entity::find()
+        .filter(attribute_definitions::Column::IsFilterable.eq(true))
+        .order_by_asc(attribute_definitions::Column::Code)
+        .all(db)
+        .await?;
+
+    let mut counts = count_values(db, category_id, filters.iter().collect(), None).await?;
+    for filter in filters {
+        let others = filters
+            .iter()
+            .filter(|other| other.attribute.id != filter.attribute.id)
+            .collect();
+        counts.retain(|(attribute_id, _, _)| *attribute_id != filter.attribute.id);
+        counts.extend(count_values(db, category_id, others, Some(filter.attribute.id)).await?);
+    }
+
+    let mut grouped: HashMap<Uuid, Vec<FacetValue>> = HashMap::new();
+    for (attribute_id, value, count) in counts {
+        grouped.entry(attribute_id).or_default().push(FacetValue { value, count });
+    }
+
+    Ok(definitions
+        .into_iter()
+        .map(|definition| Facet {
+            values: grouped.remove(&definition.id).unwrap_or_default(),
+            code: definition.code,
+            name: definition.name,
+            data_type: definition.data_type,
+            unit: definition.unit,
+        })
+        .collect())
+}
+
+async fn count_values(
+    db: &DatabaseConnection,
+    category_id: Option<Uuid>,
+    filters: Vec<&AttributeFilter>,
+    attribute_id: Option<Uuid>,
+) -> Result<Vec<(Uuid, Value, i64)>, ApiError> {
+    let mut catalog = product_service::catalog_query();
+    if let Some(category_id) = category_id {
+        catalog = catalog.filter(products::Column::CategoryId.eq(category_id));
+    }
+    let catalog = apply_filters(catalog, filters)
+        .select_only()
+        .column(products::Column::Id)
+        .into_query();
+
+    let mut query = product_attribute_values::Entity::find()
+        .select_only()
+        .column(product_attribute_values::Column::AttributeId)
+        .column(product_attribute_values::Column::TextValue)
+        .column(product_attribute_values::Column::NumberValue)
+        .column(product_attribute_values::Column::BooleanValue)
+        .column_as(Expr::col(product_attribute_values::Column::Id).count(), "count")
+        .filter(product_attribute_values::Column::ProductId.in_subquery(catalog));
+    if let Some(attribute_id) = attribute_id {
+        query = query.filter(product_attribute_values::Column::AttributeId.eq(attribute_id));
+    }
+
+    let rows: Vec<FacetRow> = query
+        .group_by(product_attribute_values::Column::AttributeId)
+        .group_by(product_attribute_values::Column::TextValue)
+        .group_by(product_attribute_values::Column::NumberValue)
+        .group_by(product_attribute_values::Column::BooleanValue)
+        .order_by_desc(Expr::col(product_attribute_values::Column::Id).count())
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(attribute_id, text_value, number_value, boolean_value, count)| {
+            (attribute_id, to_json(text_value, number_value, boolean_value), count)
+        })
+        .collect())
+}
+
+async fn definitions_by_code(
+    db: &DatabaseConnection,
+) -> Result<HashMap<String, attribute_definitions::Model>, ApiError> {
+    Ok(attribute_definitions::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|definition| (definition.code.clone(), definition))
+        .collect())
+}
+
+/// ตรวจค่าตามชนิดของ attribute แล้วแยกลงคอลัมน์ที่ตรงชนิด
+fn typed_value(
+    definition: &attribute_definitions::Model,
+    value: Value,
+) -> Result<Option<StoredValue>, ApiError> {
+    let invalid = || {
+        ApiError::ValidationError(format!(
+            "Invalid value for attribute {}: expected {:?}",
+            definition.code, definition.data_type
+        ))
+    };
+
+    let stored = match (definition.data_type, value) {
+        (_, Value::Null) => return Ok(None),
+        (AttributeDataType::Text, Value::String(text)) => (Some(text), None, None),
+        (AttributeDataType::Number, Value::Number(number)) => {
+            let number = Decimal::from_str(&number.to_string()).map_err(|_| invalid())?;
+            (None, Some(number), None)
+        }
+        (AttributeDataType::Boolean, Value::Bool(flag)) => (None, None, Some(flag)),
+        (AttributeDataType::Enum, Value::String(text)) => {
+            if !allowed_values(definition).contains(&text) {
+                return Err(ApiError::ValidationError(format!(
+                    "{} is not an allowed value for attribute {}",
+                    text, definition.code
+                )));
+            }
+            (Some(text), None, None)
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(Some(stored))
+}
+
+fn parse_condition(
+    definition: &attribute_definitions::Model,
+    raw: &str,
+) -> Result<FilterCondition, ApiError> {
+    let invalid = || {
+        ApiError::ValidationError(format!(
+            "Invalid filter value for attribute {}: {}",
+            definition.code, raw
+        ))
+    };
+    let parse_number = |text: &str| Decimal::from_str(text.trim()).map_err(|_| invalid());
+    let list: Vec<String> = raw
+        .split(',')
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect();
+    if list.is_empty() {
+        return Err(invalid());
+    }
+
+    match definition.data_type {
+        AttributeDataType::Text | AttributeDataType::Enum => Ok(FilterCondition::OneOf(list)),
+        AttributeDataType::Boolean => match raw.trim() {
+            "true" => Ok(FilterCondition::Boolean(true)),
+            "false" => Ok(FilterCondition::Boolean(false)),
+            _ => Err(invalid()),
+        },
+        AttributeDataType::Number => match raw.split_once("..") {
+            Some((min, max)) => {
+                let min = (!min.trim().is_empty()).then(|| parse_number(min)).transpose()?;
+                let max = (!max.trim().is_empty()).then(|| parse_number(max)).transpose()?;
+                Ok(FilterCondition::NumberRange(min, max))
+            }
+            None => list
+                .iter()
+                .map(|value| parse_number(value))
+                .collect::<Result<Vec<_>, _>>()
+                .map(FilterCondition::NumberOneOf),
+        },
+    }
+}
+
+fn allowed_values(definition: &attribute_definitions::Model) -> Vec<String> {
+    definition
+        .allowed_values
+        .clone()
+        .and_then(|values| serde_json::from_value(values).ok())
+        .unwrap_or_default()
+}
+
+fn stored_value(value: &product_attribute_values::Model) -> Value {
+    to_json(value.text_value.clone(), value.number_value, value.boolean_value)
+}
+
+fn to_json(text_value: Option<String>, number_value: Option<Decimal>, boolean_value: Option<bool>) -> Value {
+    match (text_value, number_value, boolean_value) {
+        (Some(text), _, _) => Value::String(text),
+        (_, Some(number), _) => Value::String(number.normalize().to_string()),
+        (_, _, Some(flag)) => Value::Bool(flag),
+        _ => Value::Null,
+    }
+}
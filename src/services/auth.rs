@@ -1,7 +1,11 @@
 use bcrypt::{hash, verify};
 use jsonwebtoken::{encode, EncodingKey, Header};
+use sea_orm::{DatabaseConnection, EntityTrait};
 use serde::{Deserialize, Serialize};
 use std::env;
+use uuid::Uuid;
+use crate::entity::sea_orm_active_enums::UserRole;
+use crate::entity::users;
 use crate::error::ApiError;
 
 #[derive(Serialize, Deserialize)]
@@ -37,3 +41,26 @@ pub fn generate_jwt(user_id: &str) -> Result<String, ApiError>{
     )
     .map_err(|_| ApiError::InternalServerError)
 }
+
+/// ผู้ใช้เป็นผู้ดูแลระบบหรือไม่
+pub async fn is_admin(db: &DatabaseConnection, user_id: Uuid) -> Result<bool, ApiError> {
+    let user = users::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::AuthenticationError("User not found".to_string()))?;
+    Ok(user.role == UserRole::Admin)
+}
+
+/// ตรวจว่าผู้ใช้เป็นผู้ดูแลระบบ
+pub async fn require_admin(db: &DatabaseConnection, user_id: Uuid) -> Result<users::Model, ApiError> {
+    let user = users::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::AuthenticationError("User not found".to_string()))?;
+
+    if user.role != UserRole::Admin {
+        return Err(ApiError::Forbidden("Admin access required".to_string()));
+    }
+
+    Ok(user)
+}
@@ -0,0 +1,224 @@
+use crate::entity::sea_orm_active_enums::{ProductStatus, ProductType};
+use crate::entity::{bundle_components, products};
+use crate::error::ApiError;
+use crate::services::{inventory_service, product_service};
+use sea_orm::{entity::*, query::*, ConnectionTrait, DatabaseConnection};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// สินค้าในชุด พร้อมจำนวนที่มีในคลังที่เปิดใช้อยู่ None คือไม่ได้ติดตามสต็อก (ไม่จำกัด)
+#[derive(Serialize)]
+pub struct BundleComponentResponse {
+    pub product_id: Uuid,
+    pub name: String,
+    pub quantity: i32,
+    pub available_quantity: Option<i64>,
+}
+
+/// ข้อมูลชุดสินค้า จำนวนชุดที่ขายได้คำนวณจาก component ที่มีน้อยที่สุด
+#[derive(Serialize)]
+pub struct BundleResponse {
+    /// None คือทุก component ไม่ได้ติดตามสต็อก จึงขายได้ไม่จำกัด
+    pub available_quantity: Option<i64>,
+    pub components: Vec<BundleComponentResponse>,
+}
+
+/// component ของ bundle พร้อมข้อมูลสินค้า เรียงตามชื่อ
+pub async fn components_of<C: ConnectionTrait>(
+    conn: &C,
+    bundle_id: Uuid,
+) -> Result<Vec<(bundle_components::Model, products::Model)>, ApiError> {
+    let components = bundle_components::Entity::find()
+        .filter(bundle_components::Column::BundleId.eq(bundle_id))
+        .all(conn)
+        .await?;
+    let component_ids: Vec<Uuid> = components.iter().map(|c| c.component_id).collect();
+    let mut products: HashMap<Uuid, products::Model> = products::Entity::find()
+        .filter(products::Column::Id.is_in(component_ids))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|product| (product.id, product))
+        .collect();
+
+    let mut result: Vec<_> = components
+        .into_iter()
+        .filter_map(|component| {
+            let product = products.remove(&component.component_id)?;
+            Some((component, product))
+        })
+        .collect();
+    result.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+    Ok(result)
+}
+
+/// ข้อมูลชุดของสินค้าที่เป็น bundle ในรายการ ดึง component และสต็อกในคำสั่งเดียว
+pub async fn bundles_for_products(
+    db: &DatabaseConnection,
+    products: &[products::Model],
+) -> Result<HashMap<Uuid, BundleResponse>, ApiError> {
+    let bundle_ids: Vec<Uuid> = products
+        .iter()
+        .filter(|product| product.product_type == ProductType::Bundle)
+        .map(|product| product.id)
+        .collect();
+    if bundle_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let components = bundle_components::Entity::find()
+        .filter(bundle_components::Column::BundleId.is_in(bundle_ids.clone()))
+        .all(db)
+        .await?;
+    let component_ids: Vec<Uuid> = components
+        .iter()
+        .map(|component| component.component_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let component_products: HashMap<Uuid, products::Model> = products::Entity::find()
+        .filter(products::Column::Id.is_in(component_ids.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|product| (product.id, product))
+        .collect();
+    let stock = inventory_service::stock_totals(db, &component_ids).await?;
+
+    let mut grouped: HashMap<Uuid, Vec<BundleComponentResponse>> =
+        bundle_ids.into_iter().map(|id| (id, Vec::new())).collect();
+    for component in components {
+        let Some(product) = component_products.get(&component.component_id) else {
+            continue;
+        };
+        // component ที่ถูกเก็บหรือไม่อยู่ในสถานะขายได้ทำให้ขายชุดไม่ได้
+        // component ที่ไม่เคยมีสต็อกในคลังใดเลยไม่ถูกตัดสต็อกตอนสั่งซื้อ เหมือน `allocate_stock`
+        let available_quantity =
+            if product.deleted_at.is_none() && product.status == ProductStatus::Available {
+                stock.get(&product.id).copied()
+            } else {
+                Some(0)
+            };
+        grouped
+            .entry(component.bundle_id)
+            .or_default()
+            .push(BundleComponentResponse {
+                product_id: product.id,
+                name: product.name.clone(),
+                quantity: component.quantity,
+                available_quantity,
+            });
+    }
+
+    Ok(grouped
+        .into_iter()
+        .map(|(bundle_id, mut components)| {
+            components.sort_by(|a, b| a.name.cmp(&b.name));
+            // ชุดที่ไม่มี component ขายไม่ได้
+            let available_quantity = if components.is_empty() {
+                Some(0)
+            } else {
+                components
+                    .iter()
+                    .filter_map(|component| {
+                        component
+                            .available_quantity
+                            .map(|available| available / i64::from(component.quantity))
+                    })
+                    .min()
+            };
+            (bundle_id, BundleResponse { available_quantity, components })
+        })
+        .collect())
+}
+
+pub async fn get_bundle(
+    db: &DatabaseConnection,
+    bundle_id: Uuid,
+) -> Result<BundleResponse, ApiError> {
+    let bundle = find_bundle(db, bundle_id).await?;
+    bundles_for_products(db, std::slice::from_ref(&bundle))
+        .await?
+        .remove(&bundle.id)
+        .ok_or(ApiError::InternalServerError)
+}
+
+/// แทนที่ component ทั้งหมดของชุด
+///
+/// component ต้องเป็นสินค้าจับต้องได้ที่ยังขายอยู่ และไม่ใช่สินค้าชิ้นเดียว
+/// เพราะต้องตัดสต็อกเป็นจำนวนตามชุดที่ขาย และต้องเป็นของร้านเดียวกับชุด
+pub async fn set_components(
+    db: &DatabaseConnection,
+    bundle_id: Uuid,
+    components: Vec<(Uuid, i32)>,
+) -> Result<BundleResponse, ApiError> {
+    let bundle = find_bundle(db, bundle_id).await?;
+
+    let mut seen = HashSet::new();
+    for (component_id, quantity) in &components {
+        if *component_id == bundle_id {
+            return Err(ApiError::ValidationError(
+                "A bundle cannot contain itself".to_string(),
+            ));
+        }
+        if !seen.insert(*component_id) {
+            return Err(ApiError::ValidationError(format!(
+                "Product with ID {} is listed more than once",
+                component_id
+            )));
+        }
+        if *quantity < 1 {
+            return Err(ApiError::ValidationError(format!(
+                "Quantity of product with ID {} must be at least 1",
+                component_id
+            )));
+        }
+
+        let product = product_service::get_product_by_id(db, *component_id).await?;
+        if product.product_type != ProductType::Physical || product.is_one_of_a_kind {
+            return Err(ApiError::ValidationError(format!(
+                "Product with ID {} cannot be part of a bundle",
+                component_id
+            )));
+        }
+        if product.vendor_id != bundle.vendor_id {
+            return Err(ApiError::ValidationError(format!(
+                "Product with ID {} belongs to a different vendor than the bundle",
+                component_id
+            )));
+        }
+    }
+
+    let txn = db.begin().await?;
+    bundle_components::Entity::delete_many()
+        .filter(bundle_components::Column::BundleId.eq(bundle_id))
+        .exec(&txn)
+        .await?;
+    if !components.is_empty() {
+        let rows = components
+            .into_iter()
+            .map(|(component_id, quantity)| bundle_components::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                bundle_id: Set(bundle_id),
+                component_id: Set(component_id),
+                quantity: Set(quantity),
+            });
+        bundle_components::Entity::insert_many(rows).exec(&txn).await?;
+    }
+    txn.commit().await?;
+
+    get_bundle(db, bundle_id).await
+}
+
+async fn find_bundle(db: &DatabaseConnection, bundle_id: Uuid) -> Result<products::Model, ApiError> {
+    let product = product_service::get_product_by_id(db, bundle_id).await?;
+    if product.product_type != ProductType::Bundle {
+        return Err(ApiError::ValidationError(format!(
+            "Product with ID {} is not a bundle",
+            bundle_id
+        )));
+    }
+    Ok(product)
+}
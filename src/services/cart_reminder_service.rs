@@ -0,0 +1,294 @@
+use crate::config::AppConfig;
+use crate::entity::{cart, cart_reminders, carts, orders, users};
+use crate::error::ApiError;
+use crate::services::notification::{Notification, NotificationChannel};
+use crate::services::order_service::OrderStatus;
+use crate::services::{cart_service, named_cart_service, signing};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    entity::*, query::*, sea_query::{Expr, Func, Query}, ConnectionTrait, DatabaseConnection,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// จำนวนตะกร้าที่ส่งอีเมลเตือนต่อรอบ ที่เหลือรอรอบถัดไป
+const REMINDER_BATCH_SIZE: u64 = 100;
+
+/// อัตราการกู้คืนตะกร้าจากอีเมลเตือนในช่วงเวลาที่เลือก
+#[derive(Serialize)]
+pub struct CartRecoveryReport {
+    pub reminders_sent: i64,
+    pub carts_reminded: i64,
+    /// ตะกร้าที่ลูกค้ากดลิงก์ในอีเมลกลับมา
+    pub carts_clicked: i64,
+    /// ตะกร้าที่สั่งซื้อหลังได้อีเมลเตือน
+    pub carts_recovered: i64,
+    /// carts_recovered / carts_reminded
+    pub recovery_rate: Decimal,
+    /// ยอดรวมของคำสั่งซื้อที่กู้คืนได้ ไม่นับคำสั่งซื้อที่ถูกยกเลิก
+    pub recovered_revenue: Decimal,
+}
+
+/// token ของลิงก์ในอีเมลอยู่ในรูป `<reminder_id>.<hmac>`
+pub fn issue_token(secret: &str, reminder_id: Uuid) -> String {
+    let signature = signing::sign(secret, token_message(reminder_id).as_bytes());
+    format!("{}.{}", reminder_id, signature)
+}
+
+pub fn verify_token(secret: &str, token: &str) -> Result<Uuid, ApiError> {
+    let invalid = || ApiError::AuthenticationError("Invalid reminder link".to_string());
+    let (reminder_id, signature) = token.split_once('.').ok_or_else(invalid)?;
+    let reminder_id = Uuid::parse_str(reminder_id).map_err(|_| invalid())?;
+    if !signing::verify(secret, token_message(reminder_id).as_bytes(), signature) {
+        return Err(invalid());
+    }
+    Ok(reminder_id)
+}
+
+fn token_message(reminder_id: Uuid) -> String {
+    format!("cart-reminder:{}", reminder_id)
+}
+
+/// ส่งอีเมลเตือนตะกร้าที่มีสินค้าและไม่ได้แตะนานเกิน `cart_idle_secs`
+///
+/// แต่ละตะกร้าได้อีเมลไม่เกิน `cart_reminder_max` ฉบับ ห่างกันอย่างน้อยเท่ากับเวลาที่ถือว่าทิ้งไว้
+/// คืนค่าจำนวนอีเมลที่ส่ง
+pub async fn send_due_reminders(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    config: &AppConfig,
+) -> Result<usize, ApiError> {
+    let cutoff = Utc::now() - chrono::Duration::seconds(config.cart_idle_secs);
+    let candidates = carts::Entity::find()
+        .find_also_related(users::Entity)
+        .filter(carts::Column::UpdatedAt.lt(cutoff))
+        .filter(carts::Column::ReminderCount.lt(config.cart_reminder_max))
+        .filter(
+            Condition::any()
+                .add(carts::Column::LastRemindedAt.is_null())
+                .add(carts::Column::LastRemindedAt.lt(cutoff)),
+        )
+        .filter(
+            carts::Column::Id.in_subquery(
+                Query::select()
+                    .column(cart::Column::CartId)
+                    .from(cart::Entity)
+                    .to_owned(),
+            ),
+        )
+        .order_by_asc(carts::Column::UpdatedAt)
+        .limit(REMINDER_BATCH_SIZE)
+        .all(db)
+        .await?;
+
+    let mut sent = 0;
+    for (cart, user) in candidates {
+        let Some(user) = user else {
+            continue;
+        };
+        // ตะกร้าหนึ่งส่งไม่สำเร็จไม่ทำให้ตะกร้าอื่นในรอบนี้ไม่ได้อีเมล
+        match remind(db, channel, config, &cart, &user).await {
+            Ok(true) => sent += 1,
+            Ok(false) => {}
+            Err(e) => eprintln!("Failed to send cart reminder for {}: {}", cart.id, e),
+        }
+    }
+    Ok(sent)
+}
+
+/// บันทึกการเตือนก่อนส่งอีเมล ถ้ารอบอื่นนับไปแล้ว (reminder_count ไม่ตรง) จะไม่ส่งซ้ำ
+async fn remind(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    config: &AppConfig,
+    cart: &carts::Model,
+    user: &users::Model,
+) -> Result<bool, ApiError> {
+    let lines = cart_service::cart_lines(db, cart.id).await?;
+    if lines.is_empty() {
+        return Ok(false);
+    }
+
+    let now = Utc::now();
+    let txn = db.begin().await?;
+    let claimed = carts::Entity::update_many()
+        .col_expr(
+            carts::Column::ReminderCount,
+            Expr::col(carts::Column::ReminderCount).add(1),
+        )
+        .col_expr(carts::Column::LastRemindedAt, Expr::value(now))
+        .filter(carts::Column::Id.eq(cart.id))
+        .filter(carts::Column::ReminderCount.eq(cart.reminder_count))
+        .exec(&txn)
+        .await?;
+    if claimed.rows_affected == 0 {
+        return Ok(false);
+    }
+    let reminder = cart_reminders::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        cart_id: Set(cart.id),
+        user_id: Set(user.id),
+        sent_at: Set(now),
+        clicked_at: Set(None),
+        recovered_at: Set(None),
+        order_id: Set(None),
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+
+    let item_count: i32 = lines.iter().map(|line| line.quantity).sum();
+    let subtotal: Decimal = lines
+        .iter()
+        .map(|line| line.product.price * Decimal::from(line.quantity))
+        .sum();
+    let token = issue_token(&config.cart_reminder_secret, reminder.id);
+    let notification = Notification {
+        subject: "You left items in your cart".to_string(),
+        body: format!(
+            "Your cart \"{}\" still has {} item(s) totalling {}. Pick up where you left off: {}/{}",
+            cart.name, item_count, subtotal, config.cart_reminder_url, token
+        ),
+    };
+    channel.send(&user.email, &notification).await?;
+    Ok(true)
+}
+
+/// เปิดตะกร้าจากลิงก์ในอีเมล ตั้งเป็นตะกร้าที่ใช้งานอยู่และบันทึกว่ากดลิงก์แล้ว
+pub async fn restore_cart(
+    db: &DatabaseConnection,
+    secret: &str,
+    token: &str,
+) -> Result<carts::Model, ApiError> {
+    let reminder_id = verify_token(secret, token)?;
+    let reminder = cart_reminders::Entity::find_by_id(reminder_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Reminder not found".to_string()))?;
+
+    let cart = named_cart_service::activate_cart(db, reminder.user_id, reminder.cart_id)
+        .await
+        .map_err(|e| match e {
+            ApiError::NotFound(_) => ApiError::NotFound("This cart no longer exists".to_string()),
+            e => e,
+        })?;
+    // ลูกค้ากลับมาแล้ว นับเวลาทิ้งตะกร้าใหม่จากตอนนี้
+    cart_service::touch(db, cart.id).await?;
+
+    if reminder.clicked_at.is_none() {
+        let mut active_model: cart_reminders::ActiveModel = reminder.into();
+        active_model.clicked_at = Set(Some(Utc::now()));
+        active_model.update(db).await?;
+    }
+    Ok(cart)
+}
+
+/// ผูกคำสั่งซื้อกับอีเมลเตือนฉบับล่าสุดของตะกร้า แล้วเริ่มนับการเตือนใหม่
+pub async fn mark_recovered<C: ConnectionTrait>(
+    conn: &C,
+    cart_id: Uuid,
+    order_id: Uuid,
+) -> Result<(), ApiError> {
+    let latest = cart_reminders::Entity::find()
+        .filter(cart_reminders::Column::CartId.eq(cart_id))
+        .filter(cart_reminders::Column::RecoveredAt.is_null())
+        .order_by_desc(cart_reminders::Column::SentAt)
+        .one(conn)
+        .await?;
+    let Some(latest) = latest else {
+        return Ok(());
+    };
+
+    let mut active_model: cart_reminders::ActiveModel = latest.into();
+    active_model.recovered_at = Set(Some(Utc::now()));
+    active_model.order_id = Set(Some(order_id));
+    active_model.update(conn).await?;
+
+    carts::Entity::update_many()
+        .col_expr(carts::Column::ReminderCount, Expr::value(0))
+        .col_expr(
+            carts::Column::LastRemindedAt,
+            Expr::value(Option::<DateTime<Utc>>::None),
+        )
+        .filter(carts::Column::Id.eq(cart_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+/// ลบตะกร้าที่ไม่ได้แตะนานเกิน `retention_secs` คืนค่าจำนวนตะกร้าที่ลบ
+pub async fn purge_stale_carts(
+    db: &DatabaseConnection,
+    retention_secs: i64,
+) -> Result<u64, ApiError> {
+    let cutoff = Utc::now() - chrono::Duration::seconds(retention_secs);
+    let result = carts::Entity::delete_many()
+        .filter(carts::Column::UpdatedAt.lt(cutoff))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// รายงานการกู้คืนตะกร้าของอีเมลที่ส่งในช่วง `[from, to)` ไม่ระบุคือไม่จำกัด
+pub async fn recovery_report(
+    db: &DatabaseConnection,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Result<CartRecoveryReport, ApiError> {
+    let mut period = Condition::all();
+    if let Some(from) = from {
+        period = period.add(cart_reminders::Column::SentAt.gte(from));
+    }
+    if let Some(to) = to {
+        period = period.add(cart_reminders::Column::SentAt.lt(to));
+    }
+
+    let (reminders_sent, carts_reminded, carts_clicked, carts_recovered): (i64, i64, i64, i64) =
+        cart_reminders::Entity::find()
+            .select_only()
+            .column_as(Expr::col(cart_reminders::Column::Id).count(), "reminders_sent")
+            .column_as(Expr::cust("COUNT(DISTINCT cart_id)"), "carts_reminded")
+            .column_as(
+                Expr::cust("COUNT(DISTINCT cart_id) FILTER (WHERE clicked_at IS NOT NULL)"),
+                "carts_clicked",
+            )
+            .column_as(
+                Expr::cust("COUNT(DISTINCT cart_id) FILTER (WHERE recovered_at IS NOT NULL)"),
+                "carts_recovered",
+            )
+            .filter(period.clone())
+            .into_tuple()
+            .one(db)
+            .await?
+            .unwrap_or((0, 0, 0, 0));
+
+    let recovered_revenue: Option<Decimal> = cart_reminders::Entity::find()
+        .select_only()
+        .column_as(
+            Expr::expr(Func::sum(Expr::col((orders::Entity, orders::Column::TotalPrice)))),
+            "recovered_revenue",
+        )
+        .inner_join(orders::Entity)
+        .filter(period)
+        .filter(orders::Column::Status.ne(OrderStatus::Cancelled.to_string()))
+        .into_tuple()
+        .one(db)
+        .await?
+        .flatten();
+
+    let recovery_rate = if carts_reminded == 0 {
+        Decimal::ZERO
+    } else {
+        (Decimal::from(carts_recovered) / Decimal::from(carts_reminded)).round_dp(4)
+    };
+
+    Ok(CartRecoveryReport {
+        reminders_sent,
+        carts_reminded,
+        carts_clicked,
+        carts_recovered,
+        recovery_rate,
+        recovered_revenue: recovered_revenue.unwrap_or(Decimal::ZERO),
+    })
+}
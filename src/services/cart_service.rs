@@ -1,55 +1,277 @@
-use sea_orm::{entity::*, query::*, DatabaseConnection};
+use sea_orm::{
+    entity::*, query::*, sea_query::{Alias, Expr, OnConflict}, ConnectionTrait,
+    DatabaseConnection,
+};
+use chrono::Utc;
 use uuid::Uuid;
 use rust_decimal::Decimal;
-use crate::entity::{cart, products};
+use serde::Serialize;
+use crate::entity::sea_orm_active_enums::{ProductStatus, ProductType};
+use crate::entity::{cart, carts, products};
 use crate::error::ApiError;
+use crate::services::media_store::MediaStore;
+use crate::services::coupon_service::{self, AppliedCoupon};
+use crate::services::{bundle_service, image_service, inventory_service, product_service};
 
+/// แถวหนึ่งในตะกร้าพร้อมข้อมูลสินค้า ใช้แสดงหน้าตะกร้าได้โดยไม่ต้องดึงสินค้าซ้ำ
+#[derive(Serialize)]
+pub struct CartLineResponse {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub name: String,
+    pub sku: Option<String>,
+    pub image_url: Option<String>,
+    pub unit_price: Decimal,
+    /// ราคาตอนที่ลูกค้าใส่ตะกร้าหรือยืนยันการเปลี่ยนแปลงครั้งล่าสุด
+    pub price_snapshot: Decimal,
+    pub price_changed: bool,
+    pub status_changed: bool,
+    pub quantity: i32,
+    pub line_total: Decimal,
+    /// สั่งซื้อได้ในจำนวนนี้หรือไม่ ถ้าไม่ได้ดูเหตุผลใน `warnings`
+    pub available: bool,
+    /// จำนวนที่มีในคลังที่เปิดใช้อยู่ None คือไม่จำกัด (สินค้าดิจิทัลหรือไม่ได้ติดตามสต็อก)
+    pub available_quantity: Option<i64>,
+    pub warnings: Vec<String>,
+}
+
+/// ตะกร้าพร้อมยอดรวม ยอดภาษีเป็นค่าประมาณตาม `TAX_RATE`
+#[derive(Serialize)]
+pub struct CartResponse {
+    pub items: Vec<CartLineResponse>,
+    pub item_count: i32,
+    pub subtotal: Decimal,
+    pub discount: Decimal,
+    pub coupon_code: Option<String>,
+    /// เหตุผลที่คูปองในตะกร้าใช้ไม่ได้แล้ว
+    pub coupon_warning: Option<String>,
+    pub tax: Decimal,
+    pub grand_total: Decimal,
+    /// มีแถวที่ราคาหรือสถานะเปลี่ยนไป ต้องยืนยันก่อนสั่งซื้อ
+    pub has_changes: bool,
+}
+
+/// แถวของตะกร้าก่อนแนบข้อมูลสต็อกและรูป พร้อมราคาและสถานะที่ลูกค้าเห็นล่าสุด
+pub struct CartLine {
+    pub id: Uuid,
+    pub quantity: i32,
+    pub price_snapshot: Decimal,
+    pub status_snapshot: ProductStatus,
+    pub product: products::Model,
+}
+
+impl CartLine {
+    pub fn price_changed(&self) -> bool {
+        self.price_snapshot != self.product.price
+    }
+
+    pub fn status_changed(&self) -> bool {
+        self.status_snapshot != self.product.status
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.price_changed() || self.status_changed()
+    }
+}
+
+/// ราคาหรือสถานะสินค้าเปลี่ยนหลังใส่ตะกร้า ลูกค้าต้องยืนยันก่อนจึงจะสั่งซื้อได้
+pub fn ensure_acknowledged(lines: &[CartLine]) -> Result<(), ApiError> {
+    if lines.iter().any(CartLine::has_changes) {
+        return Err(ApiError::Conflict(
+            "Some items in the cart have changed price or availability; review and acknowledge the changes before ordering".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+
+/// ชื่อตะกร้าที่สร้างให้อัตโนมัติเมื่อผู้ใช้ยังไม่มีตะกร้าที่ใช้งานอยู่
+pub const DEFAULT_CART_NAME: &str = "Default";
+
+/// ตะกร้าที่ใช้งานอยู่ของผู้ใช้ ถ้ายังไม่มีจะสร้างตะกร้าเริ่มต้นให้
+pub async fn active_cart<C: ConnectionTrait>(
+    conn: &C,
+    user_id: Uuid,
+) -> Result<carts::Model, ApiError> {
+    if let Some(cart) = find_active_cart(conn, user_id).await? {
+        return Ok(cart);
+    }
+
+    // ถ้ามีคำขออื่นสร้างไปก่อน unique index ของตะกร้าที่ใช้งานอยู่จะทำให้แถวนี้ไม่ถูก insert
+    let now = Utc::now();
+    carts::Entity::insert(carts::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        name: Set(DEFAULT_CART_NAME.to_string()),
+        is_active: Set(true),
+        created_at: Set(now),
+        updated_at: Set(now),
+        reminder_count: Set(0),
+        last_reminded_at: Set(None),
+        coupon_id: Set(None),
+    })
+    .on_conflict(OnConflict::new().do_nothing().to_owned())
+    .exec_without_returning(conn)
+    .await?;
+
+    find_active_cart(conn, user_id)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Could not create a cart for this user".to_string()))
+}
 
- pub async fn add_to_cart(
+async fn find_active_cart<C: ConnectionTrait>(
+    conn: &C,
+    user_id: Uuid,
+) -> Result<Option<carts::Model>, ApiError> {
+    carts::Entity::find()
+        .filter(carts::Column::UserId.eq(user_id))
+        .filter(carts::Column::IsActive.eq(true))
+        .one(conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// ตะกร้าตาม id ที่ต้องเป็นของผู้ใช้รายนี้ ถ้าไม่ระบุ id จะใช้ตะกร้าที่ใช้งานอยู่
+pub async fn resolve_cart<C: ConnectionTrait>(
+    conn: &C,
+    user_id: Uuid,
+    cart_id: Option<Uuid>,
+) -> Result<carts::Model, ApiError> {
+    let Some(cart_id) = cart_id else {
+        return active_cart(conn, user_id).await;
+    };
+    carts::Entity::find_by_id(cart_id)
+        .filter(carts::Column::UserId.eq(user_id))
+        .one(conn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Cart with ID {} not found", cart_id)))
+}
+
+/// บันทึกเวลาแก้ไขล่าสุดของตะกร้า
+pub async fn touch<C: ConnectionTrait>(conn: &C, cart_id: Uuid) -> Result<(), ApiError> {
+    carts::Entity::update_many()
+        .col_expr(carts::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(carts::Column::Id.eq(cart_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+/// เพิ่มจำนวนสินค้าในตะกร้าที่ใช้งานอยู่
+pub async fn add_to_cart(
     db: &DatabaseConnection,
     user_id: Uuid,
     product_id: Uuid,
     quantity: i32,
 ) -> Result<cart::Model, ApiError> {
-    // หาสินค้าในตะกร้าที่มีอยู่
-    let existing_item = cart::Entity::find()
-        .filter(cart::Column::UserId.eq(user_id))
-        .filter(cart::Column::ProductId.eq(product_id))
-        .one(db)
-        .await
-        .map_err(ApiError::from)?;
+    if quantity < 1 {
+        return Err(ApiError::ValidationError(
+            "Quantity must be at least 1".to_string(),
+        ));
+    }
+    let product = product_service::get_product_by_id(db, product_id).await?;
 
-    match existing_item {
-        Some(item) => {
-            // กรณีมีสินค้าในตะกร้าอยู่แล้ว
-            let mut active_model: cart::ActiveModel = item.into();
-            let current_quantity = match &active_model.quantity {
-                ActiveValue::Set(value) => *value,
-                _ => 0,
-            };
-            active_model.quantity = Set(current_quantity + quantity);
-            active_model.update(db).await.map_err(ApiError::from)
-        }
-        None => {
-            // กรณีไม่มีสินค้าในตะกร้า
-            let new_cart_item = cart::ActiveModel {
-                id: Set(Uuid::new_v4()),
-                user_id: Set(user_id),
-                product_id: Set(product_id),
-                quantity: Set(quantity),
-            };
-            new_cart_item.insert(db).await.map_err(ApiError::from)
-        }
+    let txn = db.begin().await?;
+    let cart = active_cart(&txn, user_id).await?;
+    let item = add_line(&txn, &cart, &product, quantity).await?;
+    txn.commit().await?;
+    Ok(item)
+}
+
+/// เพิ่มจำนวนในตะกร้าที่ระบุ ถ้ามีแถวอยู่แล้วจะบวกเพิ่มแบบ atomic ด้วย upsert
+///
+/// ตรวจจำนวนหลัง upsert ผู้เรียกต้องใช้ transaction เพื่อให้ rollback ได้เมื่อไม่ผ่าน
+/// แถวถูกล็อกตั้งแต่ upsert จึงไม่มีการเพิ่มพร้อมกันแทรกเข้ามาได้
+pub async fn add_line<C: ConnectionTrait>(
+    conn: &C,
+    cart: &carts::Model,
+    product: &products::Model,
+    quantity: i32,
+) -> Result<cart::Model, ApiError> {
+    // ลูกค้าเห็นราคาปัจจุบันตอนกดเพิ่ม จึงอัปเดต snapshot ไปด้วย
+    let item = cart::Entity::insert(new_cart_item(cart, product, quantity))
+        .on_conflict(
+            OnConflict::columns([cart::Column::CartId, cart::Column::ProductId])
+                .value(
+                    cart::Column::Quantity,
+                    Expr::col((cart::Entity, cart::Column::Quantity))
+                        .add(Expr::col((Alias::new("excluded"), cart::Column::Quantity))),
+                )
+                .update_columns([cart::Column::PriceSnapshot, cart::Column::StatusSnapshot])
+                .to_owned(),
+        )
+        .exec_with_returning(conn)
+        .await?;
+    ensure_cart_quantity(product, item.quantity)?;
+    touch(conn, cart.id).await?;
+    Ok(item)
+}
+
+/// ตั้งจำนวนของสินค้าในตะกร้าเป็นค่าที่ระบุ จำนวน 0 คือเอาออกจากตะกร้า (คืนค่า None)
+pub async fn set_quantity(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    product_id: Uuid,
+    quantity: i32,
+) -> Result<Option<cart::Model>, ApiError> {
+    if quantity < 0 {
+        return Err(ApiError::ValidationError(
+            "Quantity must not be negative".to_string(),
+        ));
+    }
+    if quantity == 0 {
+        remove_from_cart(db, user_id, product_id).await?;
+        return Ok(None);
+    }
+
+    let product = product_service::get_product_by_id(db, product_id).await?;
+    ensure_cart_quantity(&product, quantity)?;
+
+    let cart = active_cart(db, user_id).await?;
+    let item = cart::Entity::insert(new_cart_item(&cart, &product, quantity))
+        .on_conflict(
+            OnConflict::columns([cart::Column::CartId, cart::Column::ProductId])
+                .update_column(cart::Column::Quantity)
+                .to_owned(),
+        )
+        .exec_with_returning(db)
+        .await?;
+    touch(db, cart.id).await?;
+    Ok(Some(item))
+}
+
+/// สินค้าต้องยังขายได้ และจำนวนต้องตรงตามกฎขั้นต่ำ/สูงสุด/step ของสินค้า
+pub fn ensure_cart_quantity(product: &products::Model, quantity: i32) -> Result<(), ApiError> {
+    product_service::ensure_purchasable(product, quantity)?;
+    product_service::ensure_quantity_allowed(product, quantity)
+}
+
+/// แถวใหม่ในตะกร้า พร้อม snapshot ราคาและสถานะปัจจุบันของสินค้า
+pub fn new_cart_item(
+    cart: &carts::Model,
+    product: &products::Model,
+    quantity: i32,
+) -> cart::ActiveModel {
+    cart::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        cart_id: Set(cart.id),
+        user_id: Set(cart.user_id),
+        product_id: Set(product.id),
+        quantity: Set(quantity),
+        price_snapshot: Set(product.price),
+        status_snapshot: Set(product.status),
     }
 }
+
 /// ลบสินค้าออกจากตะกร้า
 pub async fn remove_from_cart(
     db: &DatabaseConnection,
     user_id: Uuid,
     product_id: Uuid,
 ) -> Result<(), ApiError> {
+    let cart = active_cart(db, user_id).await?;
     let item_to_remove = cart::Entity::find()
-        .filter(cart::Column::UserId.eq(user_id))
+        .filter(cart::Column::CartId.eq(cart.id))
         .filter(cart::Column::ProductId.eq(product_id))
         .one(db)
         .await
@@ -58,6 +280,7 @@ pub async fn remove_from_cart(
     if let Some(item) = item_to_remove {
         let item: cart::ActiveModel = item.into();
         item.delete(db).await.map_err(ApiError::from)?;
+        touch(db, cart.id).await?;
     }
 
     Ok(())
@@ -66,57 +289,232 @@ pub async fn clear_cart(
     db: &DatabaseConnection, 
     user_id: Uuid
 ) -> Result<(), ApiError> {
-    // ลบทุกรายการในตะกร้าของผู้ใช้รายนี้
+    // ลบทุกรายการในตะกร้าที่ใช้งานอยู่ของผู้ใช้รายนี้
+    let cart = active_cart(db, user_id).await?;
     cart::Entity::delete_many()
-        .filter(cart::Column::UserId.eq(user_id))
+        .filter(cart::Column::CartId.eq(cart.id))
         .exec(db)
         .await
         .map_err(ApiError::from)?;
+    touch(db, cart.id).await?;
 
     Ok(())
 }
 
-/// ดึงรายการสินค้าในตะกร้าทั้งหมดของผู้ใช้
+/// ดึงตะกร้าของผู้ใช้พร้อมข้อมูลสินค้าในคำสั่งเดียว ไม่ระบุ `cart_id` คือตะกร้าที่ใช้งานอยู่
 pub async fn get_cart(
-    db: &DatabaseConnection, 
-    user_id: Uuid
-) -> Result<Vec<cart::Model>, ApiError> {
-    let cart_items = cart::Entity::find()
-        .filter(cart::Column::UserId.eq(user_id))
-        .all(db)
-        .await
-        .map_err(ApiError::from)?;
-
-    Ok(cart_items)
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    tax_rate: Decimal,
+    user_id: Uuid,
+    cart_id: Option<Uuid>,
+) -> Result<CartResponse, ApiError> {
+    let cart = resolve_cart(db, user_id, cart_id).await?;
+    let lines = cart_lines(db, cart.id).await?;
+    let coupon = coupon_service::cart_coupon(db, &cart, &lines).await?;
+    build_cart_response(db, store, tax_rate, lines, coupon).await
 }
 
-/// คำนวณราคารวมสินค้าในตะกร้า
+/// คำนวณราคารวมสินค้าในตะกร้า หักส่วนลดจากคูปองที่ใส่ไว้แล้ว
 pub async fn calculate_cart_total(
     db: &DatabaseConnection,
     user_id: Uuid,
 ) -> Result<Decimal, ApiError> {
-    let cart_items = cart::Entity::find()
-        .filter(cart::Column::UserId.eq(user_id))
-        .all(db)
-        .await
-        .map_err(ApiError::from)?;
+    let cart = active_cart(db, user_id).await?;
+    let lines = cart_lines(db, cart.id).await?;
+    let subtotal: Decimal = lines
+        .iter()
+        .map(|line| line.product.price * Decimal::from(line.quantity))
+        .sum();
+    let discount = coupon_service::cart_coupon(db, &cart, &lines)
+        .await?
+        .map_or(Decimal::ZERO, |coupon| coupon.discount);
+    Ok(subtotal - discount)
+}
+
+/// ยืนยันว่าลูกค้าเห็นราคาและสถานะปัจจุบันแล้ว คืนค่าจำนวนแถวที่อัปเดต
+pub async fn acknowledge_changes(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    cart_id: Option<Uuid>,
+) -> Result<usize, ApiError> {
+    let cart = resolve_cart(db, user_id, cart_id).await?;
+    let lines = cart_lines(db, cart.id).await?;
+    let txn = db.begin().await?;
+    let mut acknowledged = 0;
+    for line in lines.iter().filter(|line| line.has_changes()) {
+        cart::ActiveModel {
+            id: Set(line.id),
+            price_snapshot: Set(line.product.price),
+            status_snapshot: Set(line.product.status),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        acknowledged += 1;
+    }
+    txn.commit().await?;
+    Ok(acknowledged)
+}
+
+/// แถวในตะกร้าพร้อมสินค้า เรียงตามชื่อสินค้า
+pub async fn cart_lines<C: ConnectionTrait>(
+    conn: &C,
+    cart_id: Uuid,
+) -> Result<Vec<CartLine>, ApiError> {
+    let rows = cart::Entity::find()
+        .find_also_related(products::Entity)
+        .filter(cart::Column::CartId.eq(cart_id))
+        .order_by_asc(products::Column::Name)
+        .all(conn)
+        .await?;
+
+    rows.into_iter()
+        .map(|(item, product)| {
+            let product = product.ok_or_else(|| {
+                ApiError::NotFound(format!("Product with ID {} not found", item.product_id))
+            })?;
+            Ok(CartLine {
+                id: item.id,
+                quantity: item.quantity,
+                price_snapshot: item.price_snapshot,
+                status_snapshot: item.status_snapshot,
+                product,
+            })
+        })
+        .collect()
+}
+
+/// แนบรูป สต็อก และคำเตือนให้แถวในตะกร้า แล้วคำนวณยอดรวม
+///
+/// ดึงรูปและสต็อกของทุกแถวอย่างละคำสั่ง ใช้ร่วมกันทั้งตะกร้าผู้ใช้และตะกร้า guest
+/// ตะกร้า guest ใส่คูปองไม่ได้ จึงส่ง `coupon` เป็น None
+pub async fn build_cart_response(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    tax_rate: Decimal,
+    lines: Vec<CartLine>,
+    coupon: Option<AppliedCoupon>,
+) -> Result<CartResponse, ApiError> {
+    let product_ids: Vec<Uuid> = lines.iter().map(|line| line.product.id).collect();
+    let images = image_service::images_for_products(db, &product_ids).await?;
+    let stock = inventory_service::stock_totals(db, &product_ids).await?;
+    let products: Vec<products::Model> =
+        lines.iter().map(|line| line.product.clone()).collect();
+    let bundles = bundle_service::bundles_for_products(db, &products).await?;
 
-    let mut total_price = Decimal::new(0, 0);
-
-    for item in cart_items {
-        if let Some(product) = products::Entity::find_by_id(item.product_id)
-            .one(db)
-            .await
-            .map_err(ApiError::from)?
-        {
-            total_price += product.price * Decimal::from(item.quantity);
-        } else {
-            return Err(ApiError::NotFound(format!(
-                "Product with ID {} not found",
-                item.product_id
-            )));
+    let mut item_count = 0;
+    let mut subtotal = Decimal::ZERO;
+    let has_changes = lines.iter().any(CartLine::has_changes);
+    let items: Vec<CartLineResponse> = lines
+        .into_iter()
+        .map(|line| {
+            let price_changed = line.price_changed();
+            let status_changed = line.status_changed();
+            let CartLine {
+                id,
+                quantity,
+                price_snapshot,
+                product,
+                ..
+            } = line;
+            let available_quantity = match product.product_type {
+                ProductType::Physical => stock.get(&product.id).copied(),
+                ProductType::Bundle => bundles
+                    .get(&product.id)
+                    .map_or(Some(0), |bundle| bundle.available_quantity),
+                ProductType::Digital => None,
+            };
+            let mut warnings = line_warnings(&product, quantity, available_quantity);
+            // ราคาเปลี่ยนยังสั่งซื้อได้หลังยืนยัน ไม่นับว่าสินค้าไม่พร้อมขาย
+            let available = warnings.is_empty();
+            if price_changed {
+                warnings.push(format!(
+                    "Price changed from {} to {}",
+                    price_snapshot, product.price
+                ));
+            }
+            let image_url = images.get(&product.id).and_then(|images| {
+                images
+                    .iter()
+                    .find(|image| image.is_primary)
+                    .or_else(|| images.first())
+                    .map(|image| store.url(&image.thumbnail_key))
+            });
+
+            let line_total = product.price * Decimal::from(quantity);
+            item_count += quantity;
+            subtotal += line_total;
+            CartLineResponse {
+                id,
+                product_id: product.id,
+                name: product.name,
+                sku: product.sku,
+                image_url,
+                unit_price: product.price,
+                price_snapshot,
+                price_changed,
+                status_changed,
+                quantity,
+                line_total,
+                available,
+                available_quantity,
+                warnings,
+            }
+        })
+        .collect();
+
+    let (discount, coupon_code, coupon_warning) = match coupon {
+        Some(coupon) => (coupon.discount, Some(coupon.code), coupon.warning),
+        None => (Decimal::ZERO, None, None),
+    };
+    let tax = ((subtotal - discount) * tax_rate).round_dp(2);
+    Ok(CartResponse {
+        items,
+        item_count,
+        subtotal,
+        discount,
+        coupon_code,
+        coupon_warning,
+        tax,
+        grand_total: subtotal - discount + tax,
+        has_changes,
+    })
+}
+
+/// เหตุผลที่แถวนี้สั่งซื้อไม่ได้ ตรงกับเงื่อนไขที่ตรวจตอนสร้างคำสั่งซื้อ
+fn line_warnings(
+    product: &products::Model,
+    quantity: i32,
+    available_quantity: Option<i64>,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if product.deleted_at.is_some() {
+        warnings.push("Product is no longer sold".to_string());
+    } else if product.status != ProductStatus::Available {
+        warnings.push("Product is not available".to_string());
+    }
+    if product.is_one_of_a_kind && quantity > 1 {
+        warnings.push("Product is one of a kind and can only be bought once".to_string());
+    }
+    if product_service::ensure_quantity_allowed(product, quantity).is_err() {
+        warnings.push(quantity_rule_warning(product));
+    }
+    if let Some(available_quantity) = available_quantity {
+        if available_quantity < i64::from(quantity) {
+            warnings.push(format!("Only {} left in stock", available_quantity.max(0)));
         }
     }
+    warnings
+}
 
-    Ok(total_price)
+fn quantity_rule_warning(product: &products::Model) -> String {
+    let mut warning = format!("Quantity must be at least {}", product.min_quantity);
+    if let Some(max_quantity) = product.max_quantity {
+        warning.push_str(&format!(" and at most {}", max_quantity));
+    }
+    if product.quantity_step > 1 {
+        warning.push_str(&format!(", in steps of {}", product.quantity_step));
+    }
+    warning
 }
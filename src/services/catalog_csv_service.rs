@@ -0,0 +1,301 @@
+use crate::entity::products;
+use crate::entity::sea_orm_active_enums::{PriceChangeReason, ProductStatus, ProductType};
+use crate::error::ApiError;
+use crate::services::notification::NotificationChannel;
+use crate::services::{alert_service, price_service, product_service};
+use rust_decimal::Decimal;
+use sea_orm::{entity::*, query::*, DatabaseConnection, DatabaseTransaction, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// หนึ่งแถวใน CSV ใช้ฟิลด์เดียวกับ `CreateProductRequest` บวก id สำหรับอ้างอิงสินค้าเดิม
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProductCsvRow {
+    pub id: Option<Uuid>,
+    pub sku: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: String,
+    pub status: Option<ProductStatus>,
+    pub is_one_of_a_kind: Option<bool>,
+    /// ว่างไว้คือคงค่าเดิม สินค้าใหม่เป็น physical
+    pub product_type: Option<ProductType>,
+    /// ว่างไว้คือคงค่าเดิม สินค้าใหม่เป็น 1
+    pub min_quantity: Option<i32>,
+    /// ว่างไว้คือไม่จำกัดจำนวนสูงสุด
+    pub max_quantity: Option<i32>,
+    pub quantity_step: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    pub line: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// แถวที่ผ่านการตรวจแล้ว พร้อมสินค้าเดิมที่จะถูกอัปเดต (ถ้ามี)
+struct ValidRow {
+    row: ProductCsvRow,
+    price: Decimal,
+    existing: Option<products::Model>,
+}
+
+/// นำเข้าสินค้าจาก CSV ทั้งไฟล์ใน transaction เดียว
+///
+/// ถ้ามีแถวใดผิดจะไม่บันทึกอะไรเลย และถ้าเป็น dry run จะ rollback เสมอ
+/// หลังบันทึกจะแจ้งเตือนสินค้าเดิมที่ราคาลดหรือกลับมาขายได้
+pub async fn import_products(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    data: &[u8],
+    dry_run: bool,
+) -> Result<ImportReport, ApiError> {
+    let mut reader = csv::Reader::from_reader(data);
+    let headers = reader
+        .headers()
+        .map_err(|e| ApiError::ValidationError(format!("Invalid CSV header: {}", e)))?
+        .clone();
+
+    let txn = db.begin().await?;
+    let mut errors = Vec::new();
+    let mut valid_rows = Vec::new();
+    let mut seen_ids = HashSet::new();
+    let mut seen_skus = HashSet::new();
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map_or(0, |position| position.line());
+                errors.push(ImportRowError { line, message: e.to_string() });
+                continue;
+            }
+        };
+        let line = record.position().map_or(0, |position| position.line());
+
+        let row: ProductCsvRow = match record.deserialize(Some(&headers)) {
+            Ok(row) => row,
+            Err(e) => {
+                errors.push(ImportRowError { line, message: e.to_string() });
+                continue;
+            }
+        };
+
+        if let Some(id) = row.id {
+            if !seen_ids.insert(id) {
+                errors.push(ImportRowError { line, message: format!("Duplicate id {}", id) });
+                continue;
+            }
+        }
+        if let Some(sku) = &row.sku {
+            if !seen_skus.insert(sku.clone()) {
+                errors.push(ImportRowError { line, message: format!("Duplicate sku {}", sku) });
+                continue;
+            }
+        }
+
+        match validate_row(&txn, row).await? {
+            Ok(valid_row) => valid_rows.push(valid_row),
+            Err(message) => errors.push(ImportRowError { line, message }),
+        }
+    }
+
+    let mut report = ImportReport { dry_run, created: 0, updated: 0, errors };
+    if !report.errors.is_empty() {
+        txn.rollback().await?;
+        return Ok(report);
+    }
+
+    let mut updated_before = Vec::new();
+    for valid_row in valid_rows {
+        match &valid_row.existing {
+            Some(product) => {
+                report.updated += 1;
+                updated_before.push(product.clone());
+            }
+            None => report.created += 1,
+        }
+        save_row(&txn, valid_row).await?;
+    }
+
+    if dry_run {
+        txn.rollback().await?;
+    } else {
+        txn.commit().await?;
+        alert_service::notify_products_changed(db, channel, &updated_before).await;
+    }
+    Ok(report)
+}
+
+/// ส่งออกสินค้าในแคตตาล็อกปัจจุบัน (ไม่รวมที่ถูกเก็บ) เป็น CSV
+pub async fn export_products(db: &DatabaseConnection) -> Result<Vec<u8>, ApiError> {
+    let products = products::Entity::find()
+        .filter(products::Column::DeletedAt.is_null())
+        .order_by_asc(products::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for product in products {
+        writer
+            .serialize(ProductCsvRow {
+                id: Some(product.id),
+                sku: product.sku,
+                name: product.name,
+                description: product.description,
+                price: product.price.to_string(),
+                status: Some(product.status),
+                is_one_of_a_kind: Some(product.is_one_of_a_kind),
+                product_type: Some(product.product_type),
+                min_quantity: Some(product.min_quantity),
+                max_quantity: product.max_quantity,
+                quantity_step: Some(product.quantity_step),
+            })
+            .map_err(|_| ApiError::InternalServerError)?;
+    }
+
+    writer.into_inner().map_err(|_| ApiError::InternalServerError)
+}
+
+/// ตรวจค่าในแถวและหาสินค้าเดิมด้วย id ก่อน แล้วจึงใช้ sku
+///
+/// ผลลัพธ์ชั้นนอกคือ error ของฐานข้อมูล ชั้นในคือข้อความ error ของแถวนั้น
+async fn validate_row(
+    txn: &DatabaseTransaction,
+    row: ProductCsvRow,
+) -> Result<Result<ValidRow, String>, ApiError> {
+    if row.name.trim().is_empty() {
+        return Ok(Err("name must not be empty".to_string()));
+    }
+
+    let price = match Decimal::from_str(row.price.trim()) {
+        Ok(price) if price >= Decimal::ZERO => price,
+        Ok(_) => return Ok(Err("price must not be negative".to_string())),
+        Err(_) => return Ok(Err(format!("invalid price: {}", row.price))),
+    };
+
+    let existing = match (row.id, &row.sku) {
+        (Some(id), _) => match products::Entity::find_by_id(id).one(txn).await? {
+            Some(product) => Some(product),
+            None => return Ok(Err(format!("Product with ID {} not found", id))),
+        },
+        (None, Some(sku)) => {
+            products::Entity::find()
+                .filter(products::Column::Sku.eq(sku.clone()))
+                .one(txn)
+                .await?
+        }
+        (None, None) => None,
+    };
+
+    if let Some(product) = &existing {
+        if product.deleted_at.is_some() {
+            return Ok(Err(format!("Product with ID {} has been archived", product.id)));
+        }
+    }
+
+    // sku ซ้ำกับสินค้าอื่นต้องเป็น error ของแถว ไม่ใช่ไปชน unique index ตอนบันทึก
+    if let (Some(id), Some(sku)) = (row.id, &row.sku) {
+        let owner = products::Entity::find()
+            .filter(products::Column::Sku.eq(sku.clone()))
+            .filter(products::Column::Id.ne(id))
+            .one(txn)
+            .await?;
+        if let Some(owner) = owner {
+            return Ok(Err(format!("sku {} belongs to product {}", sku, owner.id)));
+        }
+    }
+
+    let min_quantity = row
+        .min_quantity
+        .or(existing.as_ref().map(|product| product.min_quantity))
+        .unwrap_or(1);
+    let quantity_step = row
+        .quantity_step
+        .or(existing.as_ref().map(|product| product.quantity_step))
+        .unwrap_or(1);
+    if let Err(e) =
+        product_service::validate_quantity_rules(min_quantity, row.max_quantity, quantity_step)
+    {
+        return Ok(Err(match e {
+            ApiError::ValidationError(message) => message,
+            e => return Err(e),
+        }));
+    }
+
+    Ok(Ok(ValidRow { row, price, existing }))
+}
+
+async fn save_row(txn: &DatabaseTransaction, valid_row: ValidRow) -> Result<(), ApiError> {
+    let ValidRow { row, price, existing } = valid_row;
+
+    match existing {
+        Some(product) => {
+            price_service::record_price_change(
+                txn,
+                product.id,
+                product.price,
+                price,
+                PriceChangeReason::Import,
+                None,
+            )
+            .await?;
+
+            let mut active_model: products::ActiveModel = product.into();
+            active_model.name = Set(row.name);
+            active_model.description = Set(row.description);
+            active_model.price = Set(price);
+            if let Some(status) = row.status {
+                active_model.status = Set(status);
+            }
+            if let Some(is_one_of_a_kind) = row.is_one_of_a_kind {
+                active_model.is_one_of_a_kind = Set(is_one_of_a_kind);
+            }
+            if let Some(product_type) = row.product_type {
+                active_model.product_type = Set(product_type);
+            }
+            if let Some(min_quantity) = row.min_quantity {
+                active_model.min_quantity = Set(min_quantity);
+            }
+            active_model.max_quantity = Set(row.max_quantity);
+            if let Some(quantity_step) = row.quantity_step {
+                active_model.quantity_step = Set(quantity_step);
+            }
+            if row.sku.is_some() {
+                active_model.sku = Set(row.sku);
+            }
+            active_model.update(txn).await?;
+        }
+        None => {
+            let new_product = products::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                name: Set(row.name),
+                description: Set(row.description),
+                price: Set(price),
+                status: Set(row.status.unwrap_or(ProductStatus::Available)),
+                created_at: Set(chrono::Utc::now()),
+                is_one_of_a_kind: Set(row.is_one_of_a_kind.unwrap_or(false)),
+                deleted_at: Set(None),
+                sku: Set(row.sku),
+                product_type: Set(row.product_type.unwrap_or_default()),
+                min_quantity: Set(row.min_quantity.unwrap_or(1)),
+                max_quantity: Set(row.max_quantity),
+                quantity_step: Set(row.quantity_step.unwrap_or(1)),
+                ..Default::default()
+            };
+            new_product.insert(txn).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::entity::{categories, products};
+use crate::error::ApiError;
+use crate::services::product_service;
+use sea_orm::{entity::*, query::*, DatabaseConnection};
+use uuid::Uuid;
+
+pub async fn list_categories(db: &DatabaseConnection) -> Result<Vec<categories::Model>, ApiError> {
+    categories::Entity::find()
+        .order_by_asc(categories::Column::Slug)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+pub async fn get_category_by_id(
+    db: &DatabaseConnection,
+    category_id: Uuid,
+) -> Result<categories::Model, ApiError> {
+    categories::Entity::find_by_id(category_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Category with ID {} not found", category_id)))
+}
+
+pub async fn create_category(
+    db: &DatabaseConnection,
+    slug: String,
+    name: String,
+    description: Option<String>,
+) -> Result<categories::Model, ApiError> {
+    let existing = categories::Entity::find()
+        .filter(categories::Column::Slug.eq(slug.clone()))
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        return Err(ApiError::Conflict(format!("Category with slug {} already exists", slug)));
+    }
+
+    let category = categories::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        slug: Set(slug),
+        name: Set(name),
+        description: Set(description),
+        created_at: Set(chrono::Utc::now()),
+    };
+
+    category.insert(db).await.map_err(ApiError::from)
+}
+
+/// ย้ายสินค้าเข้าหมวดหมู่ หรือเอาออกจากหมวดหมู่เมื่อส่ง None
+pub async fn set_product_category(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    category_id: Option<Uuid>,
+) -> Result<products::Model, ApiError> {
+    let product = product_service::get_product_by_id(db, product_id).await?;
+    if let Some(category_id) = category_id {
+        get_category_by_id(db, category_id).await?;
+    }
+
+    let mut active_model: products::ActiveModel = product.into();
+    active_model.category_id = Set(category_id);
+    active_model.update(db).await.map_err(ApiError::from)
+}
@@ -0,0 +1,558 @@
+use crate::entity::sea_orm_active_enums::DiscountType;
+use crate::entity::{carts, coupon_categories, coupon_products, coupon_redemptions, coupons, orders};
+use crate::error::ApiError;
+use crate::services::cart_service::{self, CartLine};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    entity::*, query::*, sea_query::Expr, ConnectionTrait, DatabaseConnection, PaginatorTrait,
+};
+use serde::Serialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// คูปองพร้อมรายการสินค้าและหมวดหมู่ที่ใช้ได้ ว่างทั้งคู่คือใช้ได้ทุกสินค้า
+#[derive(Serialize)]
+pub struct CouponResponse {
+    #[serde(flatten)]
+    pub coupon: coupons::Model,
+    pub product_ids: Vec<Uuid>,
+    pub category_ids: Vec<Uuid>,
+}
+
+/// คูปองที่ใส่ไว้ในตะกร้าและส่วนลดที่ได้ตอนนี้
+#[derive(Serialize)]
+pub struct AppliedCoupon {
+    pub code: String,
+    pub discount: Decimal,
+    /// เหตุผลที่คูปองใช้ไม่ได้แล้ว (เช่น หมดอายุ หรือยอดไม่ถึงขั้นต่ำ) ส่วนลดจะเป็น 0
+    pub warning: Option<String>,
+}
+
+pub struct NewCoupon {
+    pub code: String,
+    pub description: Option<String>,
+    pub discount_type: DiscountType,
+    pub amount: Decimal,
+    pub min_subtotal: Option<Decimal>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub usage_limit: Option<i32>,
+    pub per_user_limit: Option<i32>,
+    pub product_ids: Vec<Uuid>,
+    pub category_ids: Vec<Uuid>,
+}
+
+/// ค่าที่จะแก้ในคูปอง ฟิลด์ที่เป็น None จะคงค่าเดิมไว้ `Some(None)` คือล้างค่า
+#[derive(Default)]
+pub struct CouponUpdate {
+    pub description: Option<Option<String>>,
+    pub amount: Option<Decimal>,
+    pub min_subtotal: Option<Option<Decimal>>,
+    pub starts_at: Option<Option<DateTime<Utc>>>,
+    pub ends_at: Option<Option<DateTime<Utc>>>,
+    pub usage_limit: Option<Option<i32>>,
+    pub per_user_limit: Option<Option<i32>>,
+    pub is_active: Option<bool>,
+    /// แทนที่รายการเดิมทั้งหมด
+    pub product_ids: Option<Vec<Uuid>>,
+    pub category_ids: Option<Vec<Uuid>>,
+}
+
+pub async fn list_coupons(db: &DatabaseConnection) -> Result<Vec<CouponResponse>, ApiError> {
+    let coupons = coupons::Entity::find()
+        .order_by_desc(coupons::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let mut responses = Vec::with_capacity(coupons.len());
+    for coupon in coupons {
+        responses.push(coupon_response(db, coupon).await?);
+    }
+    Ok(responses)
+}
+
+/// สร้างคูปอง โค้ดเก็บเป็นตัวพิมพ์ใหญ่และห้ามซ้ำ
+pub async fn create_coupon(
+    db: &DatabaseConnection,
+    coupon: NewCoupon,
+) -> Result<CouponResponse, ApiError> {
+    let code = normalize_code(&coupon.code);
+    ensure_rules(
+        coupon.discount_type,
+        coupon.amount,
+        coupon.starts_at,
+        coupon.ends_at,
+        coupon.usage_limit,
+        coupon.per_user_limit,
+    )?;
+    if find_by_code(db, &code).await?.is_some() {
+        return Err(ApiError::Conflict(format!("Coupon code {} already exists", code)));
+    }
+
+    let txn = db.begin().await?;
+    let created = coupons::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        code: Set(code),
+        description: Set(coupon.description),
+        discount_type: Set(coupon.discount_type),
+        amount: Set(coupon.amount),
+        min_subtotal: Set(coupon.min_subtotal),
+        starts_at: Set(coupon.starts_at),
+        ends_at: Set(coupon.ends_at),
+        usage_limit: Set(coupon.usage_limit),
+        per_user_limit: Set(coupon.per_user_limit),
+        redemption_count: Set(0),
+        is_active: Set(true),
+        created_at: Set(Utc::now()),
+    }
+    .insert(&txn)
+    .await?;
+    set_products(&txn, created.id, &coupon.product_ids).await?;
+    set_categories(&txn, created.id, &coupon.category_ids).await?;
+    txn.commit().await?;
+
+    coupon_response(db, created).await
+}
+
+/// แก้คูปอง ประเภทส่วนลดและโค้ดแก้ไม่ได้เพราะผูกกับคำสั่งซื้อที่ใช้ไปแล้ว
+pub async fn update_coupon(
+    db: &DatabaseConnection,
+    coupon_id: Uuid,
+    changes: CouponUpdate,
+) -> Result<CouponResponse, ApiError> {
+    let coupon = coupons::Entity::find_by_id(coupon_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Coupon with ID {} not found", coupon_id)))?;
+    ensure_rules(
+        coupon.discount_type,
+        changes.amount.unwrap_or(coupon.amount),
+        changes.starts_at.unwrap_or(coupon.starts_at),
+        changes.ends_at.unwrap_or(coupon.ends_at),
+        changes.usage_limit.unwrap_or(coupon.usage_limit),
+        changes.per_user_limit.unwrap_or(coupon.per_user_limit),
+    )?;
+
+    let txn = db.begin().await?;
+    let mut active_model: coupons::ActiveModel = coupon.into();
+    if let Some(description) = changes.description {
+        active_model.description = Set(description);
+    }
+    if let Some(amount) = changes.amount {
+        active_model.amount = Set(amount);
+    }
+    if let Some(min_subtotal) = changes.min_subtotal {
+        active_model.min_subtotal = Set(min_subtotal);
+    }
+    if let Some(starts_at) = changes.starts_at {
+        active_model.starts_at = Set(starts_at);
+    }
+    if let Some(ends_at) = changes.ends_at {
+        active_model.ends_at = Set(ends_at);
+    }
+    if let Some(usage_limit) = changes.usage_limit {
+        active_model.usage_limit = Set(usage_limit);
+    }
+    if let Some(per_user_limit) = changes.per_user_limit {
+        active_model.per_user_limit = Set(per_user_limit);
+    }
+    if let Some(is_active) = changes.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    let coupon = active_model.update(&txn).await?;
+    if let Some(product_ids) = changes.product_ids {
+        set_products(&txn, coupon.id, &product_ids).await?;
+    }
+    if let Some(category_ids) = changes.category_ids {
+        set_categories(&txn, coupon.id, &category_ids).await?;
+    }
+    txn.commit().await?;
+
+    coupon_response(db, coupon).await
+}
+
+/// ใส่คูปองในตะกร้าที่ใช้งานอยู่ ตรวจเงื่อนไขกับสินค้าในตะกร้าตอนนี้ก่อน
+///
+/// ใส่ได้ทีละใบ ใส่ใบใหม่จะแทนใบเดิม
+pub async fn apply_to_cart(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    code: &str,
+) -> Result<AppliedCoupon, ApiError> {
+    let code = normalize_code(code);
+    let coupon = find_by_code(db, &code)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Coupon code {} not found", code)))?;
+
+    let cart = cart_service::active_cart(db, user_id).await?;
+    let lines = cart_service::cart_lines(db, cart.id).await?;
+    let discount = evaluate(db, &coupon, user_id, &lines).await?;
+
+    set_cart_coupon(db, cart.id, Some(coupon.id)).await?;
+    cart_service::touch(db, cart.id).await?;
+    Ok(AppliedCoupon {
+        code: coupon.code,
+        discount,
+        warning: None,
+    })
+}
+
+/// เอาคูปองออกจากตะกร้าที่ใช้งานอยู่
+pub async fn remove_from_cart(db: &DatabaseConnection, user_id: Uuid) -> Result<(), ApiError> {
+    let cart = cart_service::active_cart(db, user_id).await?;
+    set_cart_coupon(db, cart.id, None).await?;
+    cart_service::touch(db, cart.id).await
+}
+
+/// ส่วนลดของคูปองที่ใส่ไว้ในตะกร้า ถ้าคูปองใช้ไม่ได้แล้วจะได้ส่วนลด 0 พร้อมเหตุผล
+pub async fn cart_coupon<C: ConnectionTrait>(
+    conn: &C,
+    cart: &carts::Model,
+    lines: &[CartLine],
+) -> Result<Option<AppliedCoupon>, ApiError> {
+    let Some(coupon_id) = cart.coupon_id else {
+        return Ok(None);
+    };
+    let Some(coupon) = coupons::Entity::find_by_id(coupon_id).one(conn).await? else {
+        return Ok(None);
+    };
+
+    let (discount, warning) = match evaluate(conn, &coupon, cart.user_id, lines).await {
+        Ok(discount) => (discount, None),
+        Err(ApiError::ValidationError(reason)) => (Decimal::ZERO, Some(reason)),
+        Err(e) => return Err(e),
+    };
+    Ok(Some(AppliedCoupon {
+        code: coupon.code,
+        discount,
+        warning,
+    }))
+}
+
+/// ส่วนลดที่คูปองให้กับแถวในตะกร้านี้ ถ้าใช้ไม่ได้จะได้ ValidationError พร้อมเหตุผล
+///
+/// ส่วนลดคิดจากสินค้าที่ตรงเงื่อนไขเท่านั้น แต่ยอดขั้นต่ำเทียบกับยอดรวมทั้งตะกร้า
+pub async fn evaluate<C: ConnectionTrait>(
+    conn: &C,
+    coupon: &coupons::Model,
+    user_id: Uuid,
+    lines: &[CartLine],
+) -> Result<Decimal, ApiError> {
+    let invalid = |reason: &str| Err(ApiError::ValidationError(reason.to_string()));
+    let now = Utc::now();
+    if !coupon.is_active {
+        return invalid("Coupon is no longer active");
+    }
+    if coupon.starts_at.is_some_and(|starts_at| now < starts_at) {
+        return invalid("Coupon is not valid yet");
+    }
+    if coupon.ends_at.is_some_and(|ends_at| now >= ends_at) {
+        return invalid("Coupon has expired");
+    }
+    if coupon
+        .usage_limit
+        .is_some_and(|limit| coupon.redemption_count >= limit)
+    {
+        return invalid("Coupon has reached its usage limit");
+    }
+    if let Some(per_user_limit) = coupon.per_user_limit {
+        let used = coupon_redemptions::Entity::find()
+            .filter(coupon_redemptions::Column::CouponId.eq(coupon.id))
+            .filter(coupon_redemptions::Column::UserId.eq(user_id))
+            .count(conn)
+            .await?;
+        if used >= per_user_limit.max(0) as u64 {
+            return invalid("You have already used this coupon the maximum number of times");
+        }
+    }
+
+    let subtotal: Decimal = lines.iter().map(line_total).sum();
+    if let Some(min_subtotal) = coupon.min_subtotal {
+        if subtotal < min_subtotal {
+            return Err(ApiError::ValidationError(format!(
+                "Coupon requires a subtotal of at least {}",
+                min_subtotal
+            )));
+        }
+    }
+
+    let (product_ids, category_ids) = restrictions(conn, coupon.id).await?;
+    let eligible: Decimal = if product_ids.is_empty() && category_ids.is_empty() {
+        subtotal
+    } else {
+        lines
+            .iter()
+            .filter(|line| {
+                product_ids.contains(&line.product.id)
+                    || line
+                        .product
+                        .category_id
+                        .is_some_and(|category_id| category_ids.contains(&category_id))
+            })
+            .map(line_total)
+            .sum()
+    };
+    if eligible.is_zero() {
+        return invalid("Coupon does not apply to any item in the cart");
+    }
+
+    // ส่วนลดไม่เกินยอดของสินค้าที่ตรงเงื่อนไข
+    let discount = match coupon.discount_type {
+        DiscountType::Percentage => (eligible * coupon.amount / Decimal::ONE_HUNDRED).round_dp(2),
+        DiscountType::FixedAmount => coupon.amount.min(eligible),
+    };
+    Ok(discount)
+}
+
+/// ใช้คูปองของตะกร้ากับคำสั่งซื้อที่เพิ่งสร้าง ต้องเรียกใน transaction เดียวกับการสั่งซื้อ
+///
+/// ล็อกแถวคูปองก่อนตรวจเงื่อนไข การสั่งซื้อพร้อมกันด้วยคูปองเดียวกันจึงนับสิทธิ์ต่อกันทีละคำสั่ง
+/// ส่วนลดหักจากยอดคำสั่งซื้อ ยอดของร้านค้าไม่เปลี่ยน (แพลตฟอร์มเป็นผู้รับส่วนลด)
+pub async fn redeem<C: ConnectionTrait>(
+    txn: &C,
+    coupon_id: Uuid,
+    user_id: Uuid,
+    lines: &[CartLine],
+    order: orders::Model,
+) -> Result<orders::Model, ApiError> {
+    let coupon = coupons::Entity::find_by_id(coupon_id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Coupon with ID {} not found", coupon_id)))?;
+    let discount = evaluate(txn, &coupon, user_id, lines)
+        .await
+        .map_err(|e| match e {
+            ApiError::ValidationError(reason) => ApiError::Conflict(format!(
+                "Coupon {} can no longer be applied: {}",
+                coupon.code, reason
+            )),
+            e => e,
+        })?;
+
+    let claimed = coupons::Entity::update_many()
+        .col_expr(
+            coupons::Column::RedemptionCount,
+            Expr::col(coupons::Column::RedemptionCount).add(1),
+        )
+        .filter(coupons::Column::Id.eq(coupon.id))
+        .filter(
+            Condition::any()
+                .add(coupons::Column::UsageLimit.is_null())
+                .add(
+                    Expr::col(coupons::Column::RedemptionCount)
+                        .lt(Expr::col(coupons::Column::UsageLimit)),
+                ),
+        )
+        .exec(txn)
+        .await?;
+    if claimed.rows_affected == 0 {
+        return Err(ApiError::Conflict(format!(
+            "Coupon {} has reached its usage limit",
+            coupon.code
+        )));
+    }
+
+    coupon_redemptions::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        coupon_id: Set(coupon.id),
+        user_id: Set(user_id),
+        order_id: Set(order.id),
+        discount: Set(discount),
+        redeemed_at: Set(Utc::now()),
+    }
+    .insert(txn)
+    .await?;
+
+    let total_price = order.total_price - discount;
+    let mut active_order: orders::ActiveModel = order.into();
+    active_order.total_price = Set(total_price);
+    active_order.discount_total = Set(discount);
+    active_order.coupon_id = Set(Some(coupon.id));
+    let order = active_order.update(txn).await?;
+
+    // คูปองถูกใช้แล้ว ตะกร้าที่ใช้ต่อต้องใส่คูปองใหม่เอง
+    carts::Entity::update_many()
+        .col_expr(carts::Column::CouponId, Expr::value(Option::<Uuid>::None))
+        .filter(carts::Column::CouponId.eq(coupon.id))
+        .filter(carts::Column::UserId.eq(user_id))
+        .exec(txn)
+        .await?;
+    Ok(order)
+}
+
+/// คืนสิทธิ์การใช้คูปองของคำสั่งซื้อที่ถูกยกเลิก ส่วนลดในคำสั่งซื้อยังบันทึกไว้เหมือนเดิม
+pub async fn release<C: ConnectionTrait>(txn: &C, order_id: Uuid) -> Result<(), ApiError> {
+    let Some(redemption) = coupon_redemptions::Entity::find()
+        .filter(coupon_redemptions::Column::OrderId.eq(order_id))
+        .one(txn)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    coupons::Entity::update_many()
+        .col_expr(
+            coupons::Column::RedemptionCount,
+            Expr::col(coupons::Column::RedemptionCount).sub(1),
+        )
+        .filter(coupons::Column::Id.eq(redemption.coupon_id))
+        .filter(coupons::Column::RedemptionCount.gt(0))
+        .exec(txn)
+        .await?;
+    coupon_redemptions::Entity::delete_by_id(redemption.id)
+        .exec(txn)
+        .await?;
+    Ok(())
+}
+
+fn line_total(line: &CartLine) -> Decimal {
+    line.product.price * Decimal::from(line.quantity)
+}
+
+fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+fn ensure_rules(
+    discount_type: DiscountType,
+    amount: Decimal,
+    starts_at: Option<DateTime<Utc>>,
+    ends_at: Option<DateTime<Utc>>,
+    usage_limit: Option<i32>,
+    per_user_limit: Option<i32>,
+) -> Result<(), ApiError> {
+    if amount <= Decimal::ZERO {
+        return Err(ApiError::ValidationError(
+            "amount: must be greater than 0".to_string(),
+        ));
+    }
+    if discount_type == DiscountType::Percentage && amount > Decimal::ONE_HUNDRED {
+        return Err(ApiError::ValidationError(
+            "amount: a percentage discount must not exceed 100".to_string(),
+        ));
+    }
+    if let (Some(starts_at), Some(ends_at)) = (starts_at, ends_at) {
+        if ends_at <= starts_at {
+            return Err(ApiError::ValidationError(
+                "ends_at: must be after starts_at".to_string(),
+            ));
+        }
+    }
+    if usage_limit.is_some_and(|limit| limit < 1) || per_user_limit.is_some_and(|limit| limit < 1)
+    {
+        return Err(ApiError::ValidationError(
+            "Usage limits must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn find_by_code<C: ConnectionTrait>(
+    conn: &C,
+    code: &str,
+) -> Result<Option<coupons::Model>, ApiError> {
+    coupons::Entity::find()
+        .filter(coupons::Column::Code.eq(code))
+        .one(conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+async fn set_cart_coupon<C: ConnectionTrait>(
+    conn: &C,
+    cart_id: Uuid,
+    coupon_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    carts::Entity::update_many()
+        .col_expr(carts::Column::CouponId, Expr::value(coupon_id))
+        .filter(carts::Column::Id.eq(cart_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+async fn restrictions<C: ConnectionTrait>(
+    conn: &C,
+    coupon_id: Uuid,
+) -> Result<(HashSet<Uuid>, HashSet<Uuid>), ApiError> {
+    let product_ids: Vec<Uuid> = coupon_products::Entity::find()
+        .select_only()
+        .column(coupon_products::Column::ProductId)
+        .filter(coupon_products::Column::CouponId.eq(coupon_id))
+        .into_tuple()
+        .all(conn)
+        .await?;
+    let category_ids: Vec<Uuid> = coupon_categories::Entity::find()
+        .select_only()
+        .column(coupon_categories::Column::CategoryId)
+        .filter(coupon_categories::Column::CouponId.eq(coupon_id))
+        .into_tuple()
+        .all(conn)
+        .await?;
+    Ok((
+        product_ids.into_iter().collect(),
+        category_ids.into_iter().collect(),
+    ))
+}
+
+async fn set_products<C: ConnectionTrait>(
+    conn: &C,
+    coupon_id: Uuid,
+    product_ids: &[Uuid],
+) -> Result<(), ApiError> {
+    coupon_products::Entity::delete_many()
+        .filter(coupon_products::Column::CouponId.eq(coupon_id))
+        .exec(conn)
+        .await?;
+    let product_ids: HashSet<Uuid> = product_ids.iter().copied().collect();
+    if product_ids.is_empty() {
+        return Ok(());
+    }
+    coupon_products::Entity::insert_many(product_ids.into_iter().map(|product_id| {
+        coupon_products::ActiveModel {
+            coupon_id: Set(coupon_id),
+            product_id: Set(product_id),
+        }
+    }))
+    .exec(conn)
+    .await?;
+    Ok(())
+}
+
+async fn set_categories<C: ConnectionTrait>(
+    conn: &C,
+    coupon_id: Uuid,
+    category_ids: &[Uuid],
+) -> Result<(), ApiError> {
+    coupon_categories::Entity::delete_many()
+        .filter(coupon_categories::Column::CouponId.eq(coupon_id))
+        .exec(conn)
+        .await?;
+    let category_ids: HashSet<Uuid> = category_ids.iter().copied().collect();
+    if category_ids.is_empty() {
+        return Ok(());
+    }
+    coupon_categories::Entity::insert_many(category_ids.into_iter().map(|category_id| {
+        coupon_categories::ActiveModel {
+            coupon_id: Set(coupon_id),
+            category_id: Set(category_id),
+        }
+    }))
+    .exec(conn)
+    .await?;
+    Ok(())
+}
+
+async fn coupon_response<C: ConnectionTrait>(
+    conn: &C,
+    coupon: coupons::Model,
+) -> Result<CouponResponse, ApiError> {
+    let (product_ids, category_ids) = restrictions(conn, coupon.id).await?;
+    Ok(CouponResponse {
+        coupon,
+        product_ids: product_ids.into_iter().collect(),
+        category_ids: category_ids.into_iter().collect(),
+    })
+}
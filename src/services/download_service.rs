@@ -0,0 +1,293 @@
+use crate::config::AppConfig;
+use crate::entity::sea_orm_active_enums::ProductType;
+use crate::entity::{digital_assets, download_grants, order_items, orders, products};
+use crate::error::ApiError;
+use crate::routes::downloads::DOWNLOAD_PATH;
+use crate::services::media_store::MediaStore;
+use crate::services::order_service::OrderStatus;
+use crate::services::{product_service, signing};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    entity::*, query::*, sea_query::OnConflict, ConnectionTrait, DatabaseConnection,
+};
+use serde::Serialize;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// ลิงก์ดาวน์โหลดของไฟล์หนึ่งไฟล์ในคำสั่งซื้อ ใช้ได้ถึง `expires_at`
+#[derive(Serialize)]
+pub struct DownloadLinkResponse {
+    pub asset_id: Uuid,
+    pub product_id: Uuid,
+    pub file_name: String,
+    pub size_bytes: i64,
+    pub download_count: i32,
+    pub max_downloads: Option<i32>,
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// ไฟล์ที่พร้อมส่งให้ผู้ซื้อ
+pub struct DownloadFile {
+    pub file_name: String,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+pub async fn list_assets(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+) -> Result<Vec<digital_assets::Model>, ApiError> {
+    digital_assets::Entity::find()
+        .filter(digital_assets::Column::ProductId.eq(product_id))
+        .order_by_asc(digital_assets::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// แนบไฟล์ให้สินค้าดิจิทัล ไฟล์ถูกเก็บใน private store ไม่มี URL สาธารณะ
+pub async fn upload_asset(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    product_id: Uuid,
+    file_name: &str,
+    content_type: &str,
+    bytes: Vec<u8>,
+    max_downloads: Option<i32>,
+) -> Result<digital_assets::Model, ApiError> {
+    let product = product_service::get_product_by_id(db, product_id).await?;
+    if product.product_type != ProductType::Digital {
+        return Err(ApiError::ValidationError(format!(
+            "Product with ID {} is not a digital product",
+            product_id
+        )));
+    }
+    if bytes.is_empty() {
+        return Err(ApiError::ValidationError("File must not be empty".to_string()));
+    }
+    if max_downloads.is_some_and(|max| max < 1) {
+        return Err(ApiError::ValidationError(
+            "max_downloads must be at least 1".to_string(),
+        ));
+    }
+
+    // เก็บแค่ชื่อไฟล์ ตัด path ที่ browser อาจแนบมาออก
+    let file_name = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("download")
+        .to_string();
+
+    let asset_id = Uuid::new_v4();
+    let storage_key = format!("products/{}/{}", product_id, asset_id);
+    let size_bytes = bytes.len() as i64;
+    store.put(&storage_key, bytes).await?;
+
+    let asset = digital_assets::ActiveModel {
+        id: Set(asset_id),
+        product_id: Set(product_id),
+        file_name: Set(file_name),
+        content_type: Set(content_type.to_string()),
+        size_bytes: Set(size_bytes),
+        storage_key: Set(storage_key.clone()),
+        max_downloads: Set(max_downloads),
+        created_at: Set(Utc::now()),
+    };
+
+    match asset.insert(db).await {
+        Ok(asset) => Ok(asset),
+        Err(_) => {
+            store.delete(&storage_key).await?;
+            Err(ApiError::DatabaseError("Failed to save digital asset".to_string()))
+        }
+    }
+}
+
+/// ลบไฟล์ สิทธิ์ดาวน์โหลดที่ออกไปแล้วของไฟล์นี้จะถูกลบตามไปด้วย
+pub async fn delete_asset(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    product_id: Uuid,
+    asset_id: Uuid,
+) -> Result<(), ApiError> {
+    let asset = digital_assets::Entity::find_by_id(asset_id)
+        .filter(digital_assets::Column::ProductId.eq(product_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Digital asset with ID {} not found", asset_id)))?;
+
+    digital_assets::Entity::delete_by_id(asset.id).exec(db).await?;
+    store.delete(&asset.storage_key).await
+}
+
+/// ออกสิทธิ์ดาวน์โหลดให้ทุกไฟล์ของสินค้าดิจิทัลในคำสั่งซื้อ เรียกตอนคำสั่งซื้อถูกชำระเงิน
+///
+/// เรียกซ้ำได้ สิทธิ์ที่มีอยู่แล้วจะไม่ถูกรีเซ็ตจำนวนครั้ง
+/// คำสั่งซื้อของ guest ไม่มีสินค้าดิจิทัลจึงไม่มีสิทธิ์ให้ออก
+pub async fn grant_downloads<C: ConnectionTrait>(
+    conn: &C,
+    order: &orders::Model,
+) -> Result<(), ApiError> {
+    let Some(user_id) = order.user_id else {
+        return Ok(());
+    };
+    let assets = digital_assets::Entity::find()
+        .inner_join(products::Entity)
+        .join(JoinType::InnerJoin, products::Relation::OrderItems.def())
+        .filter(order_items::Column::OrderId.eq(order.id))
+        .filter(products::Column::ProductType.eq(ProductType::Digital))
+        .all(conn)
+        .await?;
+    if assets.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let grants = assets.into_iter().map(|asset| download_grants::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        order_id: Set(order.id),
+        user_id: Set(user_id),
+        asset_id: Set(asset.id),
+        download_count: Set(0),
+        max_downloads: Set(asset.max_downloads),
+        last_downloaded_at: Set(None),
+        created_at: Set(now),
+    });
+    download_grants::Entity::insert_many(grants)
+        .on_conflict(
+            OnConflict::columns([download_grants::Column::OrderId, download_grants::Column::AssetId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+/// ลิงก์ดาวน์โหลดของคำสั่งซื้อ ออกให้เฉพาะเจ้าของและเมื่อชำระเงินแล้วเท่านั้น
+pub async fn get_download_links(
+    db: &DatabaseConnection,
+    config: &AppConfig,
+    user_id: Uuid,
+    order_id: Uuid,
+) -> Result<Vec<DownloadLinkResponse>, ApiError> {
+    let order = orders::Entity::find_by_id(order_id)
+        .filter(orders::Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Order with ID {} not found", order_id)))?;
+    ensure_paid(&order)?;
+
+    let expires_at = Utc::now() + chrono::Duration::seconds(config.download_url_ttl_secs);
+    let links = download_grants::Entity::find()
+        .find_also_related(digital_assets::Entity)
+        .filter(download_grants::Column::OrderId.eq(order.id))
+        .order_by_asc(download_grants::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(grant, asset)| {
+            let asset = asset?;
+            let expires = expires_at.timestamp();
+            Some(DownloadLinkResponse {
+                asset_id: asset.id,
+                product_id: asset.product_id,
+                file_name: asset.file_name,
+                size_bytes: asset.size_bytes,
+                download_count: grant.download_count,
+                max_downloads: grant.max_downloads,
+                url: format!(
+                    "{}{}?expires={}&signature={}",
+                    DOWNLOAD_PATH,
+                    grant.id,
+                    expires,
+                    sign(&config.download_secret, grant.id, expires)
+                ),
+                expires_at,
+            })
+        })
+        .collect();
+    Ok(links)
+}
+
+/// ตรวจลายเซ็นและอายุลิงก์ แล้วนับครั้งดาวน์โหลดก่อนส่งไฟล์
+pub async fn download(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    config: &AppConfig,
+    grant_id: Uuid,
+    expires: i64,
+    signature: &str,
+) -> Result<DownloadFile, ApiError> {
+    verify(&config.download_secret, grant_id, expires, signature)?;
+    if Utc::now().timestamp() > expires {
+        return Err(ApiError::Forbidden("Download link has expired".to_string()));
+    }
+
+    let txn = db.begin().await?;
+    // ล็อกแถวไว้ กันการโหลดพร้อมกันหลายครั้งจนเกินจำนวนที่กำหนด
+    let grant = download_grants::Entity::find_by_id(grant_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Download not found".to_string()))?;
+
+    // คำสั่งซื้อที่ถูกยกเลิกหลังชำระเงินจะโหลดไม่ได้อีก
+    let order = orders::Entity::find_by_id(grant.order_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Download not found".to_string()))?;
+    ensure_paid(&order)?;
+
+    if grant
+        .max_downloads
+        .is_some_and(|max| grant.download_count >= max)
+    {
+        return Err(ApiError::Forbidden("Download limit reached".to_string()));
+    }
+
+    let asset = digital_assets::Entity::find_by_id(grant.asset_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Download not found".to_string()))?;
+    let bytes = store.get(&asset.storage_key).await?;
+
+    let download_count = grant.download_count + 1;
+    let mut active_model: download_grants::ActiveModel = grant.into();
+    active_model.download_count = Set(download_count);
+    active_model.last_downloaded_at = Set(Some(Utc::now()));
+    active_model.update(&txn).await?;
+    txn.commit().await?;
+
+    Ok(DownloadFile {
+        file_name: asset.file_name,
+        content_type: asset.content_type,
+        bytes,
+    })
+}
+
+fn ensure_paid(order: &orders::Model) -> Result<(), ApiError> {
+    let is_paid = OrderStatus::from_str(&order.status).is_ok_and(OrderStatus::is_paid);
+    if !is_paid {
+        return Err(ApiError::Forbidden(format!(
+            "Order with ID {} has not been paid",
+            order.id
+        )));
+    }
+    Ok(())
+}
+
+/// ลายเซ็นของ (grant_id, expires)
+fn sign(secret: &str, grant_id: Uuid, expires: i64) -> String {
+    signing::sign(secret, format!("{}.{}", grant_id, expires).as_bytes())
+}
+
+fn verify(secret: &str, grant_id: Uuid, expires: i64, signature: &str) -> Result<(), ApiError> {
+    if !signing::verify(secret, format!("{}.{}", grant_id, expires).as_bytes(), signature) {
+        return Err(ApiError::Forbidden("Invalid download signature".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,405 @@
+use crate::entity::{cart, guest_cart_items, guest_carts, products};
+use crate::error::ApiError;
+use crate::services::cart_service::{self, CartLine, CartResponse};
+use crate::services::media_store::MediaStore;
+use crate::services::{product_service, signing};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{
+    entity::*, query::*, sea_query::{Alias, Expr, OnConflict}, ConnectionTrait,
+    DatabaseConnection,
+};
+use strum_macros::EnumString;
+use uuid::Uuid;
+
+/// header ที่ผู้ใช้ที่ยังไม่เข้าสู่ระบบส่ง token ของตะกร้ามา
+pub const CART_TOKEN_HEADER: &str = "X-Cart-Token";
+
+/// วิธีรวมจำนวนเมื่อสินค้าเดียวกันอยู่ทั้งในตะกร้า guest และตะกร้าของผู้ใช้
+/// ตั้งค่าได้ผ่าน `CART_MERGE_STRATEGY`
+#[derive(Clone, Copy, Debug, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum CartMergeStrategy {
+    /// บวกจำนวนทั้งสองตะกร้า
+    Sum,
+    /// ใช้จำนวนที่มากกว่า
+    Max,
+    /// ใช้จำนวนจากตะกร้า guest
+    Guest,
+    /// คงจำนวนในตะกร้าของผู้ใช้ไว้
+    User,
+}
+
+impl CartMergeStrategy {
+    fn merge(self, user_quantity: i32, guest_quantity: i32) -> i32 {
+        match self {
+            CartMergeStrategy::Sum => user_quantity + guest_quantity,
+            CartMergeStrategy::Max => user_quantity.max(guest_quantity),
+            CartMergeStrategy::Guest => guest_quantity,
+            CartMergeStrategy::User => user_quantity,
+        }
+    }
+}
+
+/// สร้างตะกร้าใหม่ คืนค่าตะกร้าพร้อม token ที่เซ็นไว้
+pub async fn create_cart(
+    db: &DatabaseConnection,
+    secret: &str,
+) -> Result<(guest_carts::Model, String), ApiError> {
+    let now = Utc::now();
+    let cart = guest_carts::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        created_at: Set(now),
+        updated_at: Set(now),
+    }
+    .insert(db)
+    .await?;
+    let token = issue_token(secret, cart.id);
+    Ok((cart, token))
+}
+
+/// token ของตะกร้าอยู่ในรูป `<cart_id>.<hmac>`
+pub fn issue_token(secret: &str, cart_id: Uuid) -> String {
+    format!("{}.{}", cart_id, signing::sign(secret, cart_id.as_bytes()))
+}
+
+/// ตรวจลายเซ็นของ token แล้วคืน id ของตะกร้า
+pub fn verify_token(secret: &str, token: &str) -> Result<Uuid, ApiError> {
+    let invalid = || ApiError::AuthenticationError("Invalid cart token".to_string());
+    let (cart_id, signature) = token.split_once('.').ok_or_else(invalid)?;
+    let cart_id = Uuid::parse_str(cart_id).map_err(|_| invalid())?;
+    if !signing::verify(secret, cart_id.as_bytes(), signature) {
+        return Err(invalid());
+    }
+    Ok(cart_id)
+}
+
+/// เพิ่มจำนวนสินค้าในตะกร้า ใช้ upsert และกฎจำนวนเดียวกับตะกร้าของผู้ใช้
+pub async fn add_to_cart(
+    db: &DatabaseConnection,
+    cart_id: Uuid,
+    ttl_secs: i64,
+    product_id: Uuid,
+    quantity: i32,
+) -> Result<guest_cart_items::Model, ApiError> {
+    if quantity < 1 {
+        return Err(ApiError::ValidationError(
+            "Quantity must be at least 1".to_string(),
+        ));
+    }
+    find_cart(db, cart_id, ttl_secs).await?;
+    let product = product_service::get_product_by_id(db, product_id).await?;
+
+    let txn = db.begin().await?;
+    let item = guest_cart_items::Entity::insert(new_item(cart_id, &product, quantity))
+        .on_conflict(
+            OnConflict::columns([
+                guest_cart_items::Column::GuestCartId,
+                guest_cart_items::Column::ProductId,
+            ])
+            .value(
+                guest_cart_items::Column::Quantity,
+                Expr::col((guest_cart_items::Entity, guest_cart_items::Column::Quantity)).add(
+                    Expr::col((Alias::new("excluded"), guest_cart_items::Column::Quantity)),
+                ),
+            )
+            .update_columns([
+                guest_cart_items::Column::PriceSnapshot,
+                guest_cart_items::Column::StatusSnapshot,
+            ])
+            .to_owned(),
+        )
+        .exec_with_returning(&txn)
+        .await?;
+    cart_service::ensure_cart_quantity(&product, item.quantity)?;
+    touch(&txn, cart_id).await?;
+    txn.commit().await?;
+    Ok(item)
+}
+
+/// ตั้งจำนวนเป็นค่าที่ระบุ จำนวน 0 คือเอาออกจากตะกร้า (คืนค่า None)
+pub async fn set_quantity(
+    db: &DatabaseConnection,
+    cart_id: Uuid,
+    ttl_secs: i64,
+    product_id: Uuid,
+    quantity: i32,
+) -> Result<Option<guest_cart_items::Model>, ApiError> {
+    if quantity < 0 {
+        return Err(ApiError::ValidationError(
+            "Quantity must not be negative".to_string(),
+        ));
+    }
+    if quantity == 0 {
+        remove_from_cart(db, cart_id, ttl_secs, product_id).await?;
+        return Ok(None);
+    }
+
+    find_cart(db, cart_id, ttl_secs).await?;
+    let product = product_service::get_product_by_id(db, product_id).await?;
+    cart_service::ensure_cart_quantity(&product, quantity)?;
+
+    let item = guest_cart_items::Entity::insert(new_item(cart_id, &product, quantity))
+        .on_conflict(
+            OnConflict::columns([
+                guest_cart_items::Column::GuestCartId,
+                guest_cart_items::Column::ProductId,
+            ])
+            .update_column(guest_cart_items::Column::Quantity)
+            .to_owned(),
+        )
+        .exec_with_returning(db)
+        .await?;
+    touch(db, cart_id).await?;
+    Ok(Some(item))
+}
+
+fn new_item(
+    cart_id: Uuid,
+    product: &products::Model,
+    quantity: i32,
+) -> guest_cart_items::ActiveModel {
+    guest_cart_items::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        guest_cart_id: Set(cart_id),
+        product_id: Set(product.id),
+        quantity: Set(quantity),
+        price_snapshot: Set(product.price),
+        status_snapshot: Set(product.status),
+    }
+}
+
+pub async fn remove_from_cart(
+    db: &DatabaseConnection,
+    cart_id: Uuid,
+    ttl_secs: i64,
+    product_id: Uuid,
+) -> Result<(), ApiError> {
+    find_cart(db, cart_id, ttl_secs).await?;
+    guest_cart_items::Entity::delete_many()
+        .filter(guest_cart_items::Column::GuestCartId.eq(cart_id))
+        .filter(guest_cart_items::Column::ProductId.eq(product_id))
+        .exec(db)
+        .await?;
+    touch(db, cart_id).await
+}
+
+pub async fn clear_cart(
+    db: &DatabaseConnection,
+    cart_id: Uuid,
+    ttl_secs: i64,
+) -> Result<(), ApiError> {
+    find_cart(db, cart_id, ttl_secs).await?;
+    guest_cart_items::Entity::delete_many()
+        .filter(guest_cart_items::Column::GuestCartId.eq(cart_id))
+        .exec(db)
+        .await?;
+    touch(db, cart_id).await
+}
+
+/// ตะกร้าพร้อมข้อมูลสินค้าและยอดรวม รูปแบบเดียวกับตะกร้าของผู้ใช้
+pub async fn get_cart(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    tax_rate: Decimal,
+    cart_id: Uuid,
+    ttl_secs: i64,
+) -> Result<CartResponse, ApiError> {
+    let lines = cart_lines(db, cart_id, ttl_secs).await?;
+    cart_service::build_cart_response(db, store, tax_rate, lines, None).await
+}
+
+pub async fn calculate_cart_total(
+    db: &DatabaseConnection,
+    cart_id: Uuid,
+    ttl_secs: i64,
+) -> Result<Decimal, ApiError> {
+    let lines = cart_lines(db, cart_id, ttl_secs).await?;
+    Ok(lines
+        .iter()
+        .map(|line| line.product.price * Decimal::from(line.quantity))
+        .sum())
+}
+
+/// ยืนยันว่าลูกค้าเห็นราคาและสถานะปัจจุบันแล้ว คืนค่าจำนวนแถวที่อัปเดต
+pub async fn acknowledge_changes(
+    db: &DatabaseConnection,
+    cart_id: Uuid,
+    ttl_secs: i64,
+) -> Result<usize, ApiError> {
+    let lines = cart_lines(db, cart_id, ttl_secs).await?;
+    let txn = db.begin().await?;
+    let mut acknowledged = 0;
+    for line in lines.iter().filter(|line| line.has_changes()) {
+        guest_cart_items::ActiveModel {
+            id: Set(line.id),
+            price_snapshot: Set(line.product.price),
+            status_snapshot: Set(line.product.status),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        acknowledged += 1;
+    }
+    touch(&txn, cart_id).await?;
+    txn.commit().await?;
+    Ok(acknowledged)
+}
+
+/// แถวในตะกร้า guest พร้อมสินค้า เรียงตามชื่อสินค้า
+pub async fn cart_lines<C: ConnectionTrait>(
+    conn: &C,
+    cart_id: Uuid,
+    ttl_secs: i64,
+) -> Result<Vec<CartLine>, ApiError> {
+    find_cart(conn, cart_id, ttl_secs).await?;
+    let rows = guest_cart_items::Entity::find()
+        .find_also_related(products::Entity)
+        .filter(guest_cart_items::Column::GuestCartId.eq(cart_id))
+        .order_by_asc(products::Column::Name)
+        .all(conn)
+        .await?;
+
+    rows.into_iter()
+        .map(|(item, product)| {
+            let product = product.ok_or_else(|| {
+                ApiError::NotFound(format!("Product with ID {} not found", item.product_id))
+            })?;
+            Ok(CartLine {
+                id: item.id,
+                quantity: item.quantity,
+                price_snapshot: item.price_snapshot,
+                status_snapshot: item.status_snapshot,
+                product,
+            })
+        })
+        .collect()
+}
+
+/// ย้ายสินค้าจากตะกร้า guest เข้าตะกร้าของผู้ใช้ แล้วลบตะกร้า guest ทิ้ง
+///
+/// สินค้าที่ถูกเก็บไปแล้ว หรือจำนวนที่รวมได้ผิดกฎของสินค้าจะถูกข้าม
+/// คืนค่าจำนวนรายการที่ย้ายเข้าไป
+pub async fn merge_into_user_cart(
+    db: &DatabaseConnection,
+    cart_id: Uuid,
+    user_id: Uuid,
+    strategy: CartMergeStrategy,
+) -> Result<usize, ApiError> {
+    let txn = db.begin().await?;
+    let Some(guest_cart) = guest_carts::Entity::find_by_id(cart_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+    else {
+        return Ok(0);
+    };
+
+    let items = guest_cart_items::Entity::find()
+        .find_also_related(products::Entity)
+        .filter(guest_cart_items::Column::GuestCartId.eq(guest_cart.id))
+        .all(&txn)
+        .await?;
+
+    let user_cart = cart_service::active_cart(&txn, user_id).await?;
+    let mut merged = 0;
+    for (item, product) in items {
+        let Some(product) = product else {
+            continue;
+        };
+        let existing_item = cart::Entity::find()
+            .filter(cart::Column::CartId.eq(user_cart.id))
+            .filter(cart::Column::ProductId.eq(item.product_id))
+            .one(&txn)
+            .await?;
+
+        let quantity = match &existing_item {
+            Some(existing) => strategy.merge(existing.quantity, item.quantity),
+            None => item.quantity,
+        };
+        if cart_service::ensure_cart_quantity(&product, quantity).is_err() {
+            continue;
+        }
+
+        match existing_item {
+            Some(existing) if existing.quantity == quantity => continue,
+            Some(existing) => {
+                let mut active_model: cart::ActiveModel = existing.into();
+                active_model.quantity = Set(quantity);
+                active_model.update(&txn).await?;
+            }
+            None => {
+                cart::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    cart_id: Set(user_cart.id),
+                    user_id: Set(user_id),
+                    product_id: Set(item.product_id),
+                    quantity: Set(quantity),
+                    // คงราคาที่ลูกค้าเห็นตอนใส่ตะกร้า guest ไว้ ถ้าเปลี่ยนจะถูกแจ้งในตะกร้าผู้ใช้
+                    price_snapshot: Set(item.price_snapshot),
+                    status_snapshot: Set(item.status_snapshot),
+                }
+                .insert(&txn)
+                .await?;
+            }
+        }
+        merged += 1;
+    }
+
+    if merged > 0 {
+        cart_service::touch(&txn, user_cart.id).await?;
+    }
+    guest_carts::Entity::delete_by_id(guest_cart.id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(merged)
+}
+
+/// ลบตะกร้า guest ที่ไม่ได้ใช้งานนานเกิน `ttl_secs` คืนค่าจำนวนตะกร้าที่ลบ
+pub async fn purge_expired(db: &DatabaseConnection, ttl_secs: i64) -> Result<u64, ApiError> {
+    let result = guest_carts::Entity::delete_many()
+        .filter(guest_carts::Column::UpdatedAt.lt(expiry_cutoff(ttl_secs)))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// ตะกร้าที่หมดอายุแต่ยังไม่ถูกลบก็ถือว่าไม่มีแล้ว
+async fn find_cart<C: ConnectionTrait>(
+    conn: &C,
+    cart_id: Uuid,
+    ttl_secs: i64,
+) -> Result<guest_carts::Model, ApiError> {
+    guest_carts::Entity::find_by_id(cart_id)
+        .filter(guest_carts::Column::UpdatedAt.gte(expiry_cutoff(ttl_secs)))
+        .one(conn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Cart not found or expired".to_string()))
+}
+
+/// ล็อกตะกร้าไว้จนจบ transaction ตะกร้าที่ถูกสั่งซื้อไปแล้วระหว่างรอล็อกจะไม่พบ
+pub async fn lock_cart<C: ConnectionTrait>(
+    txn: &C,
+    cart_id: Uuid,
+    ttl_secs: i64,
+) -> Result<guest_carts::Model, ApiError> {
+    guest_carts::Entity::find_by_id(cart_id)
+        .filter(guest_carts::Column::UpdatedAt.gte(expiry_cutoff(ttl_secs)))
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Cart not found or expired".to_string()))
+}
+
+/// ต่ออายุตะกร้าทุกครั้งที่มีการแก้ไข
+async fn touch<C: ConnectionTrait>(conn: &C, cart_id: Uuid) -> Result<(), ApiError> {
+    guest_carts::Entity::update_many()
+        .col_expr(guest_carts::Column::UpdatedAt, Expr::value(Utc::now()))
+        .filter(guest_carts::Column::Id.eq(cart_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+fn expiry_cutoff(ttl_secs: i64) -> chrono::DateTime<Utc> {
+    Utc::now() - chrono::Duration::seconds(ttl_secs)
+}
@@ -0,0 +1,143 @@
+use crate::config::AppConfig;
+use crate::entity::orders::ShippingAddress;
+use crate::entity::{guest_carts, order_items, orders, users};
+use crate::error::ApiError;
+use crate::services::inventory_service::AllocationStrategy;
+use crate::services::notification::{Notification, NotificationChannel};
+use crate::services::order_service::{self, OrderCustomer};
+use crate::services::{cart_service, guest_cart_service, signing};
+use sea_orm::{entity::*, query::*, DatabaseConnection};
+use uuid::Uuid;
+
+/// token ที่ guest ใช้ดูสถานะคำสั่งซื้อ อยู่ในรูป `<order_id>.<hmac>`
+pub fn issue_access_token(secret: &str, order_id: Uuid) -> String {
+    format!("{}.{}", order_id, signing::sign(secret, &access_message(order_id)))
+}
+
+pub fn verify_access_token(secret: &str, token: &str) -> Result<Uuid, ApiError> {
+    let invalid = || ApiError::Forbidden("Invalid order access token".to_string());
+    let (order_id, signature) = token.split_once('.').ok_or_else(invalid)?;
+    let order_id = Uuid::parse_str(order_id).map_err(|_| invalid())?;
+    if !signing::verify(secret, &access_message(order_id), signature) {
+        return Err(invalid());
+    }
+    Ok(order_id)
+}
+
+/// สั่งซื้อจากตะกร้า guest โดยไม่มีบัญชี แล้วส่ง token สำหรับดูสถานะไปที่อีเมล
+///
+/// คืนค่าคำสั่งซื้อพร้อม access token
+pub async fn checkout(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    config: &AppConfig,
+    strategy: &dyn AllocationStrategy,
+    cart_id: Uuid,
+    email: String,
+    shipping_address: ShippingAddress,
+) -> Result<(orders::Model, String), ApiError> {
+    let txn = db.begin().await?;
+
+    // ล็อกตะกร้าไว้ กันการกดสั่งซื้อตะกร้าเดียวกันซ้ำพร้อมกัน
+    guest_cart_service::lock_cart(&txn, cart_id, config.guest_cart_ttl_secs).await?;
+    let lines = guest_cart_service::cart_lines(&txn, cart_id, config.guest_cart_ttl_secs).await?;
+    cart_service::ensure_acknowledged(&lines)?;
+    let items = lines
+        .into_iter()
+        .map(|line| (line.product.id, line.quantity))
+        .collect();
+
+    let customer = OrderCustomer::Guest {
+        email: email.clone(),
+        shipping_address,
+    };
+    let order = order_service::place_order(&txn, customer, items, strategy).await?;
+    guest_carts::Entity::delete_by_id(cart_id).exec(&txn).await?;
+    txn.commit().await?;
+
+    let token = issue_access_token(&config.order_token_secret, order.id);
+    let notification = Notification {
+        subject: "Your order has been received".to_string(),
+        body: format!(
+            "Order {} totals {}. Use this code to check its status: {}",
+            order.id, order.total_price, token
+        ),
+    };
+    // ส่งอีเมลไม่สำเร็จไม่ทำให้คำสั่งซื้อล้มเหลว token ยังอยู่ใน response
+    if let Err(e) = channel.send(&email, &notification).await {
+        eprintln!("Failed to send order confirmation for {}: {}", order.id, e);
+    }
+
+    Ok((order, token))
+}
+
+/// คำสั่งซื้อของ guest ตาม access token ใช้ไม่ได้แล้วเมื่อคำสั่งซื้อถูกรับเข้าบัญชี
+pub async fn get_order(
+    db: &DatabaseConnection,
+    secret: &str,
+    token: &str,
+) -> Result<(orders::Model, Vec<order_items::Model>), ApiError> {
+    let order_id = verify_access_token(secret, token)?;
+    let order = orders::Entity::find_by_id(order_id)
+        .filter(orders::Column::UserId.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Order with ID {} not found", order_id)))?;
+
+    let items = order_items::Entity::find()
+        .filter(order_items::Column::OrderId.eq(order.id))
+        .all(db)
+        .await?;
+    Ok((order, items))
+}
+
+/// รับคำสั่งซื้อของ guest เข้าบัญชี อีเมลของบัญชีต้องยืนยันแล้วและตรงกับอีเมลที่ใช้สั่งซื้อ
+pub async fn claim_order(
+    db: &DatabaseConnection,
+    secret: &str,
+    user_id: Uuid,
+    token: &str,
+) -> Result<orders::Model, ApiError> {
+    let order_id = verify_access_token(secret, token)?;
+    let user = users::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::AuthenticationError("User not found".to_string()))?;
+    if user.email_verified_at.is_none() {
+        return Err(ApiError::Forbidden(
+            "Verify your email before claiming orders".to_string(),
+        ));
+    }
+
+    let txn = db.begin().await?;
+    let order = orders::Entity::find_by_id(order_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Order with ID {} not found", order_id)))?;
+    if order.user_id.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "Order with ID {} has already been claimed",
+            order_id
+        )));
+    }
+    let email_matches = order
+        .guest_email
+        .as_deref()
+        .is_some_and(|email| email.eq_ignore_ascii_case(&user.email));
+    if !email_matches {
+        return Err(ApiError::Forbidden(
+            "Order was placed with a different email".to_string(),
+        ));
+    }
+
+    let mut active_order: orders::ActiveModel = order.into();
+    active_order.user_id = Set(Some(user.id));
+    let order = active_order.update(&txn).await?;
+    txn.commit().await?;
+    Ok(order)
+}
+
+fn access_message(order_id: Uuid) -> Vec<u8> {
+    format!("order:{}", order_id).into_bytes()
+}
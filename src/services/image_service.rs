@@ -0,0 +1,298 @@
+use crate::entity::product_images;
+use crate::error::ApiError;
+use crate::services::media_store::MediaStore;
+use crate::services::product_service;
+use image::{DynamicImage, ImageFormat};
+use sea_orm::{entity::*, query::*, sea_query::Expr, DatabaseConnection, TransactionTrait};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Cursor;
+use uuid::Uuid;
+
+/// ชนิดไฟล์ที่อนุญาตให้อัปโหลด
+pub const ALLOWED_CONTENT_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+
+const MEDIUM_SIZE: u32 = 800;
+const THUMBNAIL_SIZE: u32 = 200;
+
+/// รูปสินค้าพร้อม URL ทุกขนาด สำหรับแนบไปกับข้อมูลสินค้า
+#[derive(Serialize)]
+pub struct ProductImageResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub medium_url: String,
+    pub thumbnail_url: String,
+    pub alt_text: Option<String>,
+    pub position: i32,
+    pub is_primary: bool,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl ProductImageResponse {
+    pub fn new(image: &product_images::Model, store: &dyn MediaStore) -> Self {
+        Self {
+            id: image.id,
+            url: store.url(&image.original_key),
+            medium_url: store.url(&image.medium_key),
+            thumbnail_url: store.url(&image.thumbnail_key),
+            alt_text: image.alt_text.clone(),
+            position: image.position,
+            is_primary: image.is_primary,
+            width: image.width,
+            height: image.height,
+        }
+    }
+}
+
+struct ProcessedImage {
+    width: u32,
+    height: u32,
+    medium: Vec<u8>,
+    thumbnail: Vec<u8>,
+}
+
+pub async fn list_images(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+) -> Result<Vec<product_images::Model>, ApiError> {
+    product_images::Entity::find()
+        .filter(product_images::Column::ProductId.eq(product_id))
+        .order_by_asc(product_images::Column::Position)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// ดึงรูปของสินค้าหลายตัวในคำสั่งเดียว จัดกลุ่มตาม product_id
+pub async fn images_for_products(
+    db: &DatabaseConnection,
+    product_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<product_images::Model>>, ApiError> {
+    let images = product_images::Entity::find()
+        .filter(product_images::Column::ProductId.is_in(product_ids.iter().copied()))
+        .order_by_asc(product_images::Column::Position)
+        .all(db)
+        .await?;
+
+    let mut grouped: HashMap<Uuid, Vec<product_images::Model>> = HashMap::new();
+    for image in images {
+        grouped.entry(image.product_id).or_default().push(image);
+    }
+    Ok(grouped)
+}
+
+/// ตรวจไฟล์ สร้างรูปขนาดกลางและ thumbnail แล้วบันทึกทั้งหมดลง MediaStore
+pub async fn upload_image(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    product_id: Uuid,
+    bytes: Vec<u8>,
+    content_type: &str,
+    alt_text: Option<String>,
+    max_bytes: usize,
+) -> Result<product_images::Model, ApiError> {
+    product_service::get_product_by_id(db, product_id).await?;
+
+    if !ALLOWED_CONTENT_TYPES.contains(&content_type) {
+        return Err(ApiError::ValidationError(format!(
+            "Unsupported content type: {}",
+            content_type
+        )));
+    }
+    if bytes.is_empty() || bytes.len() > max_bytes {
+        return Err(ApiError::ValidationError(format!(
+            "Image must be between 1 and {} bytes",
+            max_bytes
+        )));
+    }
+
+    let format = ImageFormat::from_mime_type(content_type).ok_or_else(|| {
+        ApiError::ValidationError(format!("Unsupported content type: {}", content_type))
+    })?;
+    let extension = format.extensions_str().first().copied().unwrap_or("img");
+
+    // ย่อรูปเป็นงาน CPU จึงแยกไปทำใน blocking thread
+    let original = bytes.clone();
+    let processed = tokio::task::spawn_blocking(move || process_image(&original, format))
+        .await
+        .map_err(|_| ApiError::InternalServerError)??;
+
+    let image_id = Uuid::new_v4();
+    let prefix = format!("products/{}/{}", product_id, image_id);
+    let original_key = format!("{}/original.{}", prefix, extension);
+    let medium_key = format!("{}/medium.{}", prefix, extension);
+    let thumbnail_key = format!("{}/thumbnail.{}", prefix, extension);
+
+    let size_bytes = bytes.len() as i64;
+    store.put(&original_key, bytes).await?;
+    store.put(&medium_key, processed.medium).await?;
+    store.put(&thumbnail_key, processed.thumbnail).await?;
+
+    let existing = list_images(db, product_id).await?;
+    let new_image = product_images::ActiveModel {
+        id: Set(image_id),
+        product_id: Set(product_id),
+        position: Set(existing.iter().map(|image| image.position + 1).max().unwrap_or(0)),
+        is_primary: Set(existing.is_empty()),
+        alt_text: Set(alt_text),
+        content_type: Set(content_type.to_string()),
+        size_bytes: Set(size_bytes),
+        width: Set(processed.width as i32),
+        height: Set(processed.height as i32),
+        original_key: Set(original_key),
+        medium_key: Set(medium_key),
+        thumbnail_key: Set(thumbnail_key),
+        created_at: Set(chrono::Utc::now()),
+    };
+
+    match new_image.insert(db).await {
+        Ok(image) => Ok(image),
+        Err(_) => {
+            delete_files(store, &prefix, extension).await;
+            Err(ApiError::DatabaseError("Failed to save product image".to_string()))
+        }
+    }
+}
+
+/// แก้ alt text หรือตั้งเป็นรูปหลัก
+pub async fn update_image(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    image_id: Uuid,
+    alt_text: Option<String>,
+    is_primary: Option<bool>,
+) -> Result<product_images::Model, ApiError> {
+    let image = find_image(db, product_id, image_id).await?;
+
+    let txn = db.begin().await?;
+    if is_primary == Some(true) {
+        product_images::Entity::update_many()
+            .col_expr(product_images::Column::IsPrimary, Expr::value(false))
+            .filter(product_images::Column::ProductId.eq(product_id))
+            .exec(&txn)
+            .await?;
+    }
+
+    let mut active_model: product_images::ActiveModel = image.into();
+    if let Some(alt_text) = alt_text {
+        active_model.alt_text = Set(Some(alt_text));
+    }
+    if let Some(is_primary) = is_primary {
+        active_model.is_primary = Set(is_primary);
+    }
+    let image = active_model.update(&txn).await?;
+
+    txn.commit().await?;
+    Ok(image)
+}
+
+/// จัดลำดับรูปใหม่ตามลำดับของ id ที่ส่งมา ต้องส่งมาครบทุกรูปของสินค้า
+pub async fn reorder_images(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    image_ids: Vec<Uuid>,
+) -> Result<Vec<product_images::Model>, ApiError> {
+    let existing = list_images(db, product_id).await?;
+
+    let mut expected: Vec<Uuid> = existing.iter().map(|image| image.id).collect();
+    let mut requested = image_ids.clone();
+    expected.sort();
+    requested.sort();
+    if expected != requested {
+        return Err(ApiError::ValidationError(
+            "image_ids must list every image of the product exactly once".to_string(),
+        ));
+    }
+
+    let txn = db.begin().await?;
+    for (position, image_id) in image_ids.iter().enumerate() {
+        product_images::Entity::update_many()
+            .col_expr(product_images::Column::Position, Expr::value(position as i32))
+            .filter(product_images::Column::Id.eq(*image_id))
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+
+    list_images(db, product_id).await
+}
+
+/// ลบรูป ถ้าเป็นรูปหลักจะเลื่อนรูปแรกที่เหลือขึ้นมาเป็นรูปหลักแทน
+pub async fn delete_image(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    product_id: Uuid,
+    image_id: Uuid,
+) -> Result<(), ApiError> {
+    let image = find_image(db, product_id, image_id).await?;
+
+    let txn = db.begin().await?;
+    product_images::Entity::delete_by_id(image.id).exec(&txn).await?;
+
+    if image.is_primary {
+        if let Some(next) = product_images::Entity::find()
+            .filter(product_images::Column::ProductId.eq(product_id))
+            .order_by_asc(product_images::Column::Position)
+            .one(&txn)
+            .await?
+        {
+            let mut active_model: product_images::ActiveModel = next.into();
+            active_model.is_primary = Set(true);
+            active_model.update(&txn).await?;
+        }
+    }
+    txn.commit().await?;
+
+    for key in [&image.original_key, &image.medium_key, &image.thumbnail_key] {
+        store.delete(key).await?;
+    }
+    Ok(())
+}
+
+async fn find_image(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    image_id: Uuid,
+) -> Result<product_images::Model, ApiError> {
+    product_images::Entity::find_by_id(image_id)
+        .filter(product_images::Column::ProductId.eq(product_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Image with ID {} not found", image_id)))
+}
+
+async fn delete_files(store: &dyn MediaStore, prefix: &str, extension: &str) {
+    for size in ["original", "medium", "thumbnail"] {
+        let _ = store.delete(&format!("{}/{}.{}", prefix, size, extension)).await;
+    }
+}
+
+fn process_image(bytes: &[u8], format: ImageFormat) -> Result<ProcessedImage, ApiError> {
+    let image = image::load_from_memory_with_format(bytes, format)
+        .map_err(|_| ApiError::ValidationError("Uploaded file is not a valid image".to_string()))?;
+
+    Ok(ProcessedImage {
+        width: image.width(),
+        height: image.height(),
+        medium: encode(&fit_within(&image, MEDIUM_SIZE), format)?,
+        thumbnail: encode(&fit_within(&image, THUMBNAIL_SIZE), format)?,
+    })
+}
+
+/// ย่อให้อยู่ในกรอบ size x size โดยคงอัตราส่วน และไม่ขยายรูปที่เล็กกว่าอยู่แล้ว
+fn fit_within(image: &DynamicImage, size: u32) -> DynamicImage {
+    if image.width() <= size && image.height() <= size {
+        image.clone()
+    } else {
+        image.thumbnail(size, size)
+    }
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, ApiError> {
+    let mut buffer = Cursor::new(Vec::new());
+    image
+        .write_to(&mut buffer, format)
+        .map_err(|_| ApiError::InternalServerError)?;
+    Ok(buffer.into_inner())
+}
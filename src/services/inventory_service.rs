@@ -0,0 +1,469 @@
+use crate::entity::sea_orm_active_enums::{LocationKind, MovementReason};
+use crate::entity::{locations, stock_levels, stock_movements};
+use crate::error::ApiError;
+use crate::services::alert_service;
+use crate::services::notification::NotificationChannel;
+use sea_orm::{
+    entity::*, query::*, sea_query::{Expr, Func}, ConnectionTrait, DatabaseConnection,
+    TransactionTrait,
+};
+use std::collections::HashMap;
+use strum_macros::EnumString;
+use uuid::Uuid;
+
+/// กลยุทธ์การเลือกคลังที่ใช้จัดส่งสินค้าตอนสร้างคำสั่งซื้อ
+pub trait AllocationStrategy: Send + Sync {
+    /// เลือกคลังจากรายการคลังที่มีสต็อกของสินค้านี้ หรือ None ถ้าไม่มีคลังไหนมีของพอ
+    fn select_location(
+        &self,
+        quantity: i32,
+        candidates: &[(locations::Model, stock_levels::Model)],
+    ) -> Option<Uuid>;
+}
+
+/// เลือกคลังที่มี priority น้อยที่สุดที่ยังมีของพอ
+pub struct PriorityAllocation;
+
+impl AllocationStrategy for PriorityAllocation {
+    fn select_location(
+        &self,
+        quantity: i32,
+        candidates: &[(locations::Model, stock_levels::Model)],
+    ) -> Option<Uuid> {
+        candidates
+            .iter()
+            .filter(|(_, level)| level.quantity >= quantity)
+            .min_by_key(|(location, _)| location.priority)
+            .map(|(location, _)| location.id)
+    }
+}
+
+/// เลือกคลังที่มีของเหลือมากที่สุด เพื่อกระจายสต็อกให้สมดุล
+pub struct MostStockAllocation;
+
+impl AllocationStrategy for MostStockAllocation {
+    fn select_location(
+        &self,
+        quantity: i32,
+        candidates: &[(locations::Model, stock_levels::Model)],
+    ) -> Option<Uuid> {
+        candidates
+            .iter()
+            .filter(|(_, level)| level.quantity >= quantity)
+            .max_by_key(|(_, level)| level.quantity)
+            .map(|(location, _)| location.id)
+    }
+}
+
+/// ชื่อกลยุทธ์ที่ตั้งค่าได้ผ่าน `ALLOCATION_STRATEGY`
+#[derive(Clone, Copy, Debug, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum AllocationStrategyKind {
+    Priority,
+    MostStock,
+}
+
+impl AllocationStrategyKind {
+    pub fn strategy(&self) -> Box<dyn AllocationStrategy> {
+        match self {
+            AllocationStrategyKind::Priority => Box::new(PriorityAllocation),
+            AllocationStrategyKind::MostStock => Box::new(MostStockAllocation),
+        }
+    }
+}
+
+pub async fn list_locations(db: &DatabaseConnection) -> Result<Vec<locations::Model>, ApiError> {
+    locations::Entity::find()
+        .order_by_asc(locations::Column::Priority)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+pub async fn create_location(
+    db: &DatabaseConnection,
+    code: String,
+    name: String,
+    kind: LocationKind,
+    priority: i32,
+) -> Result<locations::Model, ApiError> {
+    let new_location = locations::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        code: Set(code),
+        name: Set(name),
+        kind: Set(kind),
+        priority: Set(priority),
+        is_active: Set(true),
+        created_at: Set(chrono::Utc::now()),
+    };
+
+    new_location
+        .insert(db)
+        .await
+        .map_err(|_| ApiError::DatabaseError("Failed to create location".to_string()))
+}
+
+/// ดึงสต็อกทุกสินค้าในคลังที่ระบุ
+pub async fn get_location_stock(
+    db: &DatabaseConnection,
+    location_id: Uuid,
+) -> Result<Vec<stock_levels::Model>, ApiError> {
+    find_location(db, location_id).await?;
+
+    stock_levels::Entity::find()
+        .filter(stock_levels::Column::LocationId.eq(location_id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// ดึงสต็อกของสินค้าในทุกคลัง
+pub async fn get_product_stock(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+) -> Result<Vec<stock_levels::Model>, ApiError> {
+    stock_levels::Entity::find()
+        .filter(stock_levels::Column::ProductId.eq(product_id))
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// จำนวนรวมในคลังที่เปิดใช้อยู่ของแต่ละสินค้า นับแบบเดียวกับ `allocate_stock`
+///
+/// สินค้าที่ไม่เคยมีแถวสต็อกในคลังใดเลยถือว่าไม่ได้ติดตามสต็อก จะไม่มีใน map
+/// ส่วนสินค้าที่มีของเฉพาะในคลังที่ปิดไปแล้วจะได้ 0
+pub async fn stock_totals<C: ConnectionTrait>(
+    conn: &C,
+    product_ids: &[Uuid],
+) -> Result<HashMap<Uuid, i64>, ApiError> {
+    if product_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(Uuid, Option<i64>)> = stock_levels::Entity::find()
+        .select_only()
+        .column(stock_levels::Column::ProductId)
+        .column_as(
+            Expr::expr(Func::sum(
+                Expr::case(
+                    Expr::col((locations::Entity, locations::Column::IsActive)).eq(true),
+                    Expr::col((stock_levels::Entity, stock_levels::Column::Quantity)),
+                )
+                .finally(0),
+            )),
+            "total",
+        )
+        .inner_join(locations::Entity)
+        .filter(stock_levels::Column::ProductId.is_in(product_ids.to_vec()))
+        .group_by(stock_levels::Column::ProductId)
+        .into_tuple()
+        .all(conn)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(product_id, total)| (product_id, total.unwrap_or(0)))
+        .collect())
+}
+
+/// ปรับสต็อกตามผลการนับจริง (cycle count) และบันทึกส่วนต่างลง movement
+///
+/// ถ้าสต็อกรวมกลับมาจาก 0 จะแจ้งคนที่รอสินค้ากลับมามีของ
+pub async fn adjust_stock(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    location_id: Uuid,
+    product_id: Uuid,
+    counted_quantity: i32,
+    note: Option<String>,
+) -> Result<stock_levels::Model, ApiError> {
+    let stock_before = stock_totals(db, &[product_id]).await?;
+    let txn = db.begin().await?;
+    find_location(&txn, location_id).await?;
+
+    let current = find_level(&txn, location_id, product_id).await?;
+    let delta = counted_quantity - current.as_ref().map_or(0, |level| level.quantity);
+
+    let level = match current {
+        Some(level) => {
+            let mut active_model: stock_levels::ActiveModel = level.into();
+            active_model.quantity = Set(counted_quantity);
+            active_model.updated_at = Set(chrono::Utc::now());
+            active_model.update(&txn).await?
+        }
+        None => {
+            let new_level = stock_levels::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                location_id: Set(location_id),
+                product_id: Set(product_id),
+                quantity: Set(counted_quantity),
+                updated_at: Set(chrono::Utc::now()),
+            };
+            new_level.insert(&txn).await?
+        }
+    };
+
+    if delta != 0 {
+        record_movement(&txn, location_id, product_id, delta, MovementReason::Adjustment, None, note)
+            .await?;
+    }
+
+    txn.commit().await?;
+
+    alert_service::notify_restocked(db, channel, &stock_before).await;
+    Ok(level)
+}
+
+/// ย้ายสต็อกระหว่างคลัง โดยบันทึก movement ขาออกและขาเข้าด้วย reference เดียวกัน
+pub async fn transfer_stock(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    product_id: Uuid,
+    from_location_id: Uuid,
+    to_location_id: Uuid,
+    quantity: i32,
+    note: Option<String>,
+) -> Result<(), ApiError> {
+    if from_location_id == to_location_id {
+        return Err(ApiError::ValidationError(
+            "Source and destination locations must differ".to_string(),
+        ));
+    }
+
+    let stock_before = stock_totals(db, &[product_id]).await?;
+    let txn = db.begin().await?;
+    find_location(&txn, from_location_id).await?;
+    find_location(&txn, to_location_id).await?;
+
+    if !take_stock(&txn, from_location_id, product_id, quantity).await? {
+        return Err(ApiError::ValidationError(format!(
+            "Insufficient stock at location {} for product {}",
+            from_location_id, product_id
+        )));
+    }
+    put_stock(&txn, to_location_id, product_id, quantity).await?;
+
+    let transfer_id = Some(Uuid::new_v4());
+    record_movement(
+        &txn,
+        from_location_id,
+        product_id,
+        -quantity,
+        MovementReason::Transfer,
+        transfer_id,
+        note.clone(),
+    )
+    .await?;
+    record_movement(
+        &txn,
+        to_location_id,
+        product_id,
+        quantity,
+        MovementReason::Transfer,
+        transfer_id,
+        note,
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    alert_service::notify_restocked(db, channel, &stock_before).await;
+    Ok(())
+}
+
+/// เลือกคลังด้วย strategy แล้วตัดสต็อกสำหรับรายการในคำสั่งซื้อ
+///
+/// สินค้าที่ยังไม่เคยมีสต็อกในคลังใดเลยถือว่าไม่ได้ติดตามสต็อก และคืนค่า None
+/// ส่วนคลังที่ปิดไปแล้วยังนับว่าติดตามสต็อก แต่ไม่ถูกเลือกมาตัดสต็อก
+pub async fn allocate_stock<C: ConnectionTrait>(
+    conn: &C,
+    strategy: &dyn AllocationStrategy,
+    product_id: Uuid,
+    quantity: i32,
+    order_id: Uuid,
+) -> Result<Option<Uuid>, ApiError> {
+    let levels: Vec<(locations::Model, stock_levels::Model)> = stock_levels::Entity::find()
+        .filter(stock_levels::Column::ProductId.eq(product_id))
+        .find_also_related(locations::Entity)
+        .all(conn)
+        .await?
+        .into_iter()
+        .filter_map(|(level, location)| location.map(|location| (location, level)))
+        .collect();
+
+    if levels.is_empty() {
+        return Ok(None);
+    }
+
+    let candidates: Vec<(locations::Model, stock_levels::Model)> = levels
+        .into_iter()
+        .filter(|(location, _)| location.is_active)
+        .collect();
+
+    let location_id = strategy
+        .select_location(quantity, &candidates)
+        .ok_or_else(|| {
+            ApiError::ValidationError(format!("Insufficient stock for product {}", product_id))
+        })?;
+
+    if !take_stock(conn, location_id, product_id, quantity).await? {
+        return Err(ApiError::ValidationError(format!(
+            "Insufficient stock for product {}",
+            product_id
+        )));
+    }
+    record_movement(
+        conn,
+        location_id,
+        product_id,
+        -quantity,
+        MovementReason::Fulfilment,
+        Some(order_id),
+        None,
+    )
+    .await?;
+
+    Ok(Some(location_id))
+}
+
+/// คืนสต็อกที่ตัดให้คำสั่งซื้อกลับเข้าคลังเดิม ใช้ตอนยกเลิกคำสั่งซื้อ
+///
+/// บันทึกเป็น movement ขาเข้าที่อ้างอิงคำสั่งซื้อเดียวกัน ยอดสุทธิของคำสั่งซื้อจึงเป็น 0
+/// และเรียกซ้ำก็ไม่คืนเกิน
+pub async fn release_order_stock<C: ConnectionTrait>(
+    conn: &C,
+    order_id: Uuid,
+) -> Result<(), ApiError> {
+    let allocations: Vec<(Uuid, Uuid, Option<i64>)> = stock_movements::Entity::find()
+        .select_only()
+        .column(stock_movements::Column::LocationId)
+        .column(stock_movements::Column::ProductId)
+        .column_as(
+            Expr::expr(Func::sum(Expr::col(stock_movements::Column::QuantityChange))),
+            "net",
+        )
+        .filter(stock_movements::Column::Reason.eq(MovementReason::Fulfilment))
+        .filter(stock_movements::Column::ReferenceId.eq(order_id))
+        .group_by(stock_movements::Column::LocationId)
+        .group_by(stock_movements::Column::ProductId)
+        .into_tuple()
+        .all(conn)
+        .await?;
+
+    for (location_id, product_id, net) in allocations {
+        let quantity = -(net.unwrap_or(0) as i32);
+        if quantity <= 0 {
+            continue;
+        }
+        put_stock(conn, location_id, product_id, quantity).await?;
+        record_movement(
+            conn,
+            location_id,
+            product_id,
+            quantity,
+            MovementReason::Fulfilment,
+            Some(order_id),
+            Some("Order cancelled".to_string()),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+async fn find_location<C: ConnectionTrait>(
+    conn: &C,
+    location_id: Uuid,
+) -> Result<locations::Model, ApiError> {
+    locations::Entity::find_by_id(location_id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Location with ID {} not found", location_id)))
+}
+
+async fn find_level<C: ConnectionTrait>(
+    conn: &C,
+    location_id: Uuid,
+    product_id: Uuid,
+) -> Result<Option<stock_levels::Model>, ApiError> {
+    stock_levels::Entity::find()
+        .filter(stock_levels::Column::LocationId.eq(location_id))
+        .filter(stock_levels::Column::ProductId.eq(product_id))
+        .one(conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// ลดสต็อกแบบมีเงื่อนไขในคำสั่งเดียว คืนค่า false ถ้าของไม่พอ
+async fn take_stock<C: ConnectionTrait>(
+    conn: &C,
+    location_id: Uuid,
+    product_id: Uuid,
+    quantity: i32,
+) -> Result<bool, ApiError> {
+    let result = stock_levels::Entity::update_many()
+        .col_expr(
+            stock_levels::Column::Quantity,
+            Expr::col(stock_levels::Column::Quantity).sub(quantity),
+        )
+        .col_expr(stock_levels::Column::UpdatedAt, Expr::value(chrono::Utc::now()))
+        .filter(stock_levels::Column::LocationId.eq(location_id))
+        .filter(stock_levels::Column::ProductId.eq(product_id))
+        .filter(stock_levels::Column::Quantity.gte(quantity))
+        .exec(conn)
+        .await?;
+
+    Ok(result.rows_affected > 0)
+}
+
+async fn put_stock<C: ConnectionTrait>(
+    conn: &C,
+    location_id: Uuid,
+    product_id: Uuid,
+    quantity: i32,
+) -> Result<(), ApiError> {
+    match find_level(conn, location_id, product_id).await? {
+        Some(level) => {
+            let new_quantity = level.quantity + quantity;
+            let mut active_model: stock_levels::ActiveModel = level.into();
+            active_model.quantity = Set(new_quantity);
+            active_model.updated_at = Set(chrono::Utc::now());
+            active_model.update(conn).await?;
+        }
+        None => {
+            let new_level = stock_levels::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                location_id: Set(location_id),
+                product_id: Set(product_id),
+                quantity: Set(quantity),
+                updated_at: Set(chrono::Utc::now()),
+            };
+            new_level.insert(conn).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn record_movement<C: ConnectionTrait>(
+    conn: &C,
+    location_id: Uuid,
+    product_id: Uuid,
+    quantity_change: i32,
+    reason: MovementReason,
+    reference_id: Option<Uuid>,
+    note: Option<String>,
+) -> Result<stock_movements::Model, ApiError> {
+    let movement = stock_movements::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        location_id: Set(location_id),
+        product_id: Set(product_id),
+        quantity_change: Set(quantity_change),
+        reason: Set(reason),
+        reference_id: Set(reference_id),
+        note: Set(note),
+        created_at: Set(chrono::Utc::now()),
+    };
+
+    movement.insert(conn).await.map_err(ApiError::from)
+}
@@ -0,0 +1,95 @@
+use crate::error::ApiError;
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// ที่เก็บไฟล์สื่อ (รูปภาพ ฯลฯ) แยกออกจาก logic เพื่อเปลี่ยนไปใช้ object storage ได้ภายหลัง
+#[async_trait]
+pub trait MediaStore: Send + Sync {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), ApiError>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>, ApiError>;
+    async fn delete(&self, key: &str) -> Result<(), ApiError>;
+    /// URL สาธารณะของไฟล์
+    fn url(&self, key: &str) -> String;
+}
+
+/// เก็บไฟล์ไว้ในดิสก์ของเครื่อง และเสิร์ฟผ่าน `base_url`
+pub struct LocalMediaStore {
+    root: PathBuf,
+    base_url: String,
+}
+
+impl LocalMediaStore {
+    pub fn new(root: impl Into<PathBuf>, base_url: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, ApiError> {
+        // key มาจากระบบเอง แต่กันไว้ไม่ให้หลุดออกนอก root
+        if key.split('/').any(|part| part.is_empty() || part == "..") {
+            return Err(ApiError::ValidationError(format!("Invalid media key: {}", key)));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl MediaStore for LocalMediaStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), ApiError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|_| ApiError::InternalServerError)?;
+        }
+        tokio::fs::write(&path, bytes)
+            .await
+            .map_err(|_| ApiError::InternalServerError)
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, ApiError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(ApiError::NotFound(format!("File {} not found", key)))
+            }
+            Err(_) => Err(ApiError::InternalServerError),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), ApiError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(_) => Err(ApiError::InternalServerError),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+}
+
+/// ที่เก็บไฟล์สินค้าดิจิทัล ไม่มี URL สาธารณะ ต้องโหลดผ่านลิงก์ที่เซ็นไว้เท่านั้น
+///
+/// แยกเป็นอีก type เพื่อไม่ให้ `web::Data` สลับกับ MediaStore ของรูปภาพ
+pub struct PrivateMediaStore(Arc<dyn MediaStore>);
+
+impl PrivateMediaStore {
+    pub fn new(store: Arc<dyn MediaStore>) -> Self {
+        Self(store)
+    }
+
+    pub fn store(&self) -> &dyn MediaStore {
+        self.0.as_ref()
+    }
+}
@@ -2,6 +2,29 @@ pub mod auth;
 pub mod product_service;
 pub mod cart_service;
 pub mod order_service;
+pub mod inventory_service;
+pub mod catalog_csv_service;
+pub mod image_service;
+pub mod media_store;
+pub mod price_service;
 
 // pub use auth::{hash_password, verify_password, generate_jwt};
 // pub use product_service::{get_all_products, get_product_by_id, create_product};
+pub mod review_service;
+pub mod attribute_service;
+pub mod category_service;
+pub mod translation_service;
+pub mod recommendation_service;
+pub mod wishlist_service;
+pub mod notification;
+pub mod alert_service;
+pub mod user_service;
+pub mod download_service;
+pub mod bundle_service;
+pub mod vendor_service;
+pub mod guest_cart_service;
+pub mod signing;
+pub mod guest_order_service;
+pub mod named_cart_service;
+pub mod cart_reminder_service;
+pub mod coupon_service;
@@ -0,0 +1,375 @@
+use crate::entity::sea_orm_active_enums::ProductStatus;
+use crate::entity::{cart, carts, products, saved_cart_items};
+use crate::error::ApiError;
+use crate::services::cart_service::{self, CartResponse};
+use crate::services::media_store::MediaStore;
+use crate::services::{coupon_service, product_service};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    entity::*, query::*, sea_query::{Alias, Expr, OnConflict}, ConnectionTrait,
+    DatabaseConnection,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// ตะกร้าที่ตั้งชื่อไว้พร้อมรายการสินค้าและยอดรวม
+#[derive(Serialize)]
+pub struct NamedCartResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub is_active: bool,
+    pub updated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub cart: CartResponse,
+}
+
+/// สินค้าที่บันทึกไว้ซื้อภายหลังพร้อมราคาและสถานะปัจจุบัน
+#[derive(Serialize)]
+pub struct SavedItemResponse {
+    pub product_id: Uuid,
+    pub name: String,
+    pub price: Decimal,
+    pub status: ProductStatus,
+    pub quantity: i32,
+    /// false เมื่อสินค้าถูกเก็บหรือไม่อยู่ในสถานะขายได้
+    pub is_available: bool,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// ตะกร้าทั้งหมดของผู้ใช้ ถ้ายังไม่มีจะสร้างตะกร้าเริ่มต้นให้
+pub async fn list_carts(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<Vec<carts::Model>, ApiError> {
+    cart_service::active_cart(db, user_id).await?;
+    carts::Entity::find()
+        .filter(carts::Column::UserId.eq(user_id))
+        .order_by_asc(carts::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// สร้างตะกร้าใหม่ ตะกร้าที่ใช้งานอยู่ยังเป็นตะกร้าเดิมจนกว่าจะสลับ
+pub async fn create_cart(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    name: String,
+) -> Result<carts::Model, ApiError> {
+    ensure_name_available(db, user_id, &name, None).await?;
+
+    let now = Utc::now();
+    let cart = carts::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        name: Set(name),
+        is_active: Set(false),
+        created_at: Set(now),
+        updated_at: Set(now),
+        reminder_count: Set(0),
+        last_reminded_at: Set(None),
+        coupon_id: Set(None),
+    };
+    cart.insert(db).await.map_err(ApiError::from)
+}
+
+pub async fn rename_cart(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    cart_id: Uuid,
+    name: String,
+) -> Result<carts::Model, ApiError> {
+    let cart = cart_service::resolve_cart(db, user_id, Some(cart_id)).await?;
+    ensure_name_available(db, user_id, &name, Some(cart.id)).await?;
+
+    let mut active_model: carts::ActiveModel = cart.into();
+    active_model.name = Set(name);
+    active_model.updated_at = Set(Utc::now());
+    active_model.update(db).await.map_err(ApiError::from)
+}
+
+/// ลบตะกร้าพร้อมสินค้าในตะกร้า ถ้าเป็นตะกร้าที่ใช้งานอยู่จะสลับไปใช้ตะกร้าที่แก้ไขล่าสุดแทน
+pub async fn delete_cart(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    cart_id: Uuid,
+) -> Result<(), ApiError> {
+    let txn = db.begin().await?;
+    let cart = cart_service::resolve_cart(&txn, user_id, Some(cart_id)).await?;
+    carts::Entity::delete_by_id(cart.id).exec(&txn).await?;
+
+    if cart.is_active {
+        let next = carts::Entity::find()
+            .filter(carts::Column::UserId.eq(user_id))
+            .order_by_desc(carts::Column::UpdatedAt)
+            .one(&txn)
+            .await?;
+        if let Some(next) = next {
+            set_active(&txn, user_id, next.id).await?;
+        }
+    }
+
+    txn.commit().await?;
+    Ok(())
+}
+
+/// สลับตะกร้าที่ใช้งานอยู่ การใส่ตะกร้าและการสั่งซื้อแบบไม่ระบุตะกร้าจะใช้ตะกร้านี้
+pub async fn activate_cart(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    cart_id: Uuid,
+) -> Result<carts::Model, ApiError> {
+    let txn = db.begin().await?;
+    let cart = cart_service::resolve_cart(&txn, user_id, Some(cart_id)).await?;
+    if !cart.is_active {
+        set_active(&txn, user_id, cart.id).await?;
+    }
+    txn.commit().await?;
+    cart_service::resolve_cart(db, user_id, Some(cart_id)).await
+}
+
+pub async fn get_cart(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    tax_rate: Decimal,
+    user_id: Uuid,
+    cart_id: Uuid,
+) -> Result<NamedCartResponse, ApiError> {
+    let cart = cart_service::resolve_cart(db, user_id, Some(cart_id)).await?;
+    let lines = cart_service::cart_lines(db, cart.id).await?;
+    let coupon = coupon_service::cart_coupon(db, &cart, &lines).await?;
+    let response = cart_service::build_cart_response(db, store, tax_rate, lines, coupon).await?;
+    Ok(NamedCartResponse {
+        id: cart.id,
+        name: cart.name,
+        is_active: cart.is_active,
+        updated_at: cart.updated_at,
+        cart: response,
+    })
+}
+
+/// ย้ายสินค้าไปอีกตะกร้าของผู้ใช้ ถ้าปลายทางมีสินค้านี้อยู่แล้วจะรวมจำนวนกัน
+///
+/// แถวใหม่คง snapshot ราคาเดิมไว้ การเปลี่ยนแปลงที่ยังไม่ยืนยันจึงตามไปด้วย
+pub async fn move_item(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    cart_id: Uuid,
+    product_id: Uuid,
+    target_cart_id: Uuid,
+) -> Result<cart::Model, ApiError> {
+    if cart_id == target_cart_id {
+        return Err(ApiError::ValidationError(
+            "Target cart must be different from the source cart".to_string(),
+        ));
+    }
+    let product = product_service::get_product_by_id(db, product_id).await?;
+
+    let txn = db.begin().await?;
+    let source = cart_service::resolve_cart(&txn, user_id, Some(cart_id)).await?;
+    let target = cart_service::resolve_cart(&txn, user_id, Some(target_cart_id)).await?;
+    let line = find_line(&txn, source.id, product_id).await?;
+
+    let item = cart::Entity::insert(cart::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        cart_id: Set(target.id),
+        user_id: Set(user_id),
+        product_id: Set(product_id),
+        quantity: Set(line.quantity),
+        price_snapshot: Set(line.price_snapshot),
+        status_snapshot: Set(line.status_snapshot),
+    })
+    .on_conflict(
+        OnConflict::columns([cart::Column::CartId, cart::Column::ProductId])
+            .value(
+                cart::Column::Quantity,
+                Expr::col((cart::Entity, cart::Column::Quantity))
+                    .add(Expr::col((Alias::new("excluded"), cart::Column::Quantity))),
+            )
+            .to_owned(),
+    )
+    .exec_with_returning(&txn)
+    .await?;
+    cart_service::ensure_cart_quantity(&product, item.quantity)?;
+
+    cart::Entity::delete_by_id(line.id).exec(&txn).await?;
+    cart_service::touch(&txn, source.id).await?;
+    cart_service::touch(&txn, target.id).await?;
+    txn.commit().await?;
+    Ok(item)
+}
+
+/// ย้ายสินค้าจากตะกร้าไปรายการบันทึกไว้ซื้อภายหลัง ถ้าบันทึกไว้แล้วจะรวมจำนวนกัน
+pub async fn save_for_later(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    cart_id: Uuid,
+    product_id: Uuid,
+) -> Result<saved_cart_items::Model, ApiError> {
+    let txn = db.begin().await?;
+    let cart = cart_service::resolve_cart(&txn, user_id, Some(cart_id)).await?;
+    let line = find_line(&txn, cart.id, product_id).await?;
+
+    let saved = saved_cart_items::Entity::insert(saved_cart_items::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        product_id: Set(product_id),
+        quantity: Set(line.quantity),
+        saved_at: Set(Utc::now()),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            saved_cart_items::Column::UserId,
+            saved_cart_items::Column::ProductId,
+        ])
+        .value(
+            saved_cart_items::Column::Quantity,
+            Expr::col((saved_cart_items::Entity, saved_cart_items::Column::Quantity)).add(
+                Expr::col((Alias::new("excluded"), saved_cart_items::Column::Quantity)),
+            ),
+        )
+        .update_column(saved_cart_items::Column::SavedAt)
+        .to_owned(),
+    )
+    .exec_with_returning(&txn)
+    .await?;
+
+    cart::Entity::delete_by_id(line.id).exec(&txn).await?;
+    cart_service::touch(&txn, cart.id).await?;
+    txn.commit().await?;
+    Ok(saved)
+}
+
+pub async fn list_saved(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<Vec<SavedItemResponse>, ApiError> {
+    let rows = saved_cart_items::Entity::find()
+        .find_also_related(products::Entity)
+        .filter(saved_cart_items::Column::UserId.eq(user_id))
+        .order_by_desc(saved_cart_items::Column::SavedAt)
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(item, product)| {
+            let product = product?;
+            Some(SavedItemResponse {
+                product_id: product.id,
+                is_available: product.deleted_at.is_none()
+                    && product.status == ProductStatus::Available,
+                name: product.name,
+                price: product.price,
+                status: product.status,
+                quantity: item.quantity,
+                saved_at: item.saved_at,
+            })
+        })
+        .collect())
+}
+
+pub async fn remove_saved(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    product_id: Uuid,
+) -> Result<(), ApiError> {
+    let result = saved_cart_items::Entity::delete_many()
+        .filter(saved_cart_items::Column::UserId.eq(user_id))
+        .filter(saved_cart_items::Column::ProductId.eq(product_id))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(ApiError::NotFound(format!(
+            "Product with ID {} is not saved for later",
+            product_id
+        )));
+    }
+    Ok(())
+}
+
+/// ย้ายสินค้าที่บันทึกไว้กลับเข้าตะกร้า ไม่ระบุ `cart_id` คือตะกร้าที่ใช้งานอยู่
+///
+/// ใช้กฎเดียวกับการใส่ตะกร้าปกติ ถ้าไม่ผ่านสินค้ายังอยู่ในรายการบันทึกตามเดิม
+pub async fn move_saved_to_cart(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    product_id: Uuid,
+    cart_id: Option<Uuid>,
+) -> Result<cart::Model, ApiError> {
+    let product = product_service::get_product_by_id(db, product_id).await?;
+
+    let txn = db.begin().await?;
+    let saved = saved_cart_items::Entity::find()
+        .filter(saved_cart_items::Column::UserId.eq(user_id))
+        .filter(saved_cart_items::Column::ProductId.eq(product_id))
+        .one(&txn)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Product with ID {} is not saved for later", product_id))
+        })?;
+    let cart = cart_service::resolve_cart(&txn, user_id, cart_id).await?;
+
+    let item = cart_service::add_line(&txn, &cart, &product, saved.quantity).await?;
+    saved_cart_items::Entity::delete_by_id(saved.id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(item)
+}
+
+/// ปิดตะกร้าที่ใช้งานอยู่ก่อน แล้วจึงเปิดตะกร้าใหม่ เพื่อไม่ให้ชน unique index
+async fn set_active<C: ConnectionTrait>(
+    conn: &C,
+    user_id: Uuid,
+    cart_id: Uuid,
+) -> Result<(), ApiError> {
+    carts::Entity::update_many()
+        .col_expr(carts::Column::IsActive, Expr::value(false))
+        .filter(carts::Column::UserId.eq(user_id))
+        .filter(carts::Column::IsActive.eq(true))
+        .exec(conn)
+        .await?;
+    carts::Entity::update_many()
+        .col_expr(carts::Column::IsActive, Expr::value(true))
+        .filter(carts::Column::Id.eq(cart_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+async fn find_line<C: ConnectionTrait>(
+    conn: &C,
+    cart_id: Uuid,
+    product_id: Uuid,
+) -> Result<cart::Model, ApiError> {
+    cart::Entity::find()
+        .filter(cart::Column::CartId.eq(cart_id))
+        .filter(cart::Column::ProductId.eq(product_id))
+        .one(conn)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Product with ID {} is not in this cart", product_id))
+        })
+}
+
+/// ชื่อตะกร้าต้องไม่ซ้ำกันในผู้ใช้คนเดียวกัน
+async fn ensure_name_available(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    name: &str,
+    except_cart_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let mut query = carts::Entity::find()
+        .filter(carts::Column::UserId.eq(user_id))
+        .filter(carts::Column::Name.eq(name));
+    if let Some(cart_id) = except_cart_id {
+        query = query.filter(carts::Column::Id.ne(cart_id));
+    }
+    if query.one(db).await?.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "A cart named '{}' already exists",
+            name
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::error::ApiError;
+use async_trait::async_trait;
+
+/// ข้อความแจ้งเตือนหนึ่งฉบับ
+pub struct Notification {
+    pub subject: String,
+    pub body: String,
+}
+
+/// ช่องทางส่งแจ้งเตือนถึงผู้ใช้ แยกออกมาเพื่อเปลี่ยนเป็นอีเมลหรือ push ได้ภายหลัง
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    async fn send(&self, email: &str, notification: &Notification) -> Result<(), ApiError>;
+}
+
+/// เขียนแจ้งเตือนลง log แทนการส่งจริง ใช้ระหว่างพัฒนาและเมื่อยังไม่ได้ตั้งค่าผู้ให้บริการ
+pub struct LogNotificationChannel;
+
+#[async_trait]
+impl NotificationChannel for LogNotificationChannel {
+    async fn send(&self, email: &str, notification: &Notification) -> Result<(), ApiError> {
+        println!(
+            "แจ้งเตือนถึง {}: {} - {}",
+            email, notification.subject, notification.body
+        );
+        Ok(())
+    }
+}
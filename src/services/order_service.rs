@@ -1,21 +1,110 @@
-use crate::entity::{cart, order_items, orders, products};
+use crate::entity::sea_orm_active_enums::{ProductStatus, ProductType};
+use crate::entity::orders::ShippingAddress;
+use crate::entity::{cart, carts, order_items, orders, products};
+use crate::services::inventory_service::{self, AllocationStrategy};
+use crate::services::{
+    alert_service, bundle_service, cart_reminder_service, cart_service, coupon_service,
+    download_service, product_service, vendor_service,
+};
+use crate::services::notification::NotificationChannel;
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{
+    sea_query::{Expr, Query, SelectStatement}, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, QueryFilter, QuerySelect, Set, TransactionTrait,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
 use uuid::Uuid;
 use crate::error::ApiError;
 
+/// สถานะคำสั่งซื้อที่ระบบรองรับ (เก็บในฐานข้อมูลเป็น string)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum OrderStatus {
+    Pending,
+    Paid,
+    Shipped,
+    Delivered,
+    Cancelled,
+}
+
+impl OrderStatus {
+    /// ชำระเงินแล้วและยังไม่ถูกยกเลิก
+    pub fn is_paid(self) -> bool {
+        matches!(self, Self::Paid | Self::Shipped | Self::Delivered)
+    }
+
+    /// สถานะที่เปลี่ยนต่อจากสถานะนี้ได้ ส่งสถานะเดิมซ้ำถือว่าไม่ใช่การเปลี่ยน
+    pub fn can_transition_to(self, next: Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::Pending, Self::Paid | Self::Cancelled)
+                | (Self::Paid, Self::Shipped | Self::Cancelled)
+                | (Self::Shipped, Self::Delivered)
+        )
+    }
+}
+
+/// ผู้สั่งซื้อ ผู้ใช้ที่มีบัญชี หรือ guest ที่ระบุด้วยอีเมลและที่อยู่จัดส่ง
+pub enum OrderCustomer {
+    User(Uuid),
+    Guest {
+        email: String,
+        shipping_address: ShippingAddress,
+    },
+}
+
+/// สั่งซื้อจากตะกร้าที่ระบุ ถ้าไม่ระบุ `cart_id` จะใช้ตะกร้าที่ใช้งานอยู่
 pub async fn create_order(
     db: &DatabaseConnection,
     user_id: Uuid,
+    cart_id: Option<Uuid>,
+    strategy: &dyn AllocationStrategy,
 ) -> Result<orders::Model, ApiError> {
-    // ดึงรายการสินค้าจากตะกร้า
-    let cart_items = cart::Entity::find()
-        .filter(cart::Column::UserId.eq(user_id))
-        .all(db)
+    let txn = db.begin().await?;
+
+    // ล็อกตะกร้าไว้ กันการสั่งซื้อตะกร้าเดียวกันซ้ำพร้อมกัน อ่านคูปองจากแถวที่ล็อกแล้ว
+    let cart = cart_service::resolve_cart(&txn, user_id, cart_id).await?;
+    let cart = carts::Entity::find_by_id(cart.id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .unwrap_or(cart);
+
+    // ดึงรายการสินค้าจากตะกร้า ราคาหรือสถานะที่เปลี่ยนต้องถูกยืนยันก่อน
+    let lines = cart_service::cart_lines(&txn, cart.id).await?;
+    cart_service::ensure_acknowledged(&lines)?;
+
+    let items = lines
+        .iter()
+        .map(|line| (line.product.id, line.quantity))
+        .collect();
+    let mut order = place_order(&txn, OrderCustomer::User(user_id), items, strategy).await?;
+    if let Some(coupon_id) = cart.coupon_id {
+        order = coupon_service::redeem(&txn, coupon_id, user_id, &lines, order).await?;
+    }
+    cart_reminder_service::mark_recovered(&txn, cart.id, order.id).await?;
+
+    // ลบสินค้าทั้งหมดจากตะกร้า ตัวตะกร้ายังอยู่ใช้ต่อได้
+    cart::Entity::delete_many()
+        .filter(cart::Column::CartId.eq(cart.id))
+        .exec(&txn)
         .await
         .map_err(ApiError::from)?;
 
-    if cart_items.is_empty() {
+    txn.commit().await?;
+    Ok(order)
+}
+
+/// สร้างคำสั่งซื้อจากรายการ (product_id, quantity) พร้อมตัดสต็อก ผู้เรียกเป็นคนจัดการตะกร้าเอง
+pub async fn place_order<C: ConnectionTrait>(
+    txn: &C,
+    customer: OrderCustomer,
+    items: Vec<(Uuid, i32)>,
+    strategy: &dyn AllocationStrategy,
+) -> Result<orders::Model, ApiError> {
+    if items.is_empty() {
         return Err(ApiError::ValidationError(
             "Cart is empty, cannot create order".to_string(),
         ));
@@ -23,50 +112,113 @@ pub async fn create_order(
 
     // คำนวณราคารวม
     let mut total_price = Decimal::new(0, 0);
-    for item in &cart_items {
-        if let Some(product) = products::Entity::find_by_id(item.product_id)
-            .one(db)
+    let mut lines = Vec::with_capacity(items.len());
+    for (product_id, quantity) in items {
+        if let Some(product) = products::Entity::find_by_id(product_id)
+            .one(txn)
             .await
             .map_err(ApiError::from)?
         {
-            total_price += product.price * Decimal::from(item.quantity);
+            product_service::ensure_purchasable(&product, quantity)?;
+            product_service::ensure_quantity_allowed(&product, quantity)?;
+            total_price += product.price * Decimal::from(quantity);
+            lines.push((quantity, product));
         } else {
             return Err(ApiError::NotFound(format!(
                 "Product with ID {} not found",
-                item.product_id
+                product_id
             )));
         }
     }
 
-    // สร้างคำสั่งซื้อใหม่
+    let (user_id, guest_email, shipping_address) = match customer {
+        OrderCustomer::User(user_id) => (Some(user_id), None, None),
+        OrderCustomer::Guest {
+            email,
+            shipping_address,
+        } => {
+            // guest ไม่มีบัญชีไว้รับลิงก์ดาวน์โหลด
+            if lines
+                .iter()
+                .any(|(_, product)| product.product_type == ProductType::Digital)
+            {
+                return Err(ApiError::ValidationError(
+                    "Digital products require an account to purchase".to_string(),
+                ));
+            }
+            (None, Some(email), Some(shipping_address))
+        }
+    };
+
+    // สร้างคำสั่งซื้อใหม่ ถ้ามีแต่สินค้าดิจิทัลไม่ต้องจัดส่ง
+    let requires_shipping = lines
+        .iter()
+        .any(|(_, product)| product.product_type != ProductType::Digital);
     let new_order = orders::ActiveModel {
         id: Set(Uuid::new_v4()),
         user_id: Set(user_id),
         total_price: Set(total_price),
-        status: Set("pending".to_string()),
+        status: Set(OrderStatus::Pending.to_string()),
         created_at: Set(chrono::Utc::now()),
+        version: Set(1),
+        requires_shipping: Set(requires_shipping),
+        guest_email: Set(guest_email),
+        shipping_address: Set(shipping_address),
+        coupon_id: Set(None),
+        discount_total: Set(Decimal::ZERO),
     };
-    let order = new_order.insert(db).await.map_err(ApiError::from)?;
+    let order = new_order.insert(txn).await.map_err(ApiError::from)?;
+
+    // แยกสินค้าของแต่ละร้านเป็นคำสั่งซื้อย่อย สินค้าของแพลตฟอร์มไม่มีคำสั่งซื้อย่อย
+    let mut vendor_subtotals: HashMap<Uuid, Decimal> = HashMap::new();
+    for (quantity, product) in &lines {
+        if let Some(vendor_id) = product.vendor_id {
+            *vendor_subtotals.entry(vendor_id).or_default() +=
+                product.price * Decimal::from(*quantity);
+        }
+    }
+    let vendor_orders =
+        vendor_service::create_vendor_orders(txn, order.id, &vendor_subtotals).await?;
+
+    // เพิ่มสินค้าใน OrderItems พร้อมตัดสต็อกจากคลังที่ strategy เลือก
+    // สินค้าดิจิทัลไม่มีสต็อกจึงไม่ต้องเลือกคลัง ส่วนชุดสินค้าตัดสต็อกที่ component
+    for (quantity, product) in lines {
+        if product.is_one_of_a_kind {
+            reserve_product(txn, product.id).await?;
+        }
+
+        let location_id = match product.product_type {
+            ProductType::Physical => {
+                inventory_service::allocate_stock(
+                    txn,
+                    strategy,
+                    product.id,
+                    quantity,
+                    order.id,
+                )
+                .await?
+            }
+            ProductType::Digital | ProductType::Bundle => None,
+        };
 
-    // เพิ่มสินค้าใน OrderItems
-    for item in cart_items {
         let order_item = order_items::ActiveModel {
             id: Set(Uuid::new_v4()),
             order_id: Set(order.id),
-            product_id: Set(item.product_id),
-            quantity: Set(item.quantity),
-            price: Set(item.quantity.into()),
+            product_id: Set(product.id),
+            quantity: Set(quantity),
+            price: Set(product.price),
+            location_id: Set(location_id),
+            parent_item_id: Set(None),
+            vendor_order_id: Set(product
+                .vendor_id
+                .and_then(|vendor_id| vendor_orders.get(&vendor_id).copied())),
         };
-        order_item.insert(db).await.map_err(ApiError::from)?;
-    }
-
-    // ลบสินค้าทั้งหมดจากตะกร้า
-    cart::Entity::delete_many()
-        .filter(cart::Column::UserId.eq(user_id))
-        .exec(db)
-        .await
-        .map_err(ApiError::from)?;
+        let order_item = order_item.insert(txn).await.map_err(ApiError::from)?;
 
+        if product.product_type == ProductType::Bundle {
+            add_bundle_components(txn, strategy, order.id, &order_item).await?;
+        }
+    }
     Ok(order)
 }
 
@@ -106,18 +258,282 @@ pub async fn get_order_history(
 
 pub async fn update_order_status(
     db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
     order_id: Uuid,
+    expected_version: Option<i32>,
     new_status: String,
-) -> Result<(), ApiError> {
+) -> Result<orders::Model, ApiError> {
+    let status = parse_order_status(&new_status)?;
+    let changes = OrderUpdate { status: Some(status) };
+    update_order(db, channel, order_id, expected_version, changes).await
+}
+
+pub fn parse_order_status(status: &str) -> Result<OrderStatus, ApiError> {
+    OrderStatus::from_str(status)
+        .map_err(|_| ApiError::ValidationError(format!("Invalid status: {}", status)))
+}
+
+/// ค่าที่จะแก้ในคำสั่งซื้อ ฟิลด์ที่เป็น None จะคงค่าเดิมไว้
+#[derive(Default)]
+pub struct OrderUpdate {
+    pub status: Option<OrderStatus>,
+}
+
+/// ยกเลิกคำสั่งซื้อแล้วสินค้ากลับมาขายได้ จะแจ้งคนที่รอสินค้ากลับมามีของหลัง commit
+pub async fn update_order(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    order_id: Uuid,
+    expected_version: Option<i32>,
+    changes: OrderUpdate,
+) -> Result<orders::Model, ApiError> {
+    apply_order_update(db, channel, order_id, expected_version, changes, None).await
+}
+
+/// ลูกค้ายกเลิกคำสั่งซื้อของตัวเองได้เฉพาะตอนที่ยังไม่ชำระเงิน
+pub async fn cancel_own_order(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    user_id: Uuid,
+    order_id: Uuid,
+    expected_version: Option<i32>,
+) -> Result<orders::Model, ApiError> {
+    let changes = OrderUpdate { status: Some(OrderStatus::Cancelled) };
+    apply_order_update(db, channel, order_id, expected_version, changes, Some(user_id)).await
+}
+
+/// `customer_id` คือลูกค้าที่แก้คำสั่งซื้อเอง ตรวจเจ้าของและสถานะหลังล็อกแถวแล้ว
+async fn apply_order_update(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    order_id: Uuid,
+    expected_version: Option<i32>,
+    changes: OrderUpdate,
+    customer_id: Option<Uuid>,
+) -> Result<orders::Model, ApiError> {
+    let txn = db.begin().await?;
+    // ล็อกแถวไว้จนจบ transaction กันสองคนเปลี่ยนสถานะพร้อมกัน
     let order = orders::Entity::find_by_id(order_id)
-        .one(db)
+        .lock_exclusive()
+        .one(&txn)
         .await
         .map_err(ApiError::from)?
         .ok_or(ApiError::NotFound(format!("Order with ID {} not found", order_id)))?;
+    if expected_version.is_some_and(|version| version != order.version) {
+        return Err(ApiError::PreconditionFailed(format!(
+            "Order with ID {} has been modified (current version is {})",
+            order_id, order.version
+        )));
+    }
+
+    if let Some(user_id) = customer_id {
+        if order.user_id != Some(user_id) {
+            return Err(ApiError::Forbidden(
+                "Users can only cancel their own orders".to_string(),
+            ));
+        }
+        if parse_order_status(&order.status)? != OrderStatus::Pending {
+            return Err(ApiError::Conflict(format!(
+                "Order with ID {} can only be cancelled before payment",
+                order_id
+            )));
+        }
+    }
+    if let Some(status) = changes.status {
+        let current = parse_order_status(&order.status)?;
+        if !current.can_transition_to(status) {
+            return Err(ApiError::Conflict(format!(
+                "Order with ID {} cannot change from {} to {}",
+                order_id, current, status
+            )));
+        }
+    }
+    if changes.status == Some(OrderStatus::Shipped) && !order.requires_shipping {
+        return Err(ApiError::ValidationError(format!(
+            "Order with ID {} contains only digital products and cannot be shipped",
+            order_id
+        )));
+    }
+
+    // เก็บสินค้าและสต็อกก่อนยกเลิกไว้เทียบ เพื่อแจ้งเตือนหลัง commit
+    let (products_before, stock_before) = if changes.status == Some(OrderStatus::Cancelled) {
+        let products_before = order_products(&txn, order_id).await?;
+        let product_ids: Vec<Uuid> = products_before.iter().map(|product| product.id).collect();
+        let stock_before = inventory_service::stock_totals(&txn, &product_ids).await?;
+        (products_before, stock_before)
+    } else {
+        Default::default()
+    };
 
     let mut active_order: orders::ActiveModel = order.into();
-    active_order.status = Set(new_status);
+    if let Some(status) = changes.status {
+        active_order.status = Set(status.to_string());
+    }
+    let order = active_order.update(&txn).await.map_err(ApiError::from)?;
+
+    // สินค้าชิ้นเดียว: ชำระเงินแล้วถือว่าขายแล้ว ยกเลิกแล้วปล่อยกลับมาขายใหม่
+    // ยอดของร้านลงบัญชีตอนชำระเงิน และกลับรายการเมื่อยกเลิก สต็อกที่ตัดไปคืนเข้าคลังเมื่อยกเลิก
+    // ตารางสถานะด้านบนทำให้แต่ละ side effect เกิดได้ครั้งเดียวต่อคำสั่งซื้อ
+    match changes.status {
+        Some(OrderStatus::Paid) => {
+            set_one_of_a_kind_status(
+                &txn,
+                order_id,
+                &[ProductStatus::Reserved],
+                ProductStatus::Sold,
+            )
+            .await?;
+            download_service::grant_downloads(&txn, &order).await?;
+            vendor_service::record_sales(&txn, order_id).await?;
+        }
+        Some(OrderStatus::Cancelled) => {
+            set_one_of_a_kind_status(
+                &txn,
+                order_id,
+                &[ProductStatus::Reserved, ProductStatus::Sold],
+                ProductStatus::Available,
+            )
+            .await?;
+            inventory_service::release_order_stock(&txn, order_id).await?;
+            vendor_service::reverse_sales(&txn, order_id).await?;
+            coupon_service::release(&txn, order_id).await?;
+        }
+        _ => {}
+    }
+
+    txn.commit().await?;
 
-    active_order.update(db).await.map_err(ApiError::from)?;
+    alert_service::notify_products_changed(db, channel, &products_before).await;
+    alert_service::notify_restocked(db, channel, &stock_before).await;
+    Ok(order)
+}
+
+/// สินค้าทุกรายการในคำสั่งซื้อ รวม component ของชุดสินค้า
+async fn order_products<C: ConnectionTrait>(
+    conn: &C,
+    order_id: Uuid,
+) -> Result<Vec<products::Model>, ApiError> {
+    products::Entity::find()
+        .filter(
+            products::Column::Id.in_subquery(
+                Query::select()
+                    .column(order_items::Column::ProductId)
+                    .from(order_items::Entity)
+                    .and_where(order_items::Column::OrderId.eq(order_id))
+                    .to_owned(),
+            ),
+        )
+        .all(conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// แตกชุดสินค้าเป็นแถวของ component ราคา 0 (ราคาอยู่ที่แถวของชุด) และตัดสต็อกของแต่ละชิ้น
+async fn add_bundle_components<C: ConnectionTrait>(
+    conn: &C,
+    strategy: &dyn AllocationStrategy,
+    order_id: Uuid,
+    bundle_item: &order_items::Model,
+) -> Result<(), ApiError> {
+    let components = bundle_service::components_of(conn, bundle_item.product_id).await?;
+    if components.is_empty() {
+        return Err(ApiError::Conflict(format!(
+            "Bundle with ID {} has no components",
+            bundle_item.product_id
+        )));
+    }
+
+    for (component, product) in components {
+        let quantity = component.quantity * bundle_item.quantity;
+        product_service::ensure_purchasable(&product, quantity)?;
+
+        let location_id =
+            inventory_service::allocate_stock(conn, strategy, product.id, quantity, order_id)
+                .await?;
+        let order_item = order_items::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            order_id: Set(order_id),
+            product_id: Set(product.id),
+            quantity: Set(quantity),
+            price: Set(Decimal::ZERO),
+            location_id: Set(location_id),
+            parent_item_id: Set(Some(bundle_item.id)),
+            vendor_order_id: Set(bundle_item.vendor_order_id),
+        };
+        order_item.insert(conn).await.map_err(ApiError::from)?;
+    }
     Ok(())
 }
+
+/// จองสินค้าชิ้นเดียวแบบมีเงื่อนไข ถ้ามีคนจองตัดหน้าไปแล้วจะได้ Conflict
+async fn reserve_product<C: ConnectionTrait>(conn: &C, product_id: Uuid) -> Result<(), ApiError> {
+    let result = products::Entity::update_many()
+        .col_expr(
+            products::Column::Status,
+            products::Column::Status.save_as(Expr::val(ProductStatus::Reserved)),
+        )
+        // update_many ไม่ผ่าน before_save จึงต้องเพิ่ม version เอง ไม่ให้ ETag เก่าเขียนทับได้
+        .col_expr(products::Column::Version, Expr::col(products::Column::Version).add(1))
+        .filter(products::Column::Id.eq(product_id))
+        .filter(products::Column::Status.eq(ProductStatus::Available))
+        .exec(conn)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(ApiError::Conflict(format!(
+            "Product with ID {} is no longer available",
+            product_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// เปลี่ยนสถานะสินค้าชิ้นเดียวในคำสั่งซื้อนี้ ยกเว้นชิ้นที่คำสั่งซื้ออื่นที่ยังไม่ยกเลิกถืออยู่
+async fn set_one_of_a_kind_status<C: ConnectionTrait>(
+    conn: &C,
+    order_id: Uuid,
+    from: &[ProductStatus],
+    to: ProductStatus,
+) -> Result<(), ApiError> {
+    let product_ids: Vec<Uuid> = order_items::Entity::find()
+        .select_only()
+        .column(order_items::Column::ProductId)
+        .filter(order_items::Column::OrderId.eq(order_id))
+        .into_tuple()
+        .all(conn)
+        .await?;
+
+    products::Entity::update_many()
+        .col_expr(
+            products::Column::Status,
+            products::Column::Status.save_as(Expr::val(to)),
+        )
+        .col_expr(products::Column::Version, Expr::col(products::Column::Version).add(1))
+        .filter(products::Column::Id.is_in(product_ids))
+        .filter(products::Column::Id.not_in_subquery(held_by_other_orders(order_id)))
+        .filter(products::Column::IsOneOfAKind.eq(true))
+        .filter(products::Column::Status.is_in(from.iter().copied()))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
+
+/// สินค้าที่อยู่ในคำสั่งซื้ออื่นซึ่งยังไม่ถูกยกเลิก
+fn held_by_other_orders(order_id: Uuid) -> SelectStatement {
+    Query::select()
+        .column((order_items::Entity, order_items::Column::ProductId))
+        .from(order_items::Entity)
+        .inner_join(
+            orders::Entity,
+            Expr::col((orders::Entity, orders::Column::Id))
+                .equals((order_items::Entity, order_items::Column::OrderId)),
+        )
+        .and_where(Expr::col((order_items::Entity, order_items::Column::OrderId)).ne(order_id))
+        .and_where(
+            Expr::col((orders::Entity, orders::Column::Status))
+                .ne(OrderStatus::Cancelled.to_string()),
+        )
+        .to_owned()
+}
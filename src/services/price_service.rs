@@ -0,0 +1,320 @@
+use crate::entity::sea_orm_active_enums::{PriceChangeReason, PriceScheduleStatus};
+use crate::entity::{price_schedules, product_price_history, products};
+use crate::error::ApiError;
+use crate::services::notification::NotificationChannel;
+use crate::services::{alert_service, product_service};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{entity::*, query::*, ConnectionTrait, DatabaseConnection, TransactionTrait};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// ไทม์ไลน์ราคาของสินค้า ใช้ตรวจสอบเวลามีข้อโต้แย้งเรื่องราคา
+#[derive(Serialize)]
+pub struct PriceTimeline {
+    pub product_id: Uuid,
+    pub current_price: Decimal,
+    pub history: Vec<product_price_history::Model>,
+    pub schedules: Vec<price_schedules::Model>,
+}
+
+/// บันทึกการเปลี่ยนราคาหนึ่งครั้ง ไม่บันทึกถ้าราคาไม่ได้เปลี่ยนจริง
+pub async fn record_price_change<C: ConnectionTrait>(
+    conn: &C,
+    product_id: Uuid,
+    old_price: Decimal,
+    new_price: Decimal,
+    reason: PriceChangeReason,
+    schedule_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    if old_price == new_price {
+        return Ok(());
+    }
+
+    let entry = product_price_history::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        product_id: Set(product_id),
+        old_price: Set(old_price),
+        new_price: Set(new_price),
+        reason: Set(reason),
+        schedule_id: Set(schedule_id),
+        created_at: Set(Utc::now()),
+    };
+    entry.insert(conn).await?;
+
+    Ok(())
+}
+
+pub async fn get_price_timeline(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+) -> Result<PriceTimeline, ApiError> {
+    let product = products::Entity::find_by_id(product_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Product with ID {} not found", product_id)))?;
+
+    let history = product_price_history::Entity::find()
+        .filter(product_price_history::Column::ProductId.eq(product_id))
+        .order_by_asc(product_price_history::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let schedules = price_schedules::Entity::find()
+        .filter(price_schedules::Column::ProductId.eq(product_id))
+        .order_by_asc(price_schedules::Column::StartsAt)
+        .all(db)
+        .await?;
+
+    Ok(PriceTimeline {
+        product_id,
+        current_price: product.price,
+        history,
+        schedules,
+    })
+}
+
+/// ตั้งราคาลดล่วงหน้า ช่วงเวลาต้องไม่ซ้อนกับรายการอื่นที่ยังไม่จบ
+pub async fn schedule_price(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    sale_price: Decimal,
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+) -> Result<price_schedules::Model, ApiError> {
+    product_service::get_product_by_id(db, product_id).await?;
+
+    if sale_price < Decimal::ZERO {
+        return Err(ApiError::ValidationError("sale_price must not be negative".to_string()));
+    }
+    if ends_at.is_some_and(|ends_at| ends_at <= starts_at) {
+        return Err(ApiError::ValidationError("ends_at must be after starts_at".to_string()));
+    }
+    if ends_at.is_some_and(|ends_at| ends_at <= Utc::now()) {
+        return Err(ApiError::ValidationError("ends_at must be in the future".to_string()));
+    }
+
+    let pending = price_schedules::Entity::find()
+        .filter(price_schedules::Column::ProductId.eq(product_id))
+        .filter(
+            price_schedules::Column::Status
+                .is_in([PriceScheduleStatus::Scheduled, PriceScheduleStatus::Active]),
+        )
+        .all(db)
+        .await?;
+
+    let overlaps = pending.iter().any(|other| {
+        let starts_before_other_ends = other.ends_at.is_none_or(|other_end| starts_at < other_end);
+        let ends_after_other_starts = ends_at.is_none_or(|end| end > other.starts_at);
+        starts_before_other_ends && ends_after_other_starts
+    });
+    if overlaps {
+        return Err(ApiError::Conflict(
+            "Schedule overlaps an existing price schedule".to_string(),
+        ));
+    }
+
+    let schedule = price_schedules::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        product_id: Set(product_id),
+        sale_price: Set(sale_price),
+        starts_at: Set(starts_at),
+        ends_at: Set(ends_at),
+        original_price: Set(None),
+        status: Set(PriceScheduleStatus::Scheduled),
+        created_at: Set(Utc::now()),
+    };
+
+    schedule.insert(db).await.map_err(ApiError::from)
+}
+
+/// ยกเลิกราคาที่ตั้งไว้ ถ้ากำลังใช้อยู่จะคืนราคาเดิมทันที
+pub async fn cancel_schedule(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    schedule_id: Uuid,
+) -> Result<price_schedules::Model, ApiError> {
+    let txn = db.begin().await?;
+    // ล็อกไว้กัน background job เริ่มหรือจบราคาลดพร้อมกับที่ยกเลิก
+    let schedule = price_schedules::Entity::find_by_id(schedule_id)
+        .filter(price_schedules::Column::ProductId.eq(product_id))
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Price schedule with ID {} not found", schedule_id))
+        })?;
+
+    let schedule = match schedule.status {
+        PriceScheduleStatus::Scheduled => {
+            set_schedule_status(&txn, schedule, PriceScheduleStatus::Cancelled).await?
+        }
+        PriceScheduleStatus::Active => {
+            end_sale(&txn, schedule, PriceScheduleStatus::Cancelled).await?
+        }
+        _ => {
+            return Err(ApiError::Conflict(
+                "Price schedule has already finished".to_string(),
+            ))
+        }
+    };
+    txn.commit().await?;
+
+    Ok(schedule)
+}
+
+/// เริ่มและจบราคาลดที่ถึงเวลาแล้ว เรียกจาก background job คืนจำนวนรายการที่เปลี่ยน
+///
+/// ราคาลดที่เริ่มแล้วจะแจ้งคนที่ตั้งราคาเป้าหมายไว้เหมือนการแก้ราคาเอง
+/// รายการที่ทำไม่สำเร็จจะ log ไว้แล้วทำรายการถัดไปต่อ
+pub async fn apply_due_schedules(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+) -> Result<usize, ApiError> {
+    let now = Utc::now();
+    let mut applied = 0;
+
+    // จบรายการที่หมดเวลาก่อน เพื่อไม่ให้ชนกับรายการถัดไปที่เริ่มพอดี
+    let ending = price_schedules::Entity::find()
+        .filter(price_schedules::Column::Status.eq(PriceScheduleStatus::Active))
+        .filter(price_schedules::Column::EndsAt.lte(now))
+        .all(db)
+        .await?;
+    let starting = price_schedules::Entity::find()
+        .filter(price_schedules::Column::Status.eq(PriceScheduleStatus::Scheduled))
+        .filter(price_schedules::Column::StartsAt.lte(now))
+        .order_by_asc(price_schedules::Column::StartsAt)
+        .all(db)
+        .await?;
+
+    let due = ending
+        .into_iter()
+        .chain(starting)
+        .map(|schedule| (schedule.id, schedule.status));
+    for (schedule_id, status) in due {
+        match apply_schedule(db, channel, schedule_id, status).await {
+            Ok(true) => applied += 1,
+            Ok(false) => {}
+            Err(e) => eprintln!("Failed to apply price schedule {}: {}", schedule_id, e),
+        }
+    }
+
+    Ok(applied)
+}
+
+/// ล็อก schedule ใหม่ใน transaction แล้วทำต่อเมื่อสถานะยังเป็น `expected` เท่านั้น
+/// Active คือถึงเวลาจบ Scheduled คือถึงเวลาเริ่ม คืนค่า false ถ้าถูกยกเลิกไประหว่างนั้น
+async fn apply_schedule(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    schedule_id: Uuid,
+    expected: PriceScheduleStatus,
+) -> Result<bool, ApiError> {
+    let txn = db.begin().await?;
+    let Some(schedule) = price_schedules::Entity::find_by_id(schedule_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+    else {
+        return Ok(false);
+    };
+    if schedule.status != expected {
+        return Ok(false);
+    }
+
+    let before = products::Entity::find_by_id(schedule.product_id).one(&txn).await?;
+    if expected == PriceScheduleStatus::Active {
+        end_sale(&txn, schedule, PriceScheduleStatus::Completed).await?;
+    } else {
+        start_sale(&txn, schedule).await?;
+    }
+    txn.commit().await?;
+
+    if let Some(before) = before {
+        alert_service::notify_products_changed(db, channel, &[before]).await;
+    }
+    Ok(true)
+}
+
+async fn start_sale<C: ConnectionTrait>(
+    conn: &C,
+    schedule: price_schedules::Model,
+) -> Result<(), ApiError> {
+    let Some(product) = products::Entity::find_by_id(schedule.product_id).one(conn).await? else {
+        set_schedule_status(conn, schedule, PriceScheduleStatus::Cancelled).await?;
+        return Ok(());
+    };
+
+    // หมดเวลาไปแล้วก่อนจะได้เริ่ม (เช่น server ดับ) ก็ข้ามไปเลย
+    if schedule.ends_at.is_some_and(|ends_at| ends_at <= Utc::now()) {
+        set_schedule_status(conn, schedule, PriceScheduleStatus::Completed).await?;
+        return Ok(());
+    }
+
+    let original_price = product.price;
+    set_product_price(conn, product, schedule.sale_price).await?;
+    record_price_change(
+        conn,
+        schedule.product_id,
+        original_price,
+        schedule.sale_price,
+        PriceChangeReason::SaleStart,
+        Some(schedule.id),
+    )
+    .await?;
+
+    let mut active_model: price_schedules::ActiveModel = schedule.into();
+    active_model.original_price = Set(Some(original_price));
+    active_model.status = Set(PriceScheduleStatus::Active);
+    active_model.update(conn).await?;
+
+    Ok(())
+}
+
+/// คืนราคาเดิม ยกเว้นมีคนแก้ราคาเองระหว่างช่วงลดราคา ก็ให้ราคาที่แก้นั้นอยู่ต่อ
+async fn end_sale<C: ConnectionTrait>(
+    conn: &C,
+    schedule: price_schedules::Model,
+    final_status: PriceScheduleStatus,
+) -> Result<price_schedules::Model, ApiError> {
+    if let (Some(product), Some(original_price)) = (
+        products::Entity::find_by_id(schedule.product_id).one(conn).await?,
+        schedule.original_price,
+    ) {
+        if product.price == schedule.sale_price {
+            set_product_price(conn, product, original_price).await?;
+            record_price_change(
+                conn,
+                schedule.product_id,
+                schedule.sale_price,
+                original_price,
+                PriceChangeReason::SaleEnd,
+                Some(schedule.id),
+            )
+            .await?;
+        }
+    }
+
+    set_schedule_status(conn, schedule, final_status).await
+}
+
+async fn set_product_price<C: ConnectionTrait>(
+    conn: &C,
+    product: products::Model,
+    price: Decimal,
+) -> Result<(), ApiError> {
+    let mut active_model: products::ActiveModel = product.into();
+    active_model.price = Set(price);
+    active_model.update(conn).await?;
+    Ok(())
+}
+
+async fn set_schedule_status<C: ConnectionTrait>(
+    conn: &C,
+    schedule: price_schedules::Model,
+    status: PriceScheduleStatus,
+) -> Result<price_schedules::Model, ApiError> {
+    let mut active_model: price_schedules::ActiveModel = schedule.into();
+    active_model.status = Set(status);
+    active_model.update(conn).await.map_err(ApiError::from)
+}
@@ -1,12 +1,115 @@
-use crate::entity::products;
+use crate::entity::{cart, products};
+use crate::entity::sea_orm_active_enums::{PriceChangeReason, ProductStatus, ProductType};
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use sea_orm::{
+    sea_query::NullOrdering, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, Order, QueryFilter, QueryOrder, QuerySelect, Select, Set, TransactionTrait,
+};
 use uuid::Uuid;
 use crate::error::ApiError;
+use crate::services::attribute_service::{self, AttributeFilter, Facet, ProductAttributeResponse};
+use crate::services::image_service::{self, ProductImageResponse};
+use crate::services::alert_service;
+use crate::services::bundle_service::{self, BundleResponse};
+use crate::services::media_store::MediaStore;
+use crate::services::notification::NotificationChannel;
+use crate::services::price_service;
+use serde::{Deserialize, Serialize};
 
+/// ลำดับการแสดงสินค้าในหน้ารายการ
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSort {
+    #[default]
+    Newest,
+    Rating,
+    PriceAsc,
+    PriceDesc,
+}
 
-pub async fn get_all_products(db: &DatabaseConnection) -> Result<Vec<products::Model>, ApiError> {
-    products::Entity::find()
+/// ข้อมูลสินค้าที่ส่งให้ client พร้อมรูปภาพและ attribute
+#[derive(Serialize)]
+pub struct ProductResponse {
+    #[serde(flatten)]
+    pub product: products::Model,
+    pub images: Vec<ProductImageResponse>,
+    pub attributes: Vec<ProductAttributeResponse>,
+    /// มีเฉพาะสินค้าที่เป็นชุด
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<BundleResponse>,
+}
+
+/// หน้ารายการสินค้า พร้อม facet สำหรับทำแถบตัวกรอง
+#[derive(Serialize)]
+pub struct ProductListResponse {
+    pub items: Vec<ProductResponse>,
+    pub facets: Vec<Facet>,
+}
+
+/// แนบรูปภาพและ attribute ให้สินค้าหลายตัวพร้อมกัน โดยดึงแต่ละอย่างในคำสั่งเดียว
+pub async fn build_product_responses(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    products: Vec<products::Model>,
+) -> Result<Vec<ProductResponse>, ApiError> {
+    let product_ids: Vec<Uuid> = products.iter().map(|product| product.id).collect();
+    let mut images = image_service::images_for_products(db, &product_ids).await?;
+    let mut attributes = attribute_service::attributes_for_products(db, &product_ids).await?;
+    let mut bundles = bundle_service::bundles_for_products(db, &products).await?;
+
+    Ok(products
+        .into_iter()
+        .map(|product| {
+            let images = images
+                .remove(&product.id)
+                .unwrap_or_default()
+                .iter()
+                .map(|image| ProductImageResponse::new(image, store))
+                .collect();
+            let attributes = attributes.remove(&product.id).unwrap_or_default();
+            let bundle = bundles.remove(&product.id);
+            ProductResponse { product, images, attributes, bundle }
+        })
+        .collect())
+}
+
+pub async fn build_product_response(
+    db: &DatabaseConnection,
+    store: &dyn MediaStore,
+    product: products::Model,
+) -> Result<ProductResponse, ApiError> {
+    build_product_responses(db, store, vec![product])
+        .await?
+        .pop()
+        .ok_or(ApiError::InternalServerError)
+}
+
+/// สินค้าที่แสดงในแคตตาล็อก (ไม่รวมที่ถูกเก็บ) ใช้เป็นฐานของรายการและการนับ facet
+pub fn catalog_query() -> Select<products::Entity> {
+    products::Entity::find().filter(products::Column::DeletedAt.is_null())
+}
+
+pub async fn get_all_products(
+    db: &DatabaseConnection,
+    sort: ProductSort,
+    category_id: Option<Uuid>,
+    filters: &[AttributeFilter],
+) -> Result<Vec<products::Model>, ApiError> {
+    let mut query = attribute_service::apply_filters(catalog_query(), filters);
+    if let Some(category_id) = category_id {
+        query = query.filter(products::Column::CategoryId.eq(category_id));
+    }
+    let query = match sort {
+        ProductSort::Newest => query.order_by_desc(products::Column::CreatedAt),
+        // สินค้าที่ยังไม่มีรีวิวไปอยู่ท้ายสุด
+        ProductSort::Rating => query
+            .order_by_with_nulls(products::Column::RatingAverage, Order::Desc, NullOrdering::Last)
+            .order_by_desc(products::Column::ReviewCount),
+        ProductSort::PriceAsc => query.order_by_asc(products::Column::Price),
+        ProductSort::PriceDesc => query.order_by_desc(products::Column::Price),
+    };
+
+    query
         .all(db)
         .await
         .map_err(|_| ApiError::DatabaseError("Failed to fetch products".to_string()))
@@ -17,25 +120,118 @@ pub async fn get_product_by_id(
     product_id: Uuid,
 ) -> Result<products::Model, ApiError> {
     products::Entity::find_by_id(product_id)
+        .filter(products::Column::DeletedAt.is_null())
         .one(db)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Product with ID {} not found", product_id)))
 }
 
+/// ตรวจว่าสินค้ายังขายได้ในจำนวนที่ต้องการ ใช้ทั้งตอนใส่ตะกร้าและตอนสั่งซื้อ
+pub fn ensure_purchasable(product: &products::Model, quantity: i32) -> Result<(), ApiError> {
+    if product.deleted_at.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "Product with ID {} has been archived",
+            product.id
+        )));
+    }
+    if product.status != ProductStatus::Available {
+        return Err(ApiError::Conflict(format!(
+            "Product with ID {} is not available",
+            product.id
+        )));
+    }
+    if product.is_one_of_a_kind && quantity > 1 {
+        return Err(ApiError::ValidationError(format!(
+            "Product with ID {} is one of a kind and can only be bought once",
+            product.id
+        )));
+    }
+
+    Ok(())
+}
+
+/// ตรวจจำนวนในตะกร้ากับกฎของสินค้า: ไม่น้อยกว่าขั้นต่ำ ไม่เกินสูงสุด และเพิ่มจากขั้นต่ำทีละ step
+pub fn ensure_quantity_allowed(product: &products::Model, quantity: i32) -> Result<(), ApiError> {
+    if quantity < product.min_quantity {
+        return Err(ApiError::ValidationError(format!(
+            "Quantity of product with ID {} must be at least {}",
+            product.id, product.min_quantity
+        )));
+    }
+    if let Some(max_quantity) = product.max_quantity {
+        if quantity > max_quantity {
+            return Err(ApiError::ValidationError(format!(
+                "Quantity of product with ID {} must be at most {}",
+                product.id, max_quantity
+            )));
+        }
+    }
+    if (quantity - product.min_quantity) % product.quantity_step != 0 {
+        return Err(ApiError::ValidationError(format!(
+            "Quantity of product with ID {} must be {} plus a multiple of {}",
+            product.id, product.min_quantity, product.quantity_step
+        )));
+    }
+
+    Ok(())
+}
+
+/// ตรวจกฎจำนวนก่อนบันทึกลงสินค้า
+pub fn validate_quantity_rules(
+    min_quantity: i32,
+    max_quantity: Option<i32>,
+    quantity_step: i32,
+) -> Result<(), ApiError> {
+    if min_quantity < 1 || quantity_step < 1 {
+        return Err(ApiError::ValidationError(
+            "min_quantity and quantity_step must be at least 1".to_string(),
+        ));
+    }
+    if max_quantity.is_some_and(|max| max < min_quantity) {
+        return Err(ApiError::ValidationError(
+            "max_quantity must not be less than min_quantity".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// ข้อมูลสินค้าใหม่
+pub struct NewProduct {
+    pub name: String,
+    pub description: Option<String>,
+    pub price: Decimal,
+    pub status: Option<ProductStatus>,
+    pub is_one_of_a_kind: bool,
+    pub sku: Option<String>,
+    pub product_type: ProductType,
+    /// ร้านเจ้าของสินค้า None คือสินค้าของแพลตฟอร์ม
+    pub vendor_id: Option<Uuid>,
+    pub min_quantity: i32,
+    pub max_quantity: Option<i32>,
+    pub quantity_step: i32,
+}
+
 pub async fn create_product(
     db: &DatabaseConnection,
-    name: String,
-    description: Option<String>,
-    price: Decimal,
-    status: Option<String>,
+    product: NewProduct,
 ) -> Result<products::Model, ApiError> {
+    validate_quantity_rules(product.min_quantity, product.max_quantity, product.quantity_step)?;
     let new_product = products::ActiveModel {
         id: Set(Uuid::new_v4()),
-        name: Set(name),
-        description: Set(description),
-        price: Set(price),
-        status: Set(status.unwrap_or_else(|| "available".to_string())),
+        name: Set(product.name),
+        description: Set(product.description),
+        price: Set(product.price),
+        status: Set(product.status.unwrap_or(ProductStatus::Available)),
         created_at: Set(chrono::Utc::now()),
+        is_one_of_a_kind: Set(product.is_one_of_a_kind),
+        deleted_at: Set(None),
+        sku: Set(product.sku),
+        product_type: Set(product.product_type),
+        vendor_id: Set(product.vendor_id),
+        min_quantity: Set(product.min_quantity),
+        max_quantity: Set(product.max_quantity),
+        quantity_step: Set(product.quantity_step),
+        ..Default::default()
     };
 
     new_product
@@ -43,66 +239,185 @@ pub async fn create_product(
         .await
         .map_err(|_| ApiError::DatabaseError("Failed to create product".to_string()))
 }
-pub async fn update_product(
-    db: &DatabaseConnection,
+/// ค่าที่จะแก้ในสินค้า ฟิลด์ที่เป็น None จะคงค่าเดิมไว้
+/// ฟิลด์ที่ล้างค่าได้ใช้ `Some(None)` แทนการตั้งเป็น NULL
+#[derive(Default)]
+pub struct ProductUpdate {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub price: Option<Decimal>,
+    pub is_one_of_a_kind: Option<bool>,
+    pub sku: Option<Option<String>>,
+    pub product_type: Option<ProductType>,
+    pub min_quantity: Option<i32>,
+    pub max_quantity: Option<Option<i32>>,
+    pub quantity_step: Option<i32>,
+}
+
+/// ล็อกแถวสินค้าไว้จนจบ transaction แล้วตรวจ version กับที่ client ส่งมาใน If-Match
+///
+/// `expected_version` เป็น None เมื่อ client ส่ง `If-Match: *`
+async fn lock_product<C: ConnectionTrait>(
+    conn: &C,
     product_id: Uuid,
-    name: Option<String>,
-    description: Option<String>,
-    price: Option<Decimal>,
+    expected_version: Option<i32>,
 ) -> Result<products::Model, ApiError> {
     let product = products::Entity::find_by_id(product_id)
-        .one(db)
+        .filter(products::Column::DeletedAt.is_null())
+        .lock_exclusive()
+        .one(conn)
         .await?
         .ok_or_else(|| ApiError::NotFound(format!("Product with ID {} not found", product_id)))?;
 
-    let mut active_model: products::ActiveModel = product.into();
-    if let Some(name) = name {
+    match expected_version {
+        Some(version) if version != product.version => Err(ApiError::PreconditionFailed(format!(
+            "Product with ID {} has been modified (current version is {})",
+            product_id, product.version
+        ))),
+        _ => Ok(product),
+    }
+}
+
+pub async fn update_product(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    product_id: Uuid,
+    expected_version: Option<i32>,
+    changes: ProductUpdate,
+) -> Result<products::Model, ApiError> {
+    let txn = db.begin().await?;
+    let before = lock_product(&txn, product_id, expected_version).await?;
+
+    let mut active_model: products::ActiveModel = before.clone().into();
+    if let Some(name) = changes.name {
         active_model.name = Set(name);
     }
-    if let Some(description) = description {
-        active_model.description = Set(Some(description));
+    if let Some(description) = changes.description {
+        active_model.description = Set(description);
     }
-    if let Some(price) = price {
+    if let Some(price) = changes.price {
         active_model.price = Set(price);
     }
+    if let Some(is_one_of_a_kind) = changes.is_one_of_a_kind {
+        active_model.is_one_of_a_kind = Set(is_one_of_a_kind);
+    }
+    if let Some(sku) = changes.sku {
+        active_model.sku = Set(sku);
+    }
+    if let Some(product_type) = changes.product_type {
+        active_model.product_type = Set(product_type);
+    }
+    if changes.min_quantity.is_some()
+        || changes.max_quantity.is_some()
+        || changes.quantity_step.is_some()
+    {
+        let min_quantity = changes.min_quantity.unwrap_or(before.min_quantity);
+        let max_quantity = changes.max_quantity.unwrap_or(before.max_quantity);
+        let quantity_step = changes.quantity_step.unwrap_or(before.quantity_step);
+        validate_quantity_rules(min_quantity, max_quantity, quantity_step)?;
+        active_model.min_quantity = Set(min_quantity);
+        active_model.max_quantity = Set(max_quantity);
+        active_model.quantity_step = Set(quantity_step);
+    }
 
-    active_model
-        .update(db)
+    let product = active_model
+        .update(&txn)
         .await
-        .map_err(|_| ApiError::DatabaseError("Failed to update product".to_string()))
+        .map_err(|_| ApiError::DatabaseError("Failed to update product".to_string()))?;
+
+    // เก็บประวัติราคาทุกครั้งที่ราคาเปลี่ยน
+    price_service::record_price_change(
+        &txn,
+        product.id,
+        before.price,
+        product.price,
+        PriceChangeReason::Manual,
+        None,
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    alert_service::notify_product_change(db, channel, &before, &product).await;
+    Ok(product)
 }
 
 pub async fn update_product_status(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    product_id: Uuid,
+    expected_version: Option<i32>,
+    new_status: ProductStatus,
+) -> Result<products::Model, ApiError> {
+    let txn = db.begin().await?;
+    let before = lock_product(&txn, product_id, expected_version).await?;
+
+    let mut active_model: products::ActiveModel = before.clone().into();
+    active_model.status = Set(new_status);
+    let product = active_model
+        .update(&txn)
+        .await
+        .map_err(|_| ApiError::DatabaseError("Failed to update product status".to_string()))?;
+    txn.commit().await?;
+
+    // สินค้ากลับมาขายได้ ส่งแจ้งเตือนให้คนที่รออยู่
+    alert_service::notify_product_change(db, channel, &before, &product).await;
+    Ok(product)
+}
+
+/// เก็บสินค้าเข้าคลังถาวร (soft delete) ประวัติคำสั่งซื้อยังอ้างถึงสินค้าได้ตามเดิม
+pub async fn delete_product(
     db: &DatabaseConnection,
     product_id: Uuid,
-    new_status: String,
+    expected_version: Option<i32>,
 ) -> Result<(), ApiError> {
-    let product = products::Entity::find_by_id(product_id)
-        .one(db)
-        .await?
-        .ok_or_else(|| ApiError::NotFound(format!("Product with ID {} not found", product_id)))?;
+    let txn = db.begin().await?;
+    let product = lock_product(&txn, product_id, expected_version).await?;
 
     let mut active_model: products::ActiveModel = product.into();
-    active_model.status = Set(new_status);
+    active_model.deleted_at = Set(Some(chrono::Utc::now()));
     active_model
-        .update(db)
+        .update(&txn)
         .await
-        .map_err(|_| ApiError::DatabaseError("Failed to update product status".to_string()))?;
+        .map_err(|_| ApiError::DatabaseError("Failed to delete product".to_string()))?;
+
+    // สินค้าที่ถูกเก็บแล้วไม่ควรค้างอยู่ในตะกร้าของใคร
+    cart::Entity::delete_many()
+        .filter(cart::Column::ProductId.eq(product_id))
+        .exec(&txn)
+        .await?;
 
+    txn.commit().await?;
     Ok(())
 }
 
-pub async fn delete_product(db: &DatabaseConnection, product_id: Uuid) -> Result<(), ApiError> {
+pub async fn get_archived_products(
+    db: &DatabaseConnection,
+) -> Result<Vec<products::Model>, ApiError> {
+    products::Entity::find()
+        .filter(products::Column::DeletedAt.is_not_null())
+        .all(db)
+        .await
+        .map_err(|_| ApiError::DatabaseError("Failed to fetch products".to_string()))
+}
+
+/// นำสินค้าที่ถูกเก็บกลับมาแสดงในแคตตาล็อก
+pub async fn restore_product(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+) -> Result<products::Model, ApiError> {
     let product = products::Entity::find_by_id(product_id)
+        .filter(products::Column::DeletedAt.is_not_null())
         .one(db)
         .await?
-        .ok_or_else(|| ApiError::NotFound(format!("Product with ID {} not found", product_id)))?;
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Archived product with ID {} not found", product_id))
+        })?;
 
-    let active_model: products::ActiveModel = product.into();
+    let mut active_model: products::ActiveModel = product.into();
+    active_model.deleted_at = Set(None);
     active_model
-        .delete(db)
+        .update(db)
         .await
-        .map_err(|_| ApiError::DatabaseError("Failed to delete product".to_string()))?;
-
-    Ok(())
+        .map_err(|_| ApiError::DatabaseError("Failed to restore product".to_string()))
 }
@@ -0,0 +1,214 @@
+use crate::entity::sea_orm_active_enums::{ProductStatus, RelationSource};
+use crate::entity::{product_relations, products};
+use crate::error::ApiError;
+use crate::services::order_service::OrderStatus;
+use crate::services::product_service;
+use sea_orm::{
+    entity::*, query::*, DatabaseConnection, DbBackend, FromQueryResult, Statement,
+    TransactionTrait,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// นับจำนวนคำสั่งซื้อที่มีสินค้าสองตัวอยู่ด้วยกัน แล้วเก็บเฉพาะ N อันดับแรกของแต่ละสินค้า
+/// ไม่นับคำสั่งซื้อที่ถูกยกเลิก และไม่นับแถว component ที่แตกออกมาจากชุดสินค้า
+const CO_PURCHASE_SQL: &str = r#"
+SELECT product_id, related_product_id, score
+FROM (
+    SELECT
+        a.product_id AS product_id,
+        b.product_id AS related_product_id,
+        COUNT(DISTINCT a.order_id) AS score,
+        ROW_NUMBER() OVER (
+            PARTITION BY a.product_id
+            ORDER BY COUNT(DISTINCT a.order_id) DESC, b.product_id
+        ) AS rank
+    FROM order_items a
+    JOIN order_items b ON b.order_id = a.order_id AND b.product_id <> a.product_id
+    JOIN orders o ON o.id = a.order_id
+    WHERE o.status <> $1
+      AND a.parent_item_id IS NULL
+      AND b.parent_item_id IS NULL
+    GROUP BY a.product_id, b.product_id
+) ranked
+WHERE rank <= $2
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct CoPurchase {
+    product_id: Uuid,
+    related_product_id: Uuid,
+    score: i64,
+}
+
+/// คำนวณสินค้าที่ซื้อร่วมกันใหม่ทั้งหมดจากประวัติคำสั่งซื้อ เรียกจาก background job
+///
+/// แทนที่เฉพาะแถว computed คู่ที่ถูก pin ไว้จะไม่ถูกแตะ คืนจำนวนแถวที่บันทึก
+pub async fn recompute_relations(db: &DatabaseConnection, limit: usize) -> Result<usize, ApiError> {
+    let pairs = CoPurchase::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        CO_PURCHASE_SQL,
+        [OrderStatus::Cancelled.to_string().into(), (limit as i64).into()],
+    ))
+    .all(db)
+    .await?;
+
+    let txn = db.begin().await?;
+    let pinned: HashSet<(Uuid, Uuid)> = product_relations::Entity::find()
+        .filter(product_relations::Column::Source.eq(RelationSource::Pinned))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|relation| (relation.product_id, relation.related_product_id))
+        .collect();
+
+    product_relations::Entity::delete_many()
+        .filter(product_relations::Column::Source.eq(RelationSource::Computed))
+        .exec(&txn)
+        .await?;
+
+    let now = chrono::Utc::now();
+    let rows: Vec<product_relations::ActiveModel> = pairs
+        .into_iter()
+        .filter(|pair| !pinned.contains(&(pair.product_id, pair.related_product_id)))
+        .map(|pair| product_relations::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            product_id: Set(pair.product_id),
+            related_product_id: Set(pair.related_product_id),
+            source: Set(RelationSource::Computed),
+            score: Set(pair.score as i32),
+            position: Set(0),
+            updated_at: Set(now),
+        })
+        .collect();
+
+    let saved = rows.len();
+    // แบ่งเป็นชุดเพื่อไม่ให้จำนวน parameter ต่อคำสั่งเกินขีดจำกัดของ Postgres
+    for chunk in rows.chunks(1000) {
+        product_relations::Entity::insert_many(chunk.to_vec())
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+
+    Ok(saved)
+}
+
+/// สินค้าแนะนำของสินค้าหนึ่งตัว: ที่ pin ไว้ก่อนตามลำดับ แล้วจึงเป็นที่ซื้อร่วมกันบ่อยที่สุด
+///
+/// ข้ามสินค้าที่ถูกเก็บหรือขายไม่ได้ในตอนนี้
+pub async fn get_related_products(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    limit: usize,
+) -> Result<Vec<products::Model>, ApiError> {
+    product_service::get_product_by_id(db, product_id).await?;
+
+    let mut relations = product_relations::Entity::find()
+        .filter(product_relations::Column::ProductId.eq(product_id))
+        .all(db)
+        .await?;
+    relations.sort_by_key(|relation| match relation.source {
+        RelationSource::Pinned => (0, relation.position),
+        RelationSource::Computed => (1, -relation.score),
+    });
+
+    let related_ids: Vec<Uuid> = relations
+        .iter()
+        .map(|relation| relation.related_product_id)
+        .collect();
+    let mut candidates: HashMap<Uuid, products::Model> = product_service::catalog_query()
+        .filter(products::Column::Id.is_in(related_ids.clone()))
+        .filter(products::Column::Status.eq(ProductStatus::Available))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|product| (product.id, product))
+        .collect();
+
+    Ok(related_ids
+        .into_iter()
+        .filter_map(|id| candidates.remove(&id))
+        .take(limit)
+        .collect())
+}
+
+pub async fn get_pinned_relations(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+) -> Result<Vec<product_relations::Model>, ApiError> {
+    product_relations::Entity::find()
+        .filter(product_relations::Column::ProductId.eq(product_id))
+        .filter(product_relations::Column::Source.eq(RelationSource::Pinned))
+        .order_by_asc(product_relations::Column::Position)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// pin สินค้าแนะนำเอง ถ้าคู่นี้เคยถูกคำนวณไว้จะเปลี่ยนเป็น pinned แทน
+pub async fn pin_related_product(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    related_product_id: Uuid,
+    position: i32,
+) -> Result<product_relations::Model, ApiError> {
+    if product_id == related_product_id {
+        return Err(ApiError::ValidationError(
+            "A product cannot be related to itself".to_string(),
+        ));
+    }
+    product_service::get_product_by_id(db, product_id).await?;
+    product_service::get_product_by_id(db, related_product_id).await?;
+
+    let existing = product_relations::Entity::find()
+        .filter(product_relations::Column::ProductId.eq(product_id))
+        .filter(product_relations::Column::RelatedProductId.eq(related_product_id))
+        .one(db)
+        .await?;
+
+    let relation = match existing {
+        Some(relation) => {
+            let mut active_model: product_relations::ActiveModel = relation.into();
+            active_model.source = Set(RelationSource::Pinned);
+            active_model.position = Set(position);
+            active_model.updated_at = Set(chrono::Utc::now());
+            active_model.update(db).await?
+        }
+        None => {
+            product_relations::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                product_id: Set(product_id),
+                related_product_id: Set(related_product_id),
+                source: Set(RelationSource::Pinned),
+                score: Set(0),
+                position: Set(position),
+                updated_at: Set(chrono::Utc::now()),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+    Ok(relation)
+}
+
+/// ยกเลิก pin ถ้ายังซื้อร่วมกันอยู่ job รอบถัดไปจะเพิ่มกลับมาเป็น computed เอง
+pub async fn unpin_related_product(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    related_product_id: Uuid,
+) -> Result<(), ApiError> {
+    let result = product_relations::Entity::delete_many()
+        .filter(product_relations::Column::ProductId.eq(product_id))
+        .filter(product_relations::Column::RelatedProductId.eq(related_product_id))
+        .filter(product_relations::Column::Source.eq(RelationSource::Pinned))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(ApiError::NotFound(format!(
+            "Pinned related product {} not found",
+            related_product_id
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use crate::entity::sea_orm_active_enums::ReviewStatus;
+use crate::entity::{order_items, orders, products, reviews};
+use crate::error::ApiError;
+use crate::services::order_service::OrderStatus;
+use crate::services::product_service;
+use rust_decimal::Decimal;
+use sea_orm::{
+    entity::*, query::*, sea_query::{Expr, Func}, ConnectionTrait, DatabaseConnection,
+    TransactionTrait,
+};
+use uuid::Uuid;
+
+/// เขียนรีวิวได้เฉพาะลูกค้าที่มีคำสั่งซื้อที่ส่งถึงแล้วและมีสินค้านี้อยู่
+pub async fn create_review(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    product_id: Uuid,
+    rating: i32,
+    body: Option<String>,
+) -> Result<reviews::Model, ApiError> {
+    product_service::get_product_by_id(db, product_id).await?;
+
+    let purchased = order_items::Entity::find()
+        .inner_join(orders::Entity)
+        .filter(orders::Column::UserId.eq(user_id))
+        .filter(orders::Column::Status.eq(OrderStatus::Delivered.to_string()))
+        .filter(order_items::Column::ProductId.eq(product_id))
+        .one(db)
+        .await?
+        .is_some();
+    if !purchased {
+        return Err(ApiError::Forbidden(
+            "Only customers with a delivered order for this product can review it".to_string(),
+        ));
+    }
+
+    let existing = reviews::Entity::find()
+        .filter(reviews::Column::ProductId.eq(product_id))
+        .filter(reviews::Column::UserId.eq(user_id))
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        return Err(ApiError::Conflict("You have already reviewed this product".to_string()));
+    }
+
+    let review = reviews::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        product_id: Set(product_id),
+        user_id: Set(user_id),
+        rating: Set(rating),
+        body: Set(body),
+        status: Set(ReviewStatus::Pending),
+        moderation_note: Set(None),
+        moderated_by: Set(None),
+        moderated_at: Set(None),
+        created_at: Set(chrono::Utc::now()),
+    };
+
+    review.insert(db).await.map_err(ApiError::from)
+}
+
+/// รีวิวที่อนุมัติแล้วของสินค้า ใหม่สุดก่อน
+pub async fn get_product_reviews(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+) -> Result<Vec<reviews::Model>, ApiError> {
+    reviews::Entity::find()
+        .filter(reviews::Column::ProductId.eq(product_id))
+        .filter(reviews::Column::Status.eq(ReviewStatus::Approved))
+        .order_by_desc(reviews::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// รายการรีวิวสำหรับผู้ดูแล เรียงเก่าสุดก่อนเพื่อให้ตรวจตามคิว
+pub async fn get_reviews_by_status(
+    db: &DatabaseConnection,
+    status: ReviewStatus,
+) -> Result<Vec<reviews::Model>, ApiError> {
+    reviews::Entity::find()
+        .filter(reviews::Column::Status.eq(status))
+        .order_by_asc(reviews::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// เปลี่ยนสถานะรีวิวแล้วคำนวณคะแนนเฉลี่ยของสินค้าใหม่
+pub async fn moderate_review(
+    db: &DatabaseConnection,
+    review_id: Uuid,
+    moderator_id: Uuid,
+    status: ReviewStatus,
+    note: Option<String>,
+) -> Result<reviews::Model, ApiError> {
+    let review = reviews::Entity::find_by_id(review_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Review with ID {} not found", review_id)))?;
+
+    let txn = db.begin().await?;
+    let mut active_model: reviews::ActiveModel = review.into();
+    active_model.status = Set(status);
+    active_model.moderation_note = Set(note);
+    active_model.moderated_by = Set(Some(moderator_id));
+    active_model.moderated_at = Set(Some(chrono::Utc::now()));
+    let review = active_model.update(&txn).await?;
+
+    refresh_product_rating(&txn, review.product_id).await?;
+    txn.commit().await?;
+
+    Ok(review)
+}
+
+/// คำนวณคะแนนเฉลี่ยและจำนวนรีวิวที่อนุมัติแล้ว แล้วเก็บไว้ที่ตาราง products
+async fn refresh_product_rating<C: ConnectionTrait>(
+    conn: &C,
+    product_id: Uuid,
+) -> Result<(), ApiError> {
+    let (average, count): (Option<Decimal>, i64) = reviews::Entity::find()
+        .select_only()
+        .column_as(Expr::expr(Func::avg(Expr::col(reviews::Column::Rating))), "average")
+        .column_as(Expr::expr(Func::count(Expr::col(reviews::Column::Id))), "count")
+        .filter(reviews::Column::ProductId.eq(product_id))
+        .filter(reviews::Column::Status.eq(ReviewStatus::Approved))
+        .into_tuple()
+        .one(conn)
+        .await?
+        .unwrap_or((None, 0));
+
+    products::Entity::update_many()
+        .col_expr(
+            products::Column::RatingAverage,
+            Expr::value(average.map(|average| average.round_dp(2))),
+        )
+        .col_expr(products::Column::ReviewCount, Expr::value(count as i32))
+        .col_expr(products::Column::Version, Expr::col(products::Column::Version).add(1))
+        .filter(products::Column::Id.eq(product_id))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
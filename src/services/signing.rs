@@ -0,0 +1,24 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// ลายเซ็น HMAC-SHA256 ของข้อความในรูป hex ใช้กับลิงก์และ token ที่ไม่ต้องเก็บในฐานข้อมูล
+pub fn sign(secret: &str, message: &[u8]) -> String {
+    hex::encode(mac(secret, message).finalize().into_bytes())
+}
+
+/// เทียบลายเซ็นแบบ constant time ลายเซ็นที่ไม่ใช่ hex ถือว่าไม่ถูกต้อง
+pub fn verify(secret: &str, message: &[u8], signature: &str) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    mac(secret, message).verify_slice(&signature).is_ok()
+}
+
+fn mac(secret: &str, message: &[u8]) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac
+}
@@ -0,0 +1,324 @@
+use crate::config::AppConfig;
+use crate::entity::{categories, category_translations, product_translations, products};
+use crate::error::ApiError;
+use crate::services::{category_service, product_service};
+use sea_orm::{entity::*, query::*, DatabaseConnection};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// ข้อความที่ยังไม่ได้แปลของสินค้าหรือหมวดหมู่หนึ่งรายการ
+#[derive(Serialize)]
+pub struct MissingTranslation {
+    pub entity_type: &'static str,
+    pub id: Uuid,
+    pub name: String,
+    pub missing_locales: Vec<String>,
+}
+
+/// แทนชื่อและคำอธิบายสินค้าด้วยคำแปลของภาษาที่ขอ
+///
+/// ถ้าไม่มีคำแปลจะคงข้อความภาษาเริ่มต้นจากตาราง products ไว้
+pub async fn localize_products(
+    db: &DatabaseConnection,
+    products: &mut [products::Model],
+    locale: &str,
+    config: &AppConfig,
+) -> Result<(), ApiError> {
+    if locale == config.default_locale || products.is_empty() {
+        return Ok(());
+    }
+
+    let mut translations: HashMap<Uuid, product_translations::Model> =
+        product_translations::Entity::find()
+            .filter(
+                product_translations::Column::ProductId
+                    .is_in(products.iter().map(|product| product.id)),
+            )
+            .filter(product_translations::Column::Locale.eq(locale))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|translation| (translation.product_id, translation))
+            .collect();
+
+    for product in products.iter_mut() {
+        if let Some(translation) = translations.remove(&product.id) {
+            product.name = translation.name;
+            if translation.description.is_some() {
+                product.description = translation.description;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn localize_categories(
+    db: &DatabaseConnection,
+    categories: &mut [categories::Model],
+    locale: &str,
+    config: &AppConfig,
+) -> Result<(), ApiError> {
+    if locale == config.default_locale || categories.is_empty() {
+        return Ok(());
+    }
+
+    let mut translations: HashMap<Uuid, category_translations::Model> =
+        category_translations::Entity::find()
+            .filter(
+                category_translations::Column::CategoryId
+                    .is_in(categories.iter().map(|category| category.id)),
+            )
+            .filter(category_translations::Column::Locale.eq(locale))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|translation| (translation.category_id, translation))
+            .collect();
+
+    for category in categories.iter_mut() {
+        if let Some(translation) = translations.remove(&category.id) {
+            category.name = translation.name;
+            if translation.description.is_some() {
+                category.description = translation.description;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn get_product_translations(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+) -> Result<Vec<product_translations::Model>, ApiError> {
+    product_service::get_product_by_id(db, product_id).await?;
+
+    product_translations::Entity::find()
+        .filter(product_translations::Column::ProductId.eq(product_id))
+        .order_by_asc(product_translations::Column::Locale)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// เพิ่มหรือแก้คำแปลของสินค้าในภาษาหนึ่ง
+pub async fn upsert_product_translation(
+    db: &DatabaseConnection,
+    config: &AppConfig,
+    product_id: Uuid,
+    locale: &str,
+    name: String,
+    description: Option<String>,
+) -> Result<product_translations::Model, ApiError> {
+    ensure_translatable_locale(config, locale)?;
+    product_service::get_product_by_id(db, product_id).await?;
+
+    let existing = product_translations::Entity::find()
+        .filter(product_translations::Column::ProductId.eq(product_id))
+        .filter(product_translations::Column::Locale.eq(locale))
+        .one(db)
+        .await?;
+
+    let translation = match existing {
+        Some(translation) => {
+            let mut active_model: product_translations::ActiveModel = translation.into();
+            active_model.name = Set(name);
+            active_model.description = Set(description);
+            active_model.updated_at = Set(chrono::Utc::now());
+            active_model.update(db).await?
+        }
+        None => {
+            product_translations::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                product_id: Set(product_id),
+                locale: Set(locale.to_string()),
+                name: Set(name),
+                description: Set(description),
+                updated_at: Set(chrono::Utc::now()),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+    Ok(translation)
+}
+
+pub async fn delete_product_translation(
+    db: &DatabaseConnection,
+    product_id: Uuid,
+    locale: &str,
+) -> Result<(), ApiError> {
+    let result = product_translations::Entity::delete_many()
+        .filter(product_translations::Column::ProductId.eq(product_id))
+        .filter(product_translations::Column::Locale.eq(locale))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(ApiError::NotFound(format!(
+            "Translation {} for product {} not found",
+            locale, product_id
+        )));
+    }
+    Ok(())
+}
+
+pub async fn get_category_translations(
+    db: &DatabaseConnection,
+    category_id: Uuid,
+) -> Result<Vec<category_translations::Model>, ApiError> {
+    category_service::get_category_by_id(db, category_id).await?;
+
+    category_translations::Entity::find()
+        .filter(category_translations::Column::CategoryId.eq(category_id))
+        .order_by_asc(category_translations::Column::Locale)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// เพิ่มหรือแก้คำแปลของหมวดหมู่ในภาษาหนึ่ง
+pub async fn upsert_category_translation(
+    db: &DatabaseConnection,
+    config: &AppConfig,
+    category_id: Uuid,
+    locale: &str,
+    name: String,
+    description: Option<String>,
+) -> Result<category_translations::Model, ApiError> {
+    ensure_translatable_locale(config, locale)?;
+    category_service::get_category_by_id(db, category_id).await?;
+
+    let existing = category_translations::Entity::find()
+        .filter(category_translations::Column::CategoryId.eq(category_id))
+        .filter(category_translations::Column::Locale.eq(locale))
+        .one(db)
+        .await?;
+
+    let translation = match existing {
+        Some(translation) => {
+            let mut active_model: category_translations::ActiveModel = translation.into();
+            active_model.name = Set(name);
+            active_model.description = Set(description);
+            active_model.updated_at = Set(chrono::Utc::now());
+            active_model.update(db).await?
+        }
+        None => {
+            category_translations::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                category_id: Set(category_id),
+                locale: Set(locale.to_string()),
+                name: Set(name),
+                description: Set(description),
+                updated_at: Set(chrono::Utc::now()),
+            }
+            .insert(db)
+            .await?
+        }
+    };
+    Ok(translation)
+}
+
+pub async fn delete_category_translation(
+    db: &DatabaseConnection,
+    category_id: Uuid,
+    locale: &str,
+) -> Result<(), ApiError> {
+    let result = category_translations::Entity::delete_many()
+        .filter(category_translations::Column::CategoryId.eq(category_id))
+        .filter(category_translations::Column::Locale.eq(locale))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(ApiError::NotFound(format!(
+            "Translation {} for category {} not found",
+            locale, category_id
+        )));
+    }
+    Ok(())
+}
+
+/// รายการสินค้าและหมวดหมู่ที่ยังขาดคำแปล ถ้าไม่ระบุภาษาจะตรวจทุกภาษาที่รองรับ
+pub async fn get_missing_translations(
+    db: &DatabaseConnection,
+    config: &AppConfig,
+    locale: Option<&str>,
+) -> Result<Vec<MissingTranslation>, ApiError> {
+    let locales: Vec<String> = match locale {
+        Some(locale) => {
+            ensure_translatable_locale(config, locale)?;
+            vec![locale.to_string()]
+        }
+        None => config
+            .supported_locales
+            .iter()
+            .filter(|locale| **locale != config.default_locale)
+            .cloned()
+            .collect(),
+    };
+
+    let product_done: HashSet<(Uuid, String)> = product_translations::Entity::find()
+        .filter(product_translations::Column::Locale.is_in(locales.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|translation| (translation.product_id, translation.locale))
+        .collect();
+    let category_done: HashSet<(Uuid, String)> = category_translations::Entity::find()
+        .filter(category_translations::Column::Locale.is_in(locales.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|translation| (translation.category_id, translation.locale))
+        .collect();
+
+    let missing_for = |id: Uuid, done: &HashSet<(Uuid, String)>| -> Vec<String> {
+        locales
+            .iter()
+            .filter(|locale| !done.contains(&(id, (*locale).clone())))
+            .cloned()
+            .collect()
+    };
+
+    let mut missing = Vec::new();
+    for product in product_service::catalog_query()
+        .order_by_asc(products::Column::Name)
+        .all(db)
+        .await?
+    {
+        let missing_locales = missing_for(product.id, &product_done);
+        if !missing_locales.is_empty() {
+            missing.push(MissingTranslation {
+                entity_type: "product",
+                id: product.id,
+                name: product.name,
+                missing_locales,
+            });
+        }
+    }
+    for category in category_service::list_categories(db).await? {
+        let missing_locales = missing_for(category.id, &category_done);
+        if !missing_locales.is_empty() {
+            missing.push(MissingTranslation {
+                entity_type: "category",
+                id: category.id,
+                name: category.name,
+                missing_locales,
+            });
+        }
+    }
+    Ok(missing)
+}
+
+/// ภาษาเริ่มต้นเก็บในตารางหลักอยู่แล้ว จึงแปลได้เฉพาะภาษาอื่นที่รองรับ
+fn ensure_translatable_locale(config: &AppConfig, locale: &str) -> Result<(), ApiError> {
+    if locale == config.default_locale {
+        return Err(ApiError::ValidationError(format!(
+            "{} is the default locale; edit the product or category itself instead",
+            locale
+        )));
+    }
+    if !config.supported_locales.iter().any(|supported| supported == locale) {
+        return Err(ApiError::ValidationError(format!("Unsupported locale: {}", locale)));
+    }
+    Ok(())
+}
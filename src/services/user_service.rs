@@ -0,0 +1,158 @@
+use crate::entity::sea_orm_active_enums::UserRole;
+use crate::entity::users;
+use crate::error::ApiError;
+use crate::services::notification::{Notification, NotificationChannel};
+use crate::services::signing;
+use chrono::{DateTime, Utc};
+use sea_orm::{entity::*, query::*, DatabaseConnection};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// ข้อมูลโปรไฟล์ที่ส่งให้ client (ไม่มีรหัสผ่าน)
+#[derive(Serialize)]
+pub struct UserProfileResponse {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: UserRole,
+    pub email_verified: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<users::Model> for UserProfileResponse {
+    fn from(user: users::Model) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            role: user.role,
+            email_verified: user.email_verified_at.is_some(),
+            created_at: user.created_at,
+        }
+    }
+}
+
+/// ค่าที่จะแก้ในโปรไฟล์ ฟิลด์ที่เป็น None จะคงค่าเดิมไว้
+#[derive(Default)]
+pub struct UserProfileUpdate {
+    pub username: Option<String>,
+    pub email: Option<String>,
+}
+
+pub async fn get_profile(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<UserProfileResponse, ApiError> {
+    Ok(find_user(db, user_id).await?.into())
+}
+
+/// แก้โปรไฟล์ของตัวเอง username และ email ต้องไม่ซ้ำกับผู้ใช้อื่น
+/// เปลี่ยนอีเมลแล้วต้องยืนยันอีเมลใหม่อีกครั้ง
+pub async fn update_profile(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    changes: UserProfileUpdate,
+) -> Result<UserProfileResponse, ApiError> {
+    let user = find_user(db, user_id).await?;
+
+    let mut active_model: users::ActiveModel = user.into();
+    if let Some(username) = changes.username {
+        ensure_unique(db, user_id, users::Column::Username, &username).await?;
+        active_model.username = Set(username);
+    }
+    if let Some(email) = changes.email {
+        ensure_unique(db, user_id, users::Column::Email, &email).await?;
+        if email != *active_model.email.as_ref() {
+            active_model.email_verified_at = Set(None);
+        }
+        active_model.email = Set(email);
+    }
+
+    Ok(active_model.update(db).await?.into())
+}
+
+/// ส่ง token ยืนยันอีเมลไปที่อีเมลของผู้ใช้ token ผูกกับอีเมล ณ ตอนที่ขอ
+pub async fn request_email_verification(
+    db: &DatabaseConnection,
+    channel: &dyn NotificationChannel,
+    secret: &str,
+    ttl_secs: i64,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    let user = find_user(db, user_id).await?;
+    if user.email_verified_at.is_some() {
+        return Err(ApiError::Conflict("Email is already verified".to_string()));
+    }
+
+    let expires = (Utc::now() + chrono::Duration::seconds(ttl_secs)).timestamp();
+    let token = format!(
+        "{}.{}.{}",
+        user.id,
+        expires,
+        signing::sign(secret, &verification_message(user.id, expires, &user.email))
+    );
+    let notification = Notification {
+        subject: "Verify your email".to_string(),
+        body: format!("Use this code to verify your email: {}", token),
+    };
+    channel.send(&user.email, &notification).await
+}
+
+/// ยืนยันอีเมลด้วย token ที่ส่งไป ถ้าเปลี่ยนอีเมลหลังขอ token เดิมจะใช้ไม่ได้
+pub async fn verify_email(
+    db: &DatabaseConnection,
+    secret: &str,
+    token: &str,
+) -> Result<UserProfileResponse, ApiError> {
+    let invalid = || ApiError::ValidationError("Invalid verification token".to_string());
+    let mut parts = token.splitn(3, '.');
+    let (Some(user_id), Some(expires), Some(signature)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let user_id = Uuid::parse_str(user_id).map_err(|_| invalid())?;
+    let expires: i64 = expires.parse().map_err(|_| invalid())?;
+    if Utc::now().timestamp() > expires {
+        return Err(ApiError::ValidationError("Verification token has expired".to_string()));
+    }
+
+    let user = find_user(db, user_id).await.map_err(|_| invalid())?;
+    if !signing::verify(secret, &verification_message(user.id, expires, &user.email), signature) {
+        return Err(invalid());
+    }
+    if user.email_verified_at.is_some() {
+        return Ok(user.into());
+    }
+
+    let mut active_model: users::ActiveModel = user.into();
+    active_model.email_verified_at = Set(Some(Utc::now()));
+    Ok(active_model.update(db).await?.into())
+}
+
+fn verification_message(user_id: Uuid, expires: i64, email: &str) -> Vec<u8> {
+    format!("verify-email:{}:{}:{}", user_id, expires, email).into_bytes()
+}
+
+async fn find_user(db: &DatabaseConnection, user_id: Uuid) -> Result<users::Model, ApiError> {
+    users::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("User with ID {} not found", user_id)))
+}
+
+async fn ensure_unique(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    column: users::Column,
+    value: &str,
+) -> Result<(), ApiError> {
+    let taken = users::Entity::find()
+        .filter(column.eq(value))
+        .filter(users::Column::Id.ne(user_id))
+        .one(db)
+        .await?;
+    if taken.is_some() {
+        return Err(ApiError::Conflict(format!("{} is already taken", value)));
+    }
+    Ok(())
+}
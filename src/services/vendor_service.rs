@@ -0,0 +1,421 @@
+use crate::entity::sea_orm_active_enums::LedgerEntryKind;
+use crate::entity::{order_items, orders, products, users, vendor_ledger_entries, vendor_orders, vendors};
+use crate::error::ApiError;
+use crate::services::{auth::is_admin, product_service};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{entity::*, query::*, ConnectionTrait, DatabaseConnection};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// คำสั่งซื้อย่อยของร้าน พร้อมเฉพาะแถวสินค้าของร้านนั้น (ไม่มีข้อมูลลูกค้า)
+#[derive(Serialize)]
+pub struct VendorOrderResponse {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub order_status: String,
+    pub subtotal: Decimal,
+    pub commission_rate: Decimal,
+    pub commission_amount: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub items: Vec<order_items::Model>,
+}
+
+/// สมุดบัญชีของร้าน ยอดคงเหลือคือเงินที่แพลตฟอร์มค้างจ่ายร้าน
+#[derive(Serialize)]
+pub struct LedgerResponse {
+    pub balance: Decimal,
+    pub entries: Vec<vendor_ledger_entries::Model>,
+}
+
+/// ค่าที่จะแก้ในร้านค้า ฟิลด์ที่เป็น None จะคงค่าเดิมไว้
+#[derive(Default)]
+pub struct VendorUpdate {
+    pub name: Option<String>,
+    pub commission_rate: Option<Decimal>,
+    pub is_active: Option<bool>,
+}
+
+pub async fn list_vendors(db: &DatabaseConnection) -> Result<Vec<vendors::Model>, ApiError> {
+    vendors::Entity::find()
+        .order_by_asc(vendors::Column::Name)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// เปิดร้านให้ผู้ใช้ที่มีอยู่แล้ว หนึ่งบัญชีมีได้หนึ่งร้าน
+pub async fn create_vendor(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    name: String,
+    commission_rate: Decimal,
+) -> Result<vendors::Model, ApiError> {
+    ensure_commission_rate(commission_rate)?;
+    users::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("User with ID {} not found", user_id)))?;
+    if find_vendor_for_user(db, user_id).await?.is_some() {
+        return Err(ApiError::Conflict(format!(
+            "User with ID {} already owns a vendor",
+            user_id
+        )));
+    }
+
+    let vendor = vendors::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        name: Set(name),
+        commission_rate: Set(commission_rate),
+        is_active: Set(true),
+        created_at: Set(Utc::now()),
+    };
+    vendor.insert(db).await.map_err(ApiError::from)
+}
+
+/// อัตรา commission ใหม่มีผลกับคำสั่งซื้อหลังจากนี้เท่านั้น
+pub async fn update_vendor(
+    db: &DatabaseConnection,
+    vendor_id: Uuid,
+    changes: VendorUpdate,
+) -> Result<vendors::Model, ApiError> {
+    let vendor = find_vendor(db, vendor_id).await?;
+
+    let mut active_model: vendors::ActiveModel = vendor.into();
+    if let Some(name) = changes.name {
+        active_model.name = Set(name);
+    }
+    if let Some(commission_rate) = changes.commission_rate {
+        ensure_commission_rate(commission_rate)?;
+        active_model.commission_rate = Set(commission_rate);
+    }
+    if let Some(is_active) = changes.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.update(db).await.map_err(ApiError::from)
+}
+
+pub async fn find_vendor(db: &DatabaseConnection, vendor_id: Uuid) -> Result<vendors::Model, ApiError> {
+    vendors::Entity::find_by_id(vendor_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Vendor with ID {} not found", vendor_id)))
+}
+
+async fn find_vendor_for_user(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<Option<vendors::Model>, ApiError> {
+    vendors::Entity::find()
+        .filter(vendors::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// ร้านของผู้ใช้ที่เรียก ต้องเป็นร้านที่ยังเปิดอยู่
+pub async fn require_vendor(db: &DatabaseConnection, user_id: Uuid) -> Result<vendors::Model, ApiError> {
+    match find_vendor_for_user(db, user_id).await? {
+        Some(vendor) if vendor.is_active => Ok(vendor),
+        Some(_) => Err(ApiError::Forbidden("Vendor account is inactive".to_string())),
+        None => Err(ApiError::Forbidden("Vendor account required".to_string())),
+    }
+}
+
+/// ผู้ดูแลระบบแก้ได้ทุกสินค้า ร้านค้าแก้ได้เฉพาะสินค้าของตัวเอง
+pub async fn authorize_product(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    product_id: Uuid,
+) -> Result<(), ApiError> {
+    if is_admin(db, user_id).await? {
+        return Ok(());
+    }
+
+    let vendor = require_vendor(db, user_id).await?;
+    let product = products::Entity::find_by_id(product_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Product with ID {} not found", product_id)))?;
+    if product.vendor_id != Some(vendor.id) {
+        return Err(ApiError::Forbidden(
+            "Vendors can only manage their own products".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// เจ้าของสินค้าที่สร้างใหม่: ผู้ดูแลระบบสร้างสินค้าของแพลตฟอร์ม (None) ร้านค้าสร้างของร้านตัวเอง
+pub async fn owner_for_new_product(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<Option<Uuid>, ApiError> {
+    if is_admin(db, user_id).await? {
+        return Ok(None);
+    }
+    Ok(Some(require_vendor(db, user_id).await?.id))
+}
+
+pub async fn list_vendor_products(
+    db: &DatabaseConnection,
+    vendor_id: Uuid,
+) -> Result<Vec<products::Model>, ApiError> {
+    product_service::catalog_query()
+        .filter(products::Column::VendorId.eq(vendor_id))
+        .order_by_desc(products::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// แยกยอดของแต่ละร้านออกเป็นคำสั่งซื้อย่อย คืนค่า vendor_id -> vendor_order_id
+///
+/// `subtotals` คือยอดรวมของสินค้าแต่ละร้านในคำสั่งซื้อ สินค้าของแพลตฟอร์มไม่ต้องส่งมา
+pub async fn create_vendor_orders<C: ConnectionTrait>(
+    conn: &C,
+    order_id: Uuid,
+    subtotals: &HashMap<Uuid, Decimal>,
+) -> Result<HashMap<Uuid, Uuid>, ApiError> {
+    if subtotals.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let vendors = vendors::Entity::find()
+        .filter(vendors::Column::Id.is_in(subtotals.keys().copied().collect::<Vec<_>>()))
+        .all(conn)
+        .await?;
+
+    let now = Utc::now();
+    let mut vendor_orders = HashMap::with_capacity(vendors.len());
+    for vendor in vendors {
+        if !vendor.is_active {
+            return Err(ApiError::Conflict(format!(
+                "Vendor {} is not accepting orders",
+                vendor.name
+            )));
+        }
+
+        let subtotal = subtotals[&vendor.id];
+        let vendor_order = vendor_orders::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            order_id: Set(order_id),
+            vendor_id: Set(vendor.id),
+            subtotal: Set(subtotal),
+            commission_rate: Set(vendor.commission_rate),
+            commission_amount: Set((subtotal * vendor.commission_rate).round_dp(2)),
+            created_at: Set(now),
+        }
+        .insert(conn)
+        .await?;
+        vendor_orders.insert(vendor.id, vendor_order.id);
+    }
+    Ok(vendor_orders)
+}
+
+/// ลงบัญชียอดขายและ commission ของทุกร้านในคำสั่งซื้อ เรียกตอนชำระเงิน (เรียกซ้ำได้)
+pub async fn record_sales<C: ConnectionTrait>(conn: &C, order_id: Uuid) -> Result<(), ApiError> {
+    let vendor_orders = vendor_orders_of(conn, order_id).await?;
+    let recorded = entry_kinds(conn, &vendor_orders).await?;
+
+    let now = Utc::now();
+    let mut entries = Vec::new();
+    for vendor_order in vendor_orders {
+        if recorded.contains(&(vendor_order.id, LedgerEntryKind::Sale)) {
+            continue;
+        }
+        entries.push(ledger_entry(&vendor_order, LedgerEntryKind::Sale, vendor_order.subtotal, now));
+        entries.push(ledger_entry(
+            &vendor_order,
+            LedgerEntryKind::Commission,
+            -vendor_order.commission_amount,
+            now,
+        ));
+    }
+    if !entries.is_empty() {
+        vendor_ledger_entries::Entity::insert_many(entries).exec(conn).await?;
+    }
+    Ok(())
+}
+
+/// กลับรายการยอดสุทธิของร้านเมื่อคำสั่งซื้อที่ลงบัญชีไปแล้วถูกยกเลิก
+pub async fn reverse_sales<C: ConnectionTrait>(conn: &C, order_id: Uuid) -> Result<(), ApiError> {
+    let vendor_orders = vendor_orders_of(conn, order_id).await?;
+    let recorded = entry_kinds(conn, &vendor_orders).await?;
+
+    let now = Utc::now();
+    let entries: Vec<_> = vendor_orders
+        .iter()
+        .filter(|vendor_order| {
+            recorded.contains(&(vendor_order.id, LedgerEntryKind::Sale))
+                && !recorded.contains(&(vendor_order.id, LedgerEntryKind::Reversal))
+        })
+        .map(|vendor_order| {
+            let net = vendor_order.subtotal - vendor_order.commission_amount;
+            ledger_entry(vendor_order, LedgerEntryKind::Reversal, -net, now)
+        })
+        .collect();
+    if !entries.is_empty() {
+        vendor_ledger_entries::Entity::insert_many(entries).exec(conn).await?;
+    }
+    Ok(())
+}
+
+/// คำสั่งซื้อย่อยของร้าน ใหม่สุดก่อน
+pub async fn list_vendor_orders(
+    db: &DatabaseConnection,
+    vendor_id: Uuid,
+) -> Result<Vec<VendorOrderResponse>, ApiError> {
+    let vendor_orders = vendor_orders::Entity::find()
+        .find_also_related(orders::Entity)
+        .filter(vendor_orders::Column::VendorId.eq(vendor_id))
+        .order_by_desc(vendor_orders::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let ids: Vec<Uuid> = vendor_orders.iter().map(|(vendor_order, _)| vendor_order.id).collect();
+    let mut items: HashMap<Uuid, Vec<order_items::Model>> = HashMap::new();
+    for item in order_items::Entity::find()
+        .filter(order_items::Column::VendorOrderId.is_in(ids))
+        .all(db)
+        .await?
+    {
+        if let Some(vendor_order_id) = item.vendor_order_id {
+            items.entry(vendor_order_id).or_default().push(item);
+        }
+    }
+
+    Ok(vendor_orders
+        .into_iter()
+        .filter_map(|(vendor_order, order)| {
+            let order = order?;
+            Some(VendorOrderResponse {
+                items: items.remove(&vendor_order.id).unwrap_or_default(),
+                id: vendor_order.id,
+                order_id: vendor_order.order_id,
+                order_status: order.status,
+                subtotal: vendor_order.subtotal,
+                commission_rate: vendor_order.commission_rate,
+                commission_amount: vendor_order.commission_amount,
+                created_at: vendor_order.created_at,
+            })
+        })
+        .collect())
+}
+
+pub async fn get_ledger(db: &DatabaseConnection, vendor_id: Uuid) -> Result<LedgerResponse, ApiError> {
+    let entries = vendor_ledger_entries::Entity::find()
+        .filter(vendor_ledger_entries::Column::VendorId.eq(vendor_id))
+        .order_by_desc(vendor_ledger_entries::Column::CreatedAt)
+        .all(db)
+        .await?;
+    let balance = entries.iter().map(|entry| entry.amount).sum();
+    Ok(LedgerResponse { balance, entries })
+}
+
+/// บันทึกการจ่ายเงินให้ร้าน จ่ายได้ไม่เกินยอดคงเหลือ
+pub async fn record_payout(
+    db: &DatabaseConnection,
+    vendor_id: Uuid,
+    amount: Decimal,
+    reference: Option<String>,
+) -> Result<vendor_ledger_entries::Model, ApiError> {
+    if amount <= Decimal::ZERO {
+        return Err(ApiError::ValidationError(
+            "Payout amount must be greater than zero".to_string(),
+        ));
+    }
+
+    let txn = db.begin().await?;
+    // ล็อกแถวร้านไว้ กันการจ่ายซ้อนกันจนยอดติดลบ
+    vendors::Entity::find_by_id(vendor_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Vendor with ID {} not found", vendor_id)))?;
+
+    let balance: Decimal = vendor_ledger_entries::Entity::find()
+        .filter(vendor_ledger_entries::Column::VendorId.eq(vendor_id))
+        .all(&txn)
+        .await?
+        .iter()
+        .map(|entry| entry.amount)
+        .sum();
+    if amount > balance {
+        return Err(ApiError::Conflict(format!(
+            "Payout of {} exceeds the vendor balance of {}",
+            amount, balance
+        )));
+    }
+
+    let entry = vendor_ledger_entries::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        vendor_id: Set(vendor_id),
+        vendor_order_id: Set(None),
+        kind: Set(LedgerEntryKind::Payout),
+        amount: Set(-amount),
+        reference: Set(reference),
+        created_at: Set(Utc::now()),
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+    Ok(entry)
+}
+
+fn ensure_commission_rate(rate: Decimal) -> Result<(), ApiError> {
+    if rate < Decimal::ZERO || rate > Decimal::ONE {
+        return Err(ApiError::ValidationError(
+            "commission_rate must be between 0 and 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn vendor_orders_of<C: ConnectionTrait>(
+    conn: &C,
+    order_id: Uuid,
+) -> Result<Vec<vendor_orders::Model>, ApiError> {
+    vendor_orders::Entity::find()
+        .filter(vendor_orders::Column::OrderId.eq(order_id))
+        .all(conn)
+        .await
+        .map_err(ApiError::from)
+}
+
+/// ชนิดรายการที่ลงบัญชีไปแล้วของแต่ละคำสั่งซื้อย่อย ใช้กันลงซ้ำ
+async fn entry_kinds<C: ConnectionTrait>(
+    conn: &C,
+    vendor_orders: &[vendor_orders::Model],
+) -> Result<HashSet<(Uuid, LedgerEntryKind)>, ApiError> {
+    let ids: Vec<Uuid> = vendor_orders.iter().map(|vendor_order| vendor_order.id).collect();
+    if ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    Ok(vendor_ledger_entries::Entity::find()
+        .filter(vendor_ledger_entries::Column::VendorOrderId.is_in(ids))
+        .all(conn)
+        .await?
+        .into_iter()
+        .filter_map(|entry| Some((entry.vendor_order_id?, entry.kind)))
+        .collect())
+}
+
+fn ledger_entry(
+    vendor_order: &vendor_orders::Model,
+    kind: LedgerEntryKind,
+    amount: Decimal,
+    created_at: DateTime<Utc>,
+) -> vendor_ledger_entries::ActiveModel {
+    vendor_ledger_entries::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        vendor_id: Set(vendor_order.vendor_id),
+        vendor_order_id: Set(Some(vendor_order.id)),
+        kind: Set(kind),
+        amount: Set(amount),
+        reference: Set(None),
+        created_at: Set(created_at),
+    }
+}
@@ -0,0 +1,269 @@
+use crate::entity::sea_orm_active_enums::ProductStatus;
+use crate::entity::{cart, products, wishlist_items, wishlists};
+use crate::error::ApiError;
+use crate::services::{cart_service, product_service};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{entity::*, query::*, DatabaseConnection};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// สินค้าในรายการโปรดพร้อมราคาและสถานะปัจจุบัน
+#[derive(Serialize)]
+pub struct WishlistItemResponse {
+    pub product_id: Uuid,
+    pub name: String,
+    pub price: Decimal,
+    pub status: ProductStatus,
+    /// false เมื่อสินค้าถูกเก็บหรือไม่อยู่ในสถานะขายได้
+    pub is_available: bool,
+    pub added_at: DateTime<Utc>,
+}
+
+/// รายการโปรดที่ส่งให้ client ไม่มี user_id เพื่อให้ใช้กับลิงก์สาธารณะได้ด้วย
+#[derive(Serialize)]
+pub struct WishlistResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub share_token: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub items: Vec<WishlistItemResponse>,
+}
+
+pub async fn list_wishlists(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<Vec<wishlists::Model>, ApiError> {
+    wishlists::Entity::find()
+        .filter(wishlists::Column::UserId.eq(user_id))
+        .order_by_asc(wishlists::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(ApiError::from)
+}
+
+pub async fn create_wishlist(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    name: String,
+) -> Result<wishlists::Model, ApiError> {
+    let wishlist = wishlists::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        name: Set(name),
+        share_token: Set(None),
+        created_at: Set(Utc::now()),
+    };
+
+    wishlist.insert(db).await.map_err(ApiError::from)
+}
+
+pub async fn rename_wishlist(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+    name: String,
+) -> Result<wishlists::Model, ApiError> {
+    let wishlist = find_wishlist(db, user_id, wishlist_id).await?;
+
+    let mut active_model: wishlists::ActiveModel = wishlist.into();
+    active_model.name = Set(name);
+    active_model.update(db).await.map_err(ApiError::from)
+}
+
+pub async fn delete_wishlist(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+) -> Result<(), ApiError> {
+    let wishlist = find_wishlist(db, user_id, wishlist_id).await?;
+    wishlists::Entity::delete_by_id(wishlist.id).exec(db).await?;
+    Ok(())
+}
+
+pub async fn get_wishlist(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+) -> Result<WishlistResponse, ApiError> {
+    let wishlist = find_wishlist(db, user_id, wishlist_id).await?;
+    build_response(db, wishlist).await
+}
+
+/// เปิดดูรายการโปรดผ่านลิงก์สาธารณะ ไม่ต้องเข้าสู่ระบบ
+pub async fn get_shared_wishlist(
+    db: &DatabaseConnection,
+    share_token: &str,
+) -> Result<WishlistResponse, ApiError> {
+    let wishlist = wishlists::Entity::find()
+        .filter(wishlists::Column::ShareToken.eq(share_token))
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Shared wishlist not found".to_string()))?;
+    build_response(db, wishlist).await
+}
+
+/// เพิ่มสินค้าเข้ารายการโปรด ถ้ามีอยู่แล้วจะไม่เพิ่มซ้ำ
+pub async fn add_item(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+    product_id: Uuid,
+) -> Result<WishlistResponse, ApiError> {
+    let wishlist = find_wishlist(db, user_id, wishlist_id).await?;
+    product_service::get_product_by_id(db, product_id).await?;
+
+    let existing = wishlist_items::Entity::find()
+        .filter(wishlist_items::Column::WishlistId.eq(wishlist.id))
+        .filter(wishlist_items::Column::ProductId.eq(product_id))
+        .one(db)
+        .await?;
+    if existing.is_none() {
+        let item = wishlist_items::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            wishlist_id: Set(wishlist.id),
+            product_id: Set(product_id),
+            added_at: Set(Utc::now()),
+        };
+        item.insert(db).await?;
+    }
+
+    build_response(db, wishlist).await
+}
+
+pub async fn remove_item(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+    product_id: Uuid,
+) -> Result<(), ApiError> {
+    let wishlist = find_wishlist(db, user_id, wishlist_id).await?;
+
+    let result = wishlist_items::Entity::delete_many()
+        .filter(wishlist_items::Column::WishlistId.eq(wishlist.id))
+        .filter(wishlist_items::Column::ProductId.eq(product_id))
+        .exec(db)
+        .await?;
+    if result.rows_affected == 0 {
+        return Err(ApiError::NotFound(format!(
+            "Product with ID {} is not in this wishlist",
+            product_id
+        )));
+    }
+    Ok(())
+}
+
+/// ย้ายสินค้าจากรายการโปรดไปตะกร้า ใช้กฎเดียวกับการใส่ตะกร้าปกติ
+pub async fn move_to_cart(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+    product_id: Uuid,
+    quantity: i32,
+) -> Result<cart::Model, ApiError> {
+    if quantity < 1 {
+        return Err(ApiError::ValidationError(
+            "Quantity must be at least 1".to_string(),
+        ));
+    }
+    let wishlist = find_wishlist(db, user_id, wishlist_id).await?;
+    let product = product_service::get_product_by_id(db, product_id).await?;
+
+    // ใส่ตะกร้าและเอาออกจากรายการโปรดใน transaction เดียว
+    // ถ้าใส่ตะกร้าไม่ผ่าน (เช่นสินค้าหมด) สินค้ายังอยู่ในรายการโปรดตามเดิม
+    let txn = db.begin().await?;
+    let item = wishlist_items::Entity::find()
+        .filter(wishlist_items::Column::WishlistId.eq(wishlist.id))
+        .filter(wishlist_items::Column::ProductId.eq(product_id))
+        .one(&txn)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("Product with ID {} is not in this wishlist", product_id))
+        })?;
+    let cart = cart_service::active_cart(&txn, user_id).await?;
+    let cart_item = cart_service::add_line(&txn, &cart, &product, quantity).await?;
+    wishlist_items::Entity::delete_by_id(item.id).exec(&txn).await?;
+    txn.commit().await?;
+
+    Ok(cart_item)
+}
+
+/// สร้างลิงก์แชร์ ถ้าเคยสร้างไว้แล้วจะใช้ token เดิม
+pub async fn share_wishlist(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+) -> Result<wishlists::Model, ApiError> {
+    let wishlist = find_wishlist(db, user_id, wishlist_id).await?;
+    if wishlist.share_token.is_some() {
+        return Ok(wishlist);
+    }
+
+    let mut active_model: wishlists::ActiveModel = wishlist.into();
+    active_model.share_token = Set(Some(generate_share_token()));
+    active_model.update(db).await.map_err(ApiError::from)
+}
+
+/// ปิดลิงก์แชร์ ลิงก์เดิมจะใช้ไม่ได้อีก
+pub async fn unshare_wishlist(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+) -> Result<wishlists::Model, ApiError> {
+    let wishlist = find_wishlist(db, user_id, wishlist_id).await?;
+
+    let mut active_model: wishlists::ActiveModel = wishlist.into();
+    active_model.share_token = Set(None);
+    active_model.update(db).await.map_err(ApiError::from)
+}
+
+async fn find_wishlist(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    wishlist_id: Uuid,
+) -> Result<wishlists::Model, ApiError> {
+    wishlists::Entity::find_by_id(wishlist_id)
+        .filter(wishlists::Column::UserId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Wishlist with ID {} not found", wishlist_id)))
+}
+
+async fn build_response(
+    db: &DatabaseConnection,
+    wishlist: wishlists::Model,
+) -> Result<WishlistResponse, ApiError> {
+    let items = wishlist_items::Entity::find()
+        .find_also_related(products::Entity)
+        .filter(wishlist_items::Column::WishlistId.eq(wishlist.id))
+        .order_by_desc(wishlist_items::Column::AddedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(item, product)| {
+            let product = product?;
+            Some(WishlistItemResponse {
+                product_id: product.id,
+                is_available: product.deleted_at.is_none()
+                    && product.status == ProductStatus::Available,
+                name: product.name,
+                price: product.price,
+                status: product.status,
+                added_at: item.added_at,
+            })
+        })
+        .collect();
+
+    Ok(WishlistResponse {
+        id: wishlist.id,
+        name: wishlist.name,
+        share_token: wishlist.share_token,
+        created_at: wishlist.created_at,
+        items,
+    })
+}
+
+/// token แบบสุ่ม 244 บิต (UUID v4 สองตัว) เดาไม่ได้และใช้ใน URL ได้ตรง ๆ
+fn generate_share_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}